@@ -1,4 +1,3 @@
-use rat_nexus::prelude::*;
 use rat_nexus::define_app;
 use crate::pages::{Menu, MonitorPage, TimerPage, ParticlesPage, FlappyPage, TicTacToePage, LogPage};
 
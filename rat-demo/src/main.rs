@@ -5,13 +5,55 @@ mod pages;
 mod agent;
 mod app;
 
-use rat_nexus::Application;
-use crate::app::Root;
+use rat_nexus::{Action, Application, CommandPalette, Theme};
+use ratatui::style::Color;
+use crossterm::event::{KeyCode, KeyModifiers};
+use crate::app::{Root, RootRoute};
+
+/// Custom action bound to F2.
+struct ToggleSelectionMode;
 
 fn main() -> anyhow::Result<()> {
     let app = Application::new();
 
     app.run(move |cx| {
+        cx.set_title("rat-demo");
+        // Listed in every page's footer and help overlay, in this order
+        cx.bind_global(KeyCode::Char('m'), Action::navigate_to(RootRoute::Menu));
+        cx.bind_global(KeyCode::Char('q'), Action::Quit);
+        cx.enable_debug_overlay(KeyCode::F(12));
+        // Selection mode: hand the mouse back to the terminal to select and copy text
+        cx.bind_global(KeyCode::F(2), Action::custom(ToggleSelectionMode));
+        cx.on_action(|_: &ToggleSelectionMode, cx| cx.set_mouse_capture(!cx.mouse_capture()));
+        // F3 cycles high-contrast and no-color rendering
+        cx.enable_accessibility_toggle(KeyCode::F(3));
+        // Accent variants of the terminal palette, cycled with `t`
+        for (name, color) in [("green", Color::Green), ("magenta", Color::Magenta), ("yellow", Color::Yellow)] {
+            cx.register_theme(Theme::terminal().with_primary(name, color));
+        }
+        // Ctrl+P: jump to any page or run a command by name
+        let mut palette = CommandPalette::new();
+        for route in [
+            RootRoute::Menu, RootRoute::Monitor, RootRoute::Timer, RootRoute::Particles,
+            RootRoute::Flappy, RootRoute::Tictactoe, RootRoute::Logs,
+        ] {
+            palette = palette.action(format!("Go to {route}"), Action::navigate_to(route));
+        }
+        let palette = palette
+            .command("Toggle theme", |cx| {
+                cx.next_theme();
+                None
+            })
+            .command("Toggle selection mode", |cx| {
+                cx.set_mouse_capture(!cx.mouse_capture());
+                None
+            })
+            .command("Cycle accessibility mode", |cx| {
+                cx.set_accessibility_mode(cx.accessibility_mode().next());
+                None
+            })
+            .action("Quit", Action::Quit);
+        cx.enable_command_palette(palette, KeyCode::Char('p'), KeyModifiers::CONTROL);
         cx.set_root(Root::new())?;
         Ok(())
     })
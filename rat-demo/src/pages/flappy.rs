@@ -8,12 +8,25 @@ use ratatui::{
     text::Line,
 };
 use crossterm::event::KeyCode;
+use std::time::Duration;
+use crate::app::RootRoute;
 
 const GRAVITY: f64 = 0.22;
 const JUMP_FORCE: f64 = 1.6;
 const PIPE_GAP: f64 = 15.0;
 const PIPE_WIDTH: f64 = 5.0;
 const PIPE_SPEED: f64 = 0.8;
+const WORLD_HEIGHT: f64 = 50.0;
+
+/// Width of the world in game units for a canvas area, keeping units square on screen
+/// (cells are twice as tall as wide). Before the first frame the classic 100 is used.
+fn world_width(area: Rect) -> f64 {
+    let (w, h) = (area.width.saturating_sub(2), area.height.saturating_sub(2));
+    if w == 0 || h == 0 {
+        return 100.0;
+    }
+    (WORLD_HEIGHT * w as f64 / (h as f64 * 2.0)).max(60.0)
+}
 
 // ============================================
 // Bird Component - Drawn with particles
@@ -249,6 +262,8 @@ impl FlappyState {
 #[derive(Default)]
 pub struct FlappyPage {
     state: Entity<FlappyState>,
+    /// Area of the game canvas, reported by `canvas_measured`.
+    size: Entity<Rect>,
     tasks: TaskTracker,
 }
 
@@ -261,16 +276,28 @@ impl Component for FlappyPage {
         // Observe for re-renders
         self.tasks.track(cx.observe(&self.state));
 
-        let handle = cx.spawn_detached_task(move |_app| async move {
+        let size = Entity::clone(&self.size);
+        let handle = cx.spawn_page_task(move |app| async move {
             use rand::Rng;
             use rand::SeedableRng;
             let mut rng = rand::rngs::StdRng::from_entropy();
+            // Physics runs at 30 steps a second however late the task wakes
+            let mut stepper = FixedStep::new(Duration::from_millis(33));
+            let mut last = app.now();
 
             loop {
                 let (started, alive) = state.read(|s| (s.started, s.bird.alive)).unwrap_or((false, false));
-
-                if started && alive {
-                    let _ = state.update(|s| {
+                let now = app.now();
+                let dt = now.saturating_duration_since(std::mem::replace(&mut last, now));
+
+                if !(started && alive) {
+                    stepper.reset();
+                } else {
+                    let _ = state.update(|s| stepper.advance(dt, |_| {
+                        // A step earlier in this frame may have ended the game
+                        if !s.bird.alive {
+                            return;
+                        }
                         s.tick += 1;
 
                         // Update bird
@@ -280,7 +307,8 @@ impl Component for FlappyPage {
                         // Spawn pipes
                         if s.tick % 55 == 0 {
                             let gap_y = rng.gen_range(14.0..36.0);
-                            s.pipes.push(Pipe::new(105.0, gap_y));
+                            let spawn_x = world_width(size.read(|r| *r).unwrap_or_default()) + 5.0;
+                            s.pipes.push(Pipe::new(spawn_x, gap_y));
                         }
 
                         // Update pipes
@@ -298,26 +326,27 @@ impl Component for FlappyPage {
                         }
 
                         s.pipes.retain(|p| p.x > -PIPE_WIDTH);
-                    });
+                    }));
                     // app.refresh(); // redundant with observe
                 }
-                tokio::time::sleep(tokio::time::Duration::from_millis(33)).await;
+                tokio::time::sleep(stepper.step()).await;
             }
         });
         self.tasks.track(handle);
     }
 
-    fn on_exit(&mut self, _cx: &mut Context<Self>) {
+    fn on_shutdown(&mut self, _cx: &mut Context<Self>) {
         self.tasks.abort_all();
     }
 
-    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
-        let state_data = self.state.read(|s| s.clone()).unwrap_or_default();
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        let state_data = self.state.read_or_recover(|s| s.clone());
         let bird = state_data.bird.clone();
         let pipes = state_data.pipes.clone();
         let started = state_data.started;
         let score = state_data.score;
         let high_score = state_data.high_score;
+        let width = world_width(self.size.read(|r| *r).unwrap_or_default());
 
         // Header
         let status = if !bird.alive { "GAME OVER" } else if !started { "READY" } else { "FLYING" };
@@ -336,18 +365,23 @@ impl Component for FlappyPage {
         let game_view = div()
             .flex()
             .child(
-                canvas(move |frame, area| {
+                canvas_measured(&self.size, move |frame, area| {
+                    let block = Block::default()
+                        .title(" Flappy Bird ")
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(Color::Cyan));
+                    let inner = block.inner(area);
+                    frame.render_widget(block, area);
+
+                    // Game logic works in world units; very narrow terminals get bars above and below
+                    let world = CanvasWorld::new(width, WORLD_HEIGHT);
                     let canvas_widget = RatatuiCanvas::default()
-                        .block(Block::default()
-                            .title(" Flappy Bird ")
-                            .borders(Borders::ALL)
-                            .border_type(BorderType::Rounded)
-                            .border_style(Style::default().fg(Color::Cyan)))
-                        .x_bounds([0.0, 100.0])
-                        .y_bounds([0.0, 50.0])
+                        .x_bounds(world.x_bounds())
+                        .y_bounds(world.y_bounds())
                         .paint(move |ctx| {
                             // Ground
-                            ctx.draw(&Rectangle { x: 0.0, y: 0.0, width: 100.0, height: 2.0, color: Color::DarkGray });
+                            ctx.draw(&Rectangle { x: 0.0, y: 0.0, width, height: 2.0, color: Color::DarkGray });
 
                             // Render pipes
                             for pipe in &pipes {
@@ -358,19 +392,19 @@ impl Component for FlappyPage {
                             bird.render(ctx);
 
                             // Clouds
-                            ctx.print(12.0, 44.0, Line::styled("☁", Style::default().fg(Color::White)));
-                            ctx.print(55.0, 46.0, Line::styled("☁", Style::default().fg(Color::White)));
-                            ctx.print(85.0, 42.0, Line::styled("☁", Style::default().fg(Color::White)));
+                            ctx.print(width * 0.12, 44.0, Line::styled("☁", Style::default().fg(Color::White)));
+                            ctx.print(width * 0.55, 46.0, Line::styled("☁", Style::default().fg(Color::White)));
+                            ctx.print(width * 0.85, 42.0, Line::styled("☁", Style::default().fg(Color::White)));
 
                             // Instructions
                             if !started && bird.alive {
-                                ctx.print(33.0, 28.0, Line::styled("Press SPACE to fly!", Style::default().fg(Color::White)));
+                                ctx.print(width * 0.33, 20.0, Line::styled("Press SPACE to fly!", Style::default().fg(Color::White)));
                             }
                             if !bird.alive {
-                                ctx.print(40.0, 28.0, Line::styled("R to restart", Style::default().fg(Color::White)));
+                                ctx.print(width * 0.4, 20.0, Line::styled("R to restart", Style::default().fg(Color::White)));
                             }
                         });
-                    frame.render_widget(canvas_widget, area);
+                    frame.render_widget(canvas_widget, world.viewport(inner));
                 })
             );
 
@@ -380,7 +414,24 @@ impl Component for FlappyPage {
             .h(3)
             .bg(footer_color)
             .fg(Color::Black)
-            .child(text(" SPACE Flap │ R Reset │ M Menu │ Q Quit ").align_center());
+            .child(text(format!(" {} ", self.key_bindings().extend(cx.global_key_bindings()).hint_line())).align_center());
+
+        // Banner in the middle of the game view while not flying
+        let banner = if !state_data.bird.alive {
+            Some(("GAME OVER", Color::Red))
+        } else if !started {
+            Some(("READY", Color::Yellow))
+        } else {
+            None
+        };
+        let game_view: Box<dyn Element> = match banner {
+            Some((label, color)) => Box::new(
+                centered(text(label).bold().fg(Color::Black).bg(color).align_center(), 13, 1)
+                    .clear()
+                    .over(game_view),
+            ),
+            None => Box::new(game_view),
+        };
 
         // Final Layout
         div()
@@ -391,11 +442,16 @@ impl Component for FlappyPage {
             .child(footer)
     }
 
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+            .bind(KeyCode::Char(' '), "Flap")
+            .bind(KeyCode::Char('r'), "Reset")
+    }
+
     fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
-                KeyCode::Char('q') => Some(Action::Quit),
-                KeyCode::Char('m') | KeyCode::Esc => Some(Action::Navigate("menu".to_string())),
+                KeyCode::Esc => Some(Action::navigate_to(RootRoute::Menu)),
                 KeyCode::Char('r') => {
                     let _ = self.state.update(|s| s.reset());
                     None
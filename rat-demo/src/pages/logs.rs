@@ -4,11 +4,11 @@
 
 use rat_nexus::prelude::*;
 use ratatui::{
-    widgets::{Paragraph, List, ListItem, BorderType, Wrap, ListState},
+    widgets::{Paragraph, ListItem, BorderType, Wrap},
     style::{Style, Color, Modifier},
     text::{Line, Span},
 };
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent};
 use std::time::{SystemTime};
 use rand::Rng;
 
@@ -53,14 +53,21 @@ pub struct LogEntry {
     pub details: String, // Simulated JSON payload
 }
 
+impl LogEntry {
+    /// Whether the filter fuzzy-matches the message or the service.
+    fn matches(&self, filter: &str) -> bool {
+        fuzzy_match(filter, &self.message).is_some() || fuzzy_match(filter, &self.service).is_some()
+    }
+}
+
+/// Logs kept for scrolling back; older ones are dropped.
+const MAX_LOGS: usize = 1000;
+
 #[derive(Clone)]
 pub struct LogState {
-    pub logs: Vec<LogEntry>,
+    pub logs: RingBuffer<LogEntry>,
     pub filter: String,
-    pub is_typing: bool,
     pub paused: bool,
-    pub auto_scroll: bool,
-    pub selected_index: usize, // Index within filtered results
     pub filtered_indices: Vec<usize>, // Indicies of logs that match filter
     pub stats: (usize, usize, usize), // Info, Warn, Error
 }
@@ -68,12 +75,9 @@ pub struct LogState {
 impl Default for LogState {
     fn default() -> Self {
         Self {
-            logs: Vec::new(),
+            logs: RingBuffer::new(MAX_LOGS),
             filter: String::new(),
-            is_typing: false,
             paused: false,
-            auto_scroll: true,
-            selected_index: 0,
             filtered_indices: Vec::new(),
             stats: (0, 0, 0),
         }
@@ -89,22 +93,14 @@ impl LogState {
             _ => {}
         }
         
-        self.logs.push(log);
-        if self.logs.len() > 1000 {
-            self.logs.remove(0); 
-            self.recalc_filter();
-        } else {
-            let matches = self.filter.is_empty() || 
-                          self.logs.last().unwrap().message.to_lowercase().contains(&self.filter.to_lowercase()) ||
-                          self.logs.last().unwrap().service.to_lowercase().contains(&self.filter.to_lowercase());
-            
-            if matches {
-                self.filtered_indices.push(self.logs.len() - 1);
-            }
+        let matches = log.matches(&self.filter);
+        if self.logs.push(log).is_some() {
+            // The oldest log was dropped, so every index moves down by one
+            self.filtered_indices.retain(|&i| i != 0);
+            self.filtered_indices.iter_mut().for_each(|i| *i -= 1);
         }
-
-        if self.auto_scroll && !self.filtered_indices.is_empty() {
-             self.selected_index = self.filtered_indices.len().saturating_sub(1);
+        if matches {
+            self.filtered_indices.push(self.logs.len() - 1);
         }
     }
 
@@ -112,24 +108,24 @@ impl LogState {
         if self.filter.is_empty() {
             self.filtered_indices = (0..self.logs.len()).collect();
         } else {
-            let query = self.filter.to_lowercase();
             self.filtered_indices = self.logs.iter().enumerate()
-                .filter(|(_, log)| {
-                    log.message.to_lowercase().contains(&query) || 
-                    log.service.to_lowercase().contains(&query)
-                })
+                .filter(|(_, log)| log.matches(&self.filter))
                 .map(|(i, _)| i)
                 .collect();
         }
-        
-        if self.selected_index >= self.filtered_indices.len() {
-            self.selected_index = self.filtered_indices.len().saturating_sub(1);
-        }
     }
 }
 
 pub struct LogPage {
     state: Entity<LogState>,
+    /// The filter being typed; `LogState::filter` follows it via `observe_with`.
+    filter: Entity<String>,
+    /// The filter input, which writes to `filter` once typing pauses.
+    search: Input,
+    /// Selected log within the filtered results; follows the newest log while auto-scrolling.
+    selection: ListSelection,
+    /// Insert mode while typing a filter.
+    input: ModalInput,
     tasks: TaskTracker,
 }
 
@@ -137,6 +133,10 @@ impl Default for LogPage {
     fn default() -> Self {
         Self {
             state: Entity::default(),
+            filter: Entity::default(),
+            search: Input::new(),
+            selection: ListSelection::new(),
+            input: ModalInput::new(),
             tasks: TaskTracker::new(),
         }
     }
@@ -149,8 +149,30 @@ impl Component for LogPage {
 
         self.tasks.track(cx.observe(&state));
 
+        // Derived state: the query settles once typing pauses, and the next frame re-filters
+        let filter = cx.new_entity(String::new());
+        self.filter = Entity::clone(&filter);
+        let app = cx.app().clone();
+        self.search = Input::new()
+            .placeholder("Type '/' to search...")
+            .on_change(move |value| {
+                let query = Entity::clone(&filter);
+                let value = value.to_string();
+                app.debounce("log-filter", std::time::Duration::from_millis(150), move || {
+                    let _ = query.update(|q| *q = value);
+                });
+            });
+        self.tasks.track(cx.observe_with(&self.filter, |this: &mut Self, query: &String| {
+            let _ = this.state.update(|s| {
+                s.filter = query.clone();
+                s.recalc_filter();
+            });
+        }));
+
         let bg_state = state.downgrade();
-        let handle = cx.spawn_detached_task(move |_| async move {
+        // New logs go through `on_message`, so the page's own code adds them
+        let logs = cx.message_sender::<LogEntry>();
+        let handle = cx.spawn_page_task(move |_| async move {
              use rand::SeedableRng;
              let mut rng = rand::rngs::StdRng::from_entropy();
              let start_time = SystemTime::now();
@@ -165,10 +187,9 @@ impl Component for LogPage {
              ];
              let methods = ["GET", "POST", "PUT", "DELETE"];
 
-             loop {
-                 let should_add = if let Some(s) = bg_state.upgrade() {
-                     s.read(|st| !st.paused).unwrap_or(false)
-                 } else { false };
+             // Stop once the page's state is gone
+             while let Some(paused) = bg_state.read(|st| st.paused) {
+                 let should_add = !paused.unwrap_or(true);
 
                  if should_add {
                      let elapsed = SystemTime::now().duration_since(start_time).unwrap_or_default().as_secs_f64();
@@ -193,9 +214,7 @@ impl Component for LogPage {
                      );
                      
                      let log = LogEntry { id: id_counter, timestamp: elapsed, level, service, message: msg, details };
-                     if let Some(s) = bg_state.upgrade() {
-                         let _ = s.update(|st| st.add_log(log));
-                     }
+                     logs.send(log);
                  }
                  let delay = if should_add { rng.gen_range(200..1500) } else { 500 };
                  tokio::time::sleep(tokio::time::Duration::from_millis(delay)).await;
@@ -204,73 +223,69 @@ impl Component for LogPage {
         self.tasks.track(handle);
     }
 
-    fn on_exit(&mut self, _cx: &mut Context<Self>) {
+    fn on_shutdown(&mut self, _cx: &mut Context<Self>) {
         self.tasks.abort_all();
     }
 
+    fn on_message(&mut self, message: Message, _cx: &mut Context<Self>) {
+        if let Ok(log) = message.downcast::<LogEntry>() {
+            let _ = self.state.update(|s| s.add_log(log));
+        }
+    }
+
     fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
-        let state_data = self.state.read(|s| s.clone()).unwrap_or_default();
+        let state_data = self.state.read_or_recover(|s| s.clone());
+        let is_typing = self.input.is_insert();
         
-        // --- 1. Header (Div + Canvas for input simulation with cursor) ---
-        let state_data_c1 = state_data.clone();
+        // --- 1. Header (filter input) ---
         let header = div()
             .h(3)
             .border_all()
             .border_type(BorderType::Rounded)
             .title(" Log Filter ")
-            .fg(if state_data.is_typing { Color::Yellow } else { Color::DarkGray })
+            .fg(if is_typing { Color::Yellow } else { Color::DarkGray })
             .px(1)
             .child(
-                canvas(move |frame, area| {
-                    let text = if state_data_c1.filter.is_empty() { 
-                        if state_data_c1.is_typing { "" } else { "Type '/' to search..." } 
-                    } else { 
-                        &state_data_c1.filter 
-                    };
-                    let style = if state_data_c1.is_typing { 
-                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD) 
-                    } else { 
-                        Style::default().fg(Color::Cyan) 
-                    };
-                    frame.render_widget(Paragraph::new(format!(" 🔍 {}", text)).style(style), area);
-                    if state_data_c1.is_typing {
-                        frame.set_cursor_position((area.x + 3 + state_data_c1.filter.len() as u16, area.y));
-                    }
-                })
+                div()
+                    .flex_row()
+                    .child(text(" 🔍 ").w(4))
+                    .child(if is_typing {
+                        input(&self.search).focused(true).fg(Color::Yellow).bold()
+                    } else {
+                        input(&self.search).fg(Color::Cyan)
+                    })
             );
 
         // --- 2. Content Row (Div FlexBox) ---
-        let state_data_c2 = state_data.clone();
+        let items: Vec<ListItem> = state_data.filtered_indices.iter().map(|&idx| {
+            if let Some(log) = state_data.logs.get(idx) {
+                let time_str = format!("{:>6.2}s", log.timestamp);
+                let mut line = Line::from(vec![
+                    Span::styled(format!(" {} ", time_str), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format!(" {} ", log.level.as_str()), Style::default().fg(log.level.color()).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!(" {:<12} ", log.service), Style::default().fg(Color::Blue)),
+                ]);
+                // Bold the characters the filter matched
+                let indices = fuzzy_match(&state_data.filter, &log.message).map(|(_, indices)| indices).unwrap_or_default();
+                let message = highlight_matches(&log.message, &indices, Style::default(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+                line.spans.extend(message.spans);
+                line.into()
+            } else { ListItem::new("error") }
+        }).collect();
         let list_view = div()
             .w_percent(60)
             .border_all()
             .title(" Live Logs ")
             .child(
-                canvas(move |frame, area| {
-                    let items: Vec<ListItem> = state_data_c2.filtered_indices.iter().map(|&idx| {
-                        if let Some(log) = state_data_c2.logs.get(idx) {
-                            let time_str = format!("{:>6.2}s", log.timestamp);
-                            Line::from(vec![
-                                Span::styled(format!(" {} ", time_str), Style::default().fg(Color::DarkGray)),
-                                Span::styled(format!(" {} ", log.level.as_str()), Style::default().fg(log.level.color()).add_modifier(Modifier::BOLD)),
-                                Span::styled(format!(" {:<12} ", log.service), Style::default().fg(Color::Blue)),
-                                Span::raw(log.message.clone()),
-                            ]).into()
-                        } else { ListItem::new("error") }
-                    }).collect();
-
-                    let mut list_state = ListState::default();
-                    if !state_data_c2.filtered_indices.is_empty() {
-                        list_state.select(Some(state_data_c2.selected_index));
-                    }
-                    let list = List::new(items)
-                        .highlight_style(Style::default().bg(Color::Rgb(40, 40, 40)).add_modifier(Modifier::BOLD))
-                        .highlight_symbol(">> ");
-                    frame.render_stateful_widget(list, area, &mut list_state);
-                })
+                list(items)
+                    .state(&self.selection)
+                    .follow_tail(true)
+                    .highlight_style(Style::default().bg(Color::Rgb(40, 40, 40)).add_modifier(Modifier::BOLD))
+                    .highlight_symbol(">> ")
             );
 
-        let selected_log = state_data.filtered_indices.get(state_data.selected_index)
+        let selected_log = self.selection.selected()
+            .and_then(|i| state_data.filtered_indices.get(i))
             .and_then(|&idx| state_data.logs.get(idx)).cloned();
 
         let detail_element = if let Some(log) = selected_log {
@@ -281,11 +296,13 @@ impl Component for LogPage {
                 Line::from(vec![Span::styled("Level:     ", Style::default().fg(Color::Cyan)), Span::styled(log.level.as_str(), Style::default().fg(log.level.color()))]),
                 Line::from(""),
                 Line::from(Span::styled("Payload:", Style::default().fg(Color::Yellow))),
-                Line::from(log.details.clone()),
             ];
-            widget(Paragraph::new(detail_text).wrap(Wrap { trim: false }))
+            div()
+                .flex_col()
+                .child(widget(Paragraph::new(detail_text).wrap(Wrap { trim: false })).h(6))
+                .child(code(log.details.clone(), "json"))
         } else {
-            widget(Paragraph::new("No selection"))
+            div().child(widget(Paragraph::new("No selection")))
         };
 
         let content = div()
@@ -304,7 +321,7 @@ impl Component for LogPage {
         // --- 3. Footer (Rich Div FlexRow with text bits) ---
         let (n_info, n_warn, n_err) = state_data.stats;
         let status_desc = if state_data.paused { "PAUSED" } else { "STREAMING" };
-        let auto_scroll_desc = if state_data.auto_scroll { "Auto-Scroll: ON" } else { "Auto-Scroll: OFF" };
+        let auto_scroll_desc = if self.selection.is_following() { "Auto-Scroll: ON" } else { "Auto-Scroll: OFF" };
 
         let footer = div()
             .h(1)
@@ -319,7 +336,7 @@ impl Component for LogPage {
             .child(text(format!(" {} ", status_desc)).bold().fg(if state_data.paused { Color::Yellow } else { Color::Green }))
             .child(text(" | "))
             .child(text(format!(" {} ", auto_scroll_desc)).fg(Color::Cyan))
-            .child(text(" | M: Menu Q: Quit ").fg(Color::DarkGray));
+            .child(text(" | ?: Help ").fg(Color::DarkGray));
 
         // --- Final Layout Assembly ---
         div()
@@ -330,61 +347,58 @@ impl Component for LogPage {
             .child(footer)
     }
 
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+            .bind(KeyCode::Char('/'), "Filter")
+            .bind_as("Esc/Enter", KeyCode::Esc, "Stop typing")
+            .bind_as("↑/↓", KeyCode::Up, "Select log")
+            .bind(KeyCode::Char(' '), "Pause/Resume")
+            .bind(KeyCode::Char('a'), "Toggle auto-scroll")
+            .bind(KeyCode::Char('c'), "Clear")
+    }
+
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        // While typing a filter keys are text, including q/m/?
+        self.input.captures_key(key)
+    }
+
     fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
-        let is_typing = self.state.read(|s| s.is_typing).unwrap_or(false);
-
-        match event {
-            Event::Key(key) => {
-                if is_typing {
-                    match key.code {
-                         KeyCode::Esc | KeyCode::Enter => { let _ = self.state.update(|s| s.is_typing = false); None }
-                         KeyCode::Backspace => { let _ = self.state.update(|s| { s.filter.pop(); s.recalc_filter(); }); None }
-                         KeyCode::Char(c) => { let _ = self.state.update(|s| { s.filter.push(c); s.recalc_filter(); }); None }
-                         _ => None,
-                    }
-                } else {
-                    match key.code {
-                        KeyCode::Char('q') => Some(Action::Quit),
-                        KeyCode::Char('m') => Some(Action::Navigate("menu".to_string())),
-                        KeyCode::Char('/') => { 
-                            let _ = self.state.update(|s| { s.is_typing = true; s.auto_scroll = false; }); 
-                            None 
-                        },
-                        KeyCode::Char(' ') => { let _ = self.state.update(|s| s.paused = !s.paused); None }
-                        KeyCode::Char('a') => {
-                            let _ = self.state.update(|s| { 
-                                s.auto_scroll = !s.auto_scroll;
-                                if s.auto_scroll && !s.filtered_indices.is_empty() {
-                                    s.selected_index = s.filtered_indices.len() - 1;
-                                }
-                            });
-                            None
-                        }
-                        KeyCode::Char('c') => {
-                            let _ = self.state.update(|s| {
-                                s.logs.clear(); s.filtered_indices.clear(); s.selected_index = 0; s.stats = (0, 0, 0);
-                            });
-                            None
-                        }
-                        KeyCode::Up | KeyCode::Char('k') => {
-                            let _ = self.state.update(|s| {
-                                s.auto_scroll = false;
-                                if s.selected_index > 0 { s.selected_index -= 1; }
-                            });
-                            None
-                        }
-                        KeyCode::Down | KeyCode::Char('j') => {
-                            let _ = self.state.update(|s| {
-                                s.auto_scroll = false;
-                                if s.selected_index < s.filtered_indices.len().saturating_sub(1) { s.selected_index += 1; }
-                            });
-                            None
-                        }
-                        _ => None,
-                    }
-                }
-            },
-            _ => None,
+        match self.input.route(event) {
+            ModeEvent::Normal(event) => self.handle_command(event),
+            ModeEvent::Insert(event) => {
+                self.handle_typing(event);
+                None
+            }
+            ModeEvent::Switched(InputMode::Insert) => {
+                self.selection.set_following(false);
+                None
+            }
+            ModeEvent::Switched(InputMode::Normal) => None,
+        }
+    }
+}
+
+impl LogPage {
+    fn handle_command(&mut self, event: Event) -> Option<Action> {
+        let Event::Key(key) = event else {
+            return None;
+        };
+        match key.code {
+            KeyCode::Char(' ') => { let _ = self.state.update(|s| s.paused = !s.paused); }
+            KeyCode::Char('a') => self.selection.set_following(!self.selection.is_following()),
+            KeyCode::Char('c') => {
+                let _ = self.state.update(|s| {
+                    s.logs.clear(); s.filtered_indices.clear(); s.stats = (0, 0, 0);
+                });
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selection.scroll(ScrollDirection::Up),
+            KeyCode::Down | KeyCode::Char('j') => self.selection.scroll(ScrollDirection::Down),
+            _ => {}
         }
+        None
+    }
+
+    fn handle_typing(&mut self, event: Event) {
+        self.search.handle_event(&event);
     }
 }
@@ -34,13 +34,11 @@ impl Component for Menu {
         ];
 
         // Initialize/get shared state
-        let state = cx.get_or_insert_with::<Entity<AppState>, _>(|| {
-            cx.new_entity(AppState::default())
-        }).expect("Failed to initialize AppState");
-        self.state = state;
+        self.state = cx.entity_or_insert_with(AppState::default);
 
         // Observe for re-renders
         self.tasks.track(cx.observe(&self.state));
+        self.tasks.track(cx.observe(&cx.theme()));
     }
 
     fn on_enter(&mut self, _cx: &mut Context<Self>) {
@@ -55,14 +53,15 @@ impl Component for Menu {
         // Cleanup
     }
 
-    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
         use ratatui::layout::{Alignment};
         use ratatui::widgets::{Block, Borders, List, ListItem, BorderType, Paragraph};
         use ratatui::style::{Style, Modifier, Color};
         use ratatui::text::{Line, Span};
 
-        let app_state = self.state.read(|s| s.clone()).unwrap_or_default();
-        let theme_color = app_state.theme.color();
+        let app_state = self.state.read_or_recover(|s| s.clone());
+        let theme = cx.theme().read(|t| t.clone()).unwrap_or_default();
+        let theme_color = theme.primary;
 
         // ASCII Art Header
         let ascii_art = vec![
@@ -143,7 +142,7 @@ impl Component for Menu {
             ]),
             Line::from(vec![
                 Span::styled(" Theme: ", Style::default().fg(Color::DarkGray)),
-                Span::styled(app_state.theme.name(), Style::default().fg(theme_color)),
+                Span::styled(theme.name, Style::default().fg(theme_color)),
             ]),
         ];
 
@@ -159,7 +158,7 @@ impl Component for Menu {
             .h(3)
             .bg(theme_color)
             .fg(Color::Black)
-            .child(text(" ↑/↓ Navigate │ Enter Select │ T Theme │ Q Quit ").align_center());
+            .child(text(format!(" {} ", self.key_bindings().extend(cx.global_key_bindings()).hint_line())).align_center());
 
         // Compose elements
         div()
@@ -175,7 +174,14 @@ impl Component for Menu {
             .child(footer)
     }
 
-    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+            .bind_as("↑/↓", KeyCode::Up, "Navigate")
+            .bind(KeyCode::Enter, "Select")
+            .bind(KeyCode::Char('t'), "Theme")
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
                 KeyCode::Up | KeyCode::Char('k') => {
@@ -211,10 +217,9 @@ impl Component for Menu {
                     }
                 }
                 KeyCode::Char('t') => {
-                    let _ = self.state.update(|s| s.theme = s.theme.next());
+                    cx.next_theme();
                     None
                 }
-                KeyCode::Char('q') => Some(Action::Quit),
                 _ => None,
             },
             _ => None,
@@ -2,50 +2,39 @@
 //!
 //! Demonstrates:
 //! - Real-time async data updates with TaskTracker
-//! - Chart widget for time series
-//! - Multiple Sparklines
+//! - Line chart and sparkline elements bound to state
 //! - Table with dynamic data
 //! - Complex layout composition
 
 use rat_nexus::prelude::*;
-use crate::model::{AppState, MonitorState};
+use crate::model::MonitorState;
 use ratatui::{
     layout::{Layout, Constraint, Direction, Alignment, Rect},
-    widgets::{
-        Block, Borders, Paragraph, Table, Row, Cell, Sparkline,
-        BorderType, Chart, Axis, Dataset, GraphType,
-    },
+    widgets::{Block, Borders, Paragraph, Table, Row, Cell, BorderType},
     style::{Style, Color, Modifier},
     text::{Line, Span},
-    symbols,
 };
 use crossterm::event::KeyCode;
+use crate::app::RootRoute;
 
 #[derive(Default)]
 pub struct MonitorPage {
-    app_state: Entity<AppState>,
     state: Entity<MonitorState>,
     tasks: TaskTracker,
 }
 
 impl Component for MonitorPage {
     fn on_mount(&mut self, cx: &mut Context<Self>) {
-        // Get or initialize shared AppState
-        let app_state = cx.get_or_insert_with::<Entity<AppState>, _>(|| {
-            cx.new_entity(AppState::default())
-        }).expect("Failed to initialize AppState");
-        self.app_state = Entity::clone(&app_state);
-
         // Initialize MonitorState
         let state = cx.new_entity(MonitorState::default());
         self.state = Entity::clone(&state);
 
         // Observe for re-renders
-        self.tasks.track(cx.observe(&app_state));
+        self.tasks.track(cx.observe(&cx.theme()));
         self.tasks.track(cx.observe(&self.state));
 
         // Spawn data simulation task
-        let handle = cx.spawn_detached_task(move |_app| async move {
+        let handle = cx.spawn_page_task(move |_app| async move {
             use rand::Rng;
             use rand::SeedableRng;
             let mut rng = rand::rngs::StdRng::from_entropy();
@@ -102,21 +91,21 @@ impl Component for MonitorPage {
         self.tasks.track(handle);
     }
 
-    fn on_exit(&mut self, _cx: &mut Context<Self>) {
+    fn on_shutdown(&mut self, _cx: &mut Context<Self>) {
         self.tasks.abort_all();
     }
 
-    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
-        let state_data = self.state.read(|s| s.clone()).unwrap_or_default();
-        let app = self.app_state.read(|s| s.clone()).unwrap_or_default();
-        let theme_color = app.theme.color();
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        let state_data = self.state.read_or_recover(|s| s.clone());
+        let theme = cx.theme().read(|t| t.clone()).unwrap_or_default();
+        let theme_color = theme.primary;
 
         // Header with system info
         let uptime_str = format_uptime(state_data.uptime_secs);
         let header_text = format!(
             " 📊 System Monitor │ Uptime: {} │ Theme: {} ",
             uptime_str,
-            app.theme.name()
+            theme.name
         );
 
         let header = div()
@@ -137,21 +126,52 @@ impl Component for MonitorPage {
             .bg(theme_color)
             .fg(Color::Black)
             .child(
-                text(" R Reset │ T Theme │ M Menu │ Q Quit │ Mouse: Scroll to adjust ")
+                text(format!(" {} ", self.key_bindings().extend(cx.global_key_bindings()).hint_line()))
                     .align_center()
             );
 
-        // Body: Charts (Left) and Sidebar (Right)
+        // Body: Charts (Left) and Sidebar (Right), stacked on narrow terminals
         let sd1 = state_data.clone();
         let sd2 = state_data.clone();
         let body = div()
-            .flex_row()
+            .responsive(|div, bp| match bp {
+                Breakpoint::Small => div.flex_col(),
+                _ => div.flex_row(),
+            })
             .child(
                 div()
                     .w_percent(65)
-                    .child(canvas(move |frame, area| {
-                        Self::render_charts(frame, area, &sd1, theme_color);
-                    }))
+                    .m(1)
+                    .child(
+                        div()
+                            .h_percent(50)
+                            .border_all()
+                            .border_type(BorderType::Rounded)
+                            .border_style(Style::default().fg(theme_color))
+                            .title(" CPU & Memory Usage (%) ")
+                            .child(
+                                line_chart([])
+                                    .bind(&self.state, move |s| vec![
+                                        Series::from_values("CPU", s.cpu_history.iter().map(|&v| v as f64)).color(theme_color),
+                                        Series::from_values("Memory", s.memory_history.iter().map(|&v| v as f64)).color(Color::Green),
+                                    ])
+                                    .x_bounds(0.0, 60.0)
+                                    .y_bounds(0.0, 100.0)
+                                    .x_title("Time")
+                                    .y_title("Usage %")
+                                    .axis_labels()
+                            )
+                    )
+                    .child(
+                        div()
+                            .h_percent(25)
+                            .flex_row()
+                            .child(Self::network_panel(" ↓ Network In (KB/s) ", Color::Green, sparkline([]).bind(&self.state, |s| s.network_in.clone())))
+                            .child(Self::network_panel(" ↑ Network Out (KB/s) ", Color::Yellow, sparkline([]).bind(&self.state, |s| s.network_out.clone())))
+                    )
+                    .child(div().h_percent(25).child(canvas(move |frame, area| {
+                        Self::render_cores(frame, area, &sd1, theme_color);
+                    })))
             )
             .child(
                 div()
@@ -170,13 +190,19 @@ impl Component for MonitorPage {
             .child(footer)
     }
 
-    fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+            .bind(KeyCode::Char('r'), "Reset")
+            .bind(KeyCode::Char('t'), "Theme")
+            .describe("Mouse", "Scroll to adjust")
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
-                KeyCode::Char('q') => Some(Action::Quit),
-                KeyCode::Char('m') | KeyCode::Esc => Some(Action::Navigate("menu".to_string())),
+                KeyCode::Esc => Some(Action::navigate_to(RootRoute::Menu)),
                 KeyCode::Char('t') => {
-                    let _ = self.app_state.update(|s| s.theme = s.theme.next());
+                    cx.next_theme();
                     None
                 }
                 KeyCode::Char('r') => {
@@ -211,7 +237,7 @@ impl Component for MonitorPage {
                     }
                     MouseEventKind::Down(MouseButton::Left) => {
                         // Left click cycles theme
-                        let _ = self.app_state.update(|s| s.theme = s.theme.next());
+                        cx.next_theme();
                         None
                     }
                     MouseEventKind::Down(MouseButton::Right) => {
@@ -230,87 +256,17 @@ impl Component for MonitorPage {
 }
 
 impl MonitorPage {
-    fn render_charts(frame: &mut ratatui::Frame, area: Rect, state: &MonitorState, theme_color: Color) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(50), // CPU/Memory chart
-                Constraint::Percentage(25), // Network sparklines
-                Constraint::Percentage(25), // CPU cores
-            ])
-            .margin(1)
-            .split(area);
-
-        // CPU & Memory Chart
-        let cpu_data: Vec<(f64, f64)> = state.cpu_history.iter()
-            .enumerate()
-            .map(|(i, &v)| (i as f64, v as f64))
-            .collect();
-
-        let mem_data: Vec<(f64, f64)> = state.memory_history.iter()
-            .enumerate()
-            .map(|(i, &v)| (i as f64, v as f64))
-            .collect();
-
-        let datasets = vec![
-            Dataset::default()
-                .name("CPU")
-                .marker(symbols::Marker::Braille)
-                .graph_type(GraphType::Line)
-                .style(Style::default().fg(theme_color))
-                .data(&cpu_data),
-            Dataset::default()
-                .name("Memory")
-                .marker(symbols::Marker::Braille)
-                .graph_type(GraphType::Line)
-                .style(Style::default().fg(Color::Green))
-                .data(&mem_data),
-        ];
-
-        let chart = Chart::new(datasets)
-            .block(Block::default()
-                .title(" CPU & Memory Usage (%) ")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(theme_color)))
-            .x_axis(Axis::default()
-                .title("Time")
-                .style(Style::default().fg(Color::DarkGray))
-                .bounds([0.0, 60.0]))
-            .y_axis(Axis::default()
-                .title("Usage %")
-                .style(Style::default().fg(Color::DarkGray))
-                .bounds([0.0, 100.0])
-                .labels(["0", "50", "100"]));
-
-        frame.render_widget(chart, chunks[0]);
-
-        // Network sparklines
-        let net_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(chunks[1]);
-
-        let net_in_spark = Sparkline::default()
-            .block(Block::default()
-                .title(" ↓ Network In (KB/s) ")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Green)))
-            .data(&state.network_in)
-            .style(Style::default().fg(Color::Green));
-        frame.render_widget(net_in_spark, net_chunks[0]);
-
-        let net_out_spark = Sparkline::default()
-            .block(Block::default()
-                .title(" ↑ Network Out (KB/s) ")
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Yellow)))
-            .data(&state.network_out)
-            .style(Style::default().fg(Color::Yellow));
-        frame.render_widget(net_out_spark, net_chunks[1]);
+    fn network_panel(title: &str, color: Color, spark: SparklineView) -> Div {
+        div()
+            .w_percent(50)
+            .border_all()
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color))
+            .title(title)
+            .child(spark.fg(color))
+    }
 
+    fn render_cores(frame: &mut ratatui::Frame, area: Rect, state: &MonitorState, theme_color: Color) {
         // CPU cores as mini gauges
         let core_block = Block::default()
             .title(" CPU Cores ")
@@ -318,8 +274,8 @@ impl MonitorPage {
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme_color));
 
-        let inner = core_block.inner(chunks[2]);
-        frame.render_widget(core_block, chunks[2]);
+        let inner = core_block.inner(area);
+        frame.render_widget(core_block, area);
 
         let core_chunks = Layout::default()
             .direction(Direction::Horizontal)
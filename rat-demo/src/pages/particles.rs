@@ -7,6 +7,7 @@ use ratatui::{
     style::{Style, Color},
 };
 use crossterm::event::KeyCode;
+use crate::app::RootRoute;
 
 #[derive(Clone)]
 pub struct Particle {
@@ -43,7 +44,7 @@ impl Component for ParticlesPage {
         self.tasks.track(cx.observe(&self.state));
 
         // Particle physics update loop
-        let handle = cx.spawn_detached_task(move |_app| async move {
+        let handle = cx.spawn_page_task(move |_app| async move {
             use rand::Rng;
             use rand::SeedableRng;
             let mut rng = rand::rngs::StdRng::from_entropy();
@@ -92,12 +93,12 @@ impl Component for ParticlesPage {
         self.tasks.track(handle);
     }
 
-    fn on_exit(&mut self, _cx: &mut Context<Self>) {
+    fn on_shutdown(&mut self, _cx: &mut Context<Self>) {
         self.tasks.abort_all();
     }
 
-    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
-        let state_data = self.state.read(|s| s.clone()).unwrap_or_default();
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        let state_data = self.state.read_or_recover(|s| s.clone());
         let particles_data: Vec<_> = state_data.particles.iter()
             .map(|p| (p.x, p.y, p.color))
             .collect();
@@ -148,7 +149,7 @@ impl Component for ParticlesPage {
             .h(3)
             .bg(footer_color)
             .fg(Color::Black)
-            .child(text(" SPACE Pause │ Arrow Keys Move │ R Reset │ M Menu │ Q Quit ").align_center());
+            .child(text(format!(" {} ", self.key_bindings().extend(cx.global_key_bindings()).hint_line())).align_center());
 
         // Final Layout
         div()
@@ -159,11 +160,17 @@ impl Component for ParticlesPage {
             .child(footer)
     }
 
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+            .bind(KeyCode::Char(' '), "Pause")
+            .bind_as("Arrow Keys", KeyCode::Up, "Move")
+            .bind(KeyCode::Char('r'), "Reset")
+    }
+
     fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
-                KeyCode::Char('q') => Some(Action::Quit),
-                KeyCode::Char('m') | KeyCode::Esc => Some(Action::Navigate("menu".to_string())),
+                KeyCode::Esc => Some(Action::navigate_to(RootRoute::Menu)),
                 KeyCode::Char(' ') => {
                     let _ = self.state.update(|s| s.paused = !s.paused);
                     None
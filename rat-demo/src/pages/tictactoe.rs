@@ -11,6 +11,7 @@ use ratatui::{
 use crossterm::event::{KeyCode, MouseEventKind, MouseButton};
 use std::sync::{Arc, Mutex};
 use crate::agent::{create_gomoku_agent_from_env, create_commentator_agent_from_env, GomokuAgent, CommentatorAgent};
+use crate::app::RootRoute;
 
 pub const BOARD_SIZE: usize = 15;
 const WIN_COUNT: usize = 5;
@@ -839,8 +840,8 @@ impl Component for TicTacToePage {
         self.tasks.abort_all();
     }
 
-    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
-        let state_data = self.state.read(|s| s.clone()).unwrap_or_default();
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        let state_data = self.state.read_or_recover(|s| s.clone());
         let board_lock = Arc::clone(&self.board_area);
 
         // Header
@@ -898,7 +899,7 @@ impl Component for TicTacToePage {
             .h(3)
             .bg(Color::Cyan)
             .fg(Color::Black)
-            .child(text(" Click/Enter Place │ T Toggle LLM │ R Reset │ M Menu │ Q Quit ").align_center());
+            .child(text(format!(" {} ", self.key_bindings().extend(cx.global_key_bindings()).hint_line())).align_center());
 
         // Final Layout
         div()
@@ -909,11 +910,18 @@ impl Component for TicTacToePage {
             .child(footer)
     }
 
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+            .bind_as("Click/Enter", KeyCode::Enter, "Place")
+            .bind_as("Arrows/HJKL", KeyCode::Up, "Move cursor")
+            .bind(KeyCode::Char('t'), "Toggle LLM")
+            .bind(KeyCode::Char('r'), "Reset")
+    }
+
     fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
-                KeyCode::Char('q') => Some(Action::Quit),
-                KeyCode::Char('m') | KeyCode::Esc => Some(Action::Navigate("menu".to_string())),
+                KeyCode::Esc => Some(Action::navigate_to(RootRoute::Menu)),
                 KeyCode::Char('r') => {
                     let _ = self.state.update(|s| s.reset());
                     None
@@ -1,14 +1,13 @@
 //! Timer Demo - Stopwatch with lap times
-//! Showcases: Entity state, spawn_task, TaskTracker, async updates
+//! Showcases: Entity state, spawn_page_task, TaskTracker, async updates
 
 use rat_nexus::prelude::*;
 use ratatui::{
-    layout::Alignment,
-    widgets::{Block, Borders, Paragraph, List, ListItem, BorderType},
-    style::{Style, Color, Modifier},
-    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, BorderType},
+    style::{Style, Color},
 };
 use crossterm::event::KeyCode;
+use crate::app::RootRoute;
 
 #[derive(Clone, Default)]
 pub struct TimerState {
@@ -29,46 +28,42 @@ impl Component for TimerPage {
         let state = cx.new_entity(TimerState::default());
         self.state = Entity::clone(&state);
 
-        // Observe for re-renders
-        self.tasks.track(cx.observe(&self.state));
+        // The state ticks every 10ms; ~30 redraws a second is plenty
+        self.tasks.track(cx.observe_throttled(&self.state, std::time::Duration::from_millis(33)));
 
-        let handle = cx.spawn_detached_task(move |_app| async move {
+        // Elapsed time comes from the app clock, so a TestClock drives it deterministically
+        let handle = cx.spawn_page_task(move |app| async move {
+            let mut last = app.now();
             loop {
-                let running = state.read(|s| s.running).unwrap_or(false);
-                if running {
-                    let _ = state.update(|s| s.elapsed_ms += 10);
-                    // app.refresh(); // redundant with observer
-                }
-                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+                app.sleep(std::time::Duration::from_millis(10)).await;
+                let now = app.now();
+                let delta = now.duration_since(last).as_millis() as u64;
+                last = now;
+
+                // Paused ticks leave the state alone, so they don't wake observers
+                let _ = state.update_with(|s| {
+                    if s.running {
+                        s.elapsed_ms += delta;
+                    }
+                    ((), s.running)
+                });
             }
         });
         self.tasks.track(handle);
     }
 
-    fn on_exit(&mut self, _cx: &mut Context<Self>) {
+    fn on_shutdown(&mut self, _cx: &mut Context<Self>) {
         self.tasks.abort_all();
     }
 
-    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
-        let state_data = self.state.read(|s| s.clone()).unwrap_or_default();
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        let state_data = self.state.read_or_recover(|s| s.clone());
 
         // Timer display
         let time = format_time(state_data.elapsed_ms);
         let color = if state_data.running { Color::Green } else { Color::Yellow };
 
-        let timer_lines = vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::styled(time, Style::default().fg(color).add_modifier(Modifier::BOLD)),
-            ]).alignment(Alignment::Center),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    if state_data.running { "  RUNNING  " } else { "  STOPPED  " },
-                    Style::default().fg(Color::Black).bg(color)
-                ),
-            ]).alignment(Alignment::Center),
-        ];
+        let status = if state_data.running { "  RUNNING  " } else { "  STOPPED  " };
 
         let header = div()
             .h(9)
@@ -76,7 +71,11 @@ impl Component for TimerPage {
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(color))
             .title(" Stopwatch ")
-            .child(widget(Paragraph::new(timer_lines)));
+            .child(div()
+                .center()
+                .child(text(time).fg(color).bold())
+                .child(text("").h(1))
+                .child(text(status).fg(Color::Black).bg(color)));
 
         // Lap times
         let lap_items: Vec<ListItem> = state_data.laps.iter().enumerate().rev()
@@ -99,7 +98,7 @@ impl Component for TimerPage {
             .h(3)
             .bg(color)
             .fg(Color::Black)
-            .child(text(" SPACE Start/Stop │ L Lap │ R Reset │ M Menu │ Q Quit ").align_center());
+            .child(text(format!(" {} ", self.key_bindings().extend(cx.global_key_bindings()).hint_line())).align_center());
 
         // Layout
         div()
@@ -110,20 +109,28 @@ impl Component for TimerPage {
             .child(footer)
     }
 
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+            .bind(KeyCode::Char(' '), "Start/Stop")
+            .bind(KeyCode::Char('l'), "Lap")
+            .bind(KeyCode::Char('r'), "Reset")
+    }
+
     fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
         match event {
             Event::Key(key) => match key.code {
-                KeyCode::Char('q') => Some(Action::Quit),
-                KeyCode::Char('m') | KeyCode::Esc => Some(Action::Navigate("menu".to_string())),
+                KeyCode::Esc => Some(Action::navigate_to(RootRoute::Menu)),
                 KeyCode::Char(' ') => {
                     let _ = self.state.update(|s| s.running = !s.running);
                     None
                 }
                 KeyCode::Char('l') => {
-                    let _ = self.state.update(|s| {
-                        if s.running || s.elapsed_ms > 0 {
+                    let _ = self.state.update_with(|s| {
+                        let lap = s.running || s.elapsed_ms > 0;
+                        if lap {
                             s.laps.push(s.elapsed_ms);
                         }
+                        ((), lap)
                     });
                     None
                 }
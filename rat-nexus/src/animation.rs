@@ -0,0 +1,271 @@
+//! Time-based interpolation for smooth transitions.
+//!
+//! A component keeps an `Animation`, starts it when something changes and
+//! reads its value while rendering. Time comes from the application clock, so
+//! animations run at the same speed whatever the frame rate and can be
+//! stepped with a `TestClock`:
+//!
+//! ```ignore
+//! fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+//!     // Slide the panel open
+//!     self.panel = tween(0.0, 30.0, Duration::from_millis(200), Easing::EaseOut).started(cx.now());
+//!     None
+//! }
+//!
+//! fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+//!     let now = cx.now();
+//!     if self.panel.is_running(now) {
+//!         cx.refresh(); // Keep drawing until the animation ends
+//!     }
+//!     div().flex_row().child(sidebar.w(self.panel.value(now).round() as u16)).child(content)
+//! }
+//! ```
+//!
+//! Simulations such as games use a `FixedStep` instead, which runs a step
+//! function a whole number of times per frame so the outcome doesn't depend on
+//! how often frames are drawn.
+
+use std::time::{Duration, Instant};
+
+/// How an animation's progress is mapped onto its value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down, the usual choice for things coming to rest.
+    EaseOut,
+    /// Slow at both ends.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map progress `t` in `0.0..=1.0` onto eased progress in the same range.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// A value moving from `from` to `to` over `duration`.
+///
+/// Until it is started the value stays at `from`; afterwards it stays at `to`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Animation {
+    from: f64,
+    to: f64,
+    duration: Duration,
+    easing: Easing,
+    start: Option<Instant>,
+}
+
+/// Create an animation from `from` to `to`; call `start` (or `started`) to run it.
+pub fn tween(from: f64, to: f64, duration: Duration, easing: Easing) -> Animation {
+    Animation {
+        from,
+        to,
+        duration,
+        easing,
+        start: None,
+    }
+}
+
+impl Default for Animation {
+    /// An animation resting at 0 that never runs.
+    fn default() -> Self {
+        tween(0.0, 0.0, Duration::ZERO, Easing::Linear)
+    }
+}
+
+impl Animation {
+    /// Start (or restart) the animation at `now`, usually `cx.now()`.
+    pub fn start(&mut self, now: Instant) {
+        self.start = Some(now);
+    }
+
+    /// Builder form of `start`.
+    pub fn started(mut self, now: Instant) -> Self {
+        self.start(now);
+        self
+    }
+
+    /// Head for `to` from wherever the animation is at `now`, e.g. when a
+    /// progress bar gets a new value before it finished moving to the last one.
+    pub fn retarget(&mut self, to: f64, now: Instant) {
+        self.from = self.value(now);
+        self.to = to;
+        self.start = Some(now);
+    }
+
+    /// Progress at `now`, from 0.0 (not started) to 1.0 (finished), before easing.
+    pub fn progress(&self, now: Instant) -> f64 {
+        let Some(start) = self.start else {
+            return 0.0;
+        };
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(start);
+        (elapsed.as_secs_f64() / self.duration.as_secs_f64()).min(1.0)
+    }
+
+    /// The value at `now`.
+    pub fn value(&self, now: Instant) -> f64 {
+        let t = self.easing.apply(self.progress(now));
+        self.from + (self.to - self.from) * t
+    }
+
+    /// Whether the animation has reached `to`. An animation that was never
+    /// started is not finished.
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.start.is_some() && self.progress(now) >= 1.0
+    }
+
+    /// Whether the animation has started and not yet finished, i.e. whether
+    /// its value still changes and the component should keep refreshing.
+    pub fn is_running(&self, now: Instant) -> bool {
+        self.start.is_some() && self.progress(now) < 1.0
+    }
+
+    /// The value the animation ends at.
+    pub fn target(&self) -> f64 {
+        self.to
+    }
+}
+
+/// A fixed-timestep accumulator that decouples a simulation from the frame rate.
+///
+/// Each frame hands the real elapsed time to `advance`, which runs the step
+/// function once per whole `step` that has accumulated and keeps the remainder
+/// for the next frame. A slow frame runs several steps, a fast one may run
+/// none, so the simulation moves at the same speed either way:
+///
+/// ```ignore
+/// let now = app.now();
+/// let dt = now - std::mem::replace(&mut last, now);
+/// state.update(|s| stepper.advance(dt, |_| s.step()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedStep {
+    step: Duration,
+    accumulated: Duration,
+    max_steps: usize,
+}
+
+impl FixedStep {
+    /// Step every `step`, catching up at most 8 steps per `advance`.
+    ///
+    /// # Panics
+    /// If `step` is zero.
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "FixedStep needs a non-zero step");
+        Self {
+            step,
+            accumulated: Duration::ZERO,
+            max_steps: 8,
+        }
+    }
+
+    /// Most steps one `advance` runs. Time beyond them is dropped, so after a
+    /// long stall (e.g. a suspended terminal) the simulation skips ahead rather
+    /// than running hundreds of steps to catch up.
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// The simulated time per step.
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Add `dt` of real time and run `f` once per whole step accumulated,
+    /// passing it the step duration. Returns the number of steps run.
+    pub fn advance(&mut self, dt: Duration, mut f: impl FnMut(Duration)) -> usize {
+        self.accumulated += dt;
+        let mut steps = 0;
+        while self.accumulated >= self.step && steps < self.max_steps {
+            f(self.step);
+            self.accumulated -= self.step;
+            steps += 1;
+        }
+        if self.accumulated >= self.step {
+            self.accumulated = Duration::ZERO;
+        }
+        steps
+    }
+
+    /// How far the simulation is into the next step, from 0.0 to just under
+    /// 1.0, for interpolating between the last two states when drawing.
+    pub fn alpha(&self) -> f64 {
+        self.accumulated.as_secs_f64() / self.step.as_secs_f64()
+    }
+
+    /// Drop the accumulated time, e.g. when the simulation resumes after a pause.
+    pub fn reset(&mut self) {
+        self.accumulated = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, TestClock};
+
+    #[test]
+    fn values_follow_the_clock_and_retargeting_continues_from_the_current_value() {
+        let clock = TestClock::new();
+        let mut bar = tween(0.0, 100.0, Duration::from_millis(100), Easing::Linear);
+        assert_eq!(bar.value(clock.now()), 0.0);
+        assert!(!bar.is_finished(clock.now()));
+
+        bar.start(clock.now());
+        clock.advance(Duration::from_millis(25));
+        assert_eq!(bar.value(clock.now()), 25.0);
+        assert!(bar.is_running(clock.now()));
+
+        bar.retarget(50.0, clock.now());
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(bar.value(clock.now()), 37.5);
+        clock.advance(Duration::from_millis(80));
+        assert_eq!(bar.value(clock.now()), 50.0);
+        assert!(bar.is_finished(clock.now()) && !bar.is_running(clock.now()));
+        assert!(!Animation::default().is_running(clock.now()));
+
+        for easing in [Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!((easing.apply(0.0), easing.apply(1.0)), (0.0, 1.0));
+        }
+        assert!(Easing::EaseOut.apply(0.5) > 0.5 && Easing::EaseIn.apply(0.5) < 0.5);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn fixed_steps_follow_real_time_and_stalls_are_capped() {
+        let mut stepper = FixedStep::new(Duration::from_millis(10)).max_steps(4);
+        let mut simulated = Duration::ZERO;
+        assert_eq!(stepper.advance(Duration::from_millis(25), |dt| simulated += dt), 2);
+        assert_eq!(stepper.alpha(), 0.5);
+        assert_eq!(stepper.advance(Duration::from_millis(4), |dt| simulated += dt), 0);
+        assert_eq!(stepper.advance(Duration::from_millis(1), |dt| simulated += dt), 1);
+        assert_eq!(simulated, Duration::from_millis(30));
+
+        assert_eq!(stepper.advance(Duration::from_secs(5), |dt| simulated += dt), 4);
+        assert_eq!(stepper.advance(Duration::from_millis(7), |dt| simulated += dt), 0);
+        assert_eq!(simulated, Duration::from_millis(70));
+        stepper.reset();
+        assert_eq!(stepper.alpha(), 0.0);
+    }
+}
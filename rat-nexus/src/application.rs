@@ -1,23 +1,72 @@
 //! High‑level Application abstraction inspired by GPUI.
 
-use crate::component::traits::{Event, Action, Component, AnyComponent};
-use crate::state::{Entity, WeakEntity, EntityId};
+use crate::component::traits::{Event, Action, Component, AnyComponent, CustomAction, Message};
+use crate::state::{Entity, EntityList, WeakEntity, EntityId, TypeMap};
+use crate::clock::{Clock, RealClock};
+use crate::theme::{self, Theme};
+use crate::color::{AccessibilityMode, ColorDepth};
+use crate::breakpoint::{self, Breakpoint, Breakpoints};
+use crate::debug::{DebugOverlay, DebugStats, RenderStats, ToggleDebugOverlay};
+use crate::overlay::{OverlayId, OverlayStack};
+use crate::terminal::{CursorShape, TerminalModes, TerminalSync};
+use crate::element::Element;
+use crate::keymap::KeyBindings;
 use ratatui::prelude::*;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, KeyEventKind},
+    cursor::SetCursorStyle,
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{self, stdout};
+use std::marker::PhantomData;
 use std::sync::{Arc, Mutex, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
-/// Type-erased storage for application-level shared state.
-type StateMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+/// Key bindings that apply regardless of the active page.
+/// Keys bound with `bind_global`, in the order they were bound.
+type GlobalKeys = Vec<GlobalBinding>;
+
+/// A key bound with `bind_global`, see `AppContext::global_key_bindings`.
+struct GlobalBinding {
+    key: KeyCode,
+    modifiers: KeyModifiers,
+    action: Action,
+    /// Shown in the help overlay; bindings without one are left out.
+    description: Option<String>,
+}
+
+/// Runs an `observe_with` callback on a type-erased component.
+type ObserverCallback = Box<dyn FnMut(&mut dyn Any) + Send>;
+
+/// A callback registered with `Context::observe_with`, waiting to run on its component.
+struct PendingObserver {
+    /// Set by the watcher task on change. Dead once the task (and its handle) is gone.
+    pending: std::sync::Weak<std::sync::atomic::AtomicBool>,
+    callback: ObserverCallback,
+}
+
+/// The component a registration such as an `observe_with` callback, a mailbox or
+/// a drawn area belongs to: its entity, or its type for contexts without one (`define_app!` pages,
+/// reached through `Context::cast`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ComponentKey {
+    Entity(EntityId),
+    Type(TypeId),
+}
+
+/// `observe_with` callbacks, by component.
+type Observers = HashMap<ComponentKey, Vec<PendingObserver>>;
+
+/// Messages waiting to be delivered, by component. See `Context::message_sender`.
+type Mailboxes = HashMap<ComponentKey, VecDeque<Message>>;
+
+/// Handler for custom actions that reach the top of the component tree.
+type ActionHandler = Arc<dyn Fn(&CustomAction, &AppContext) + Send + Sync>;
 
 pub struct AppContext {
     /// The root component to render, if set by the user.
@@ -26,8 +75,58 @@ pub struct AppContext {
     re_render_tx: mpsc::UnboundedSender<()>,
     /// Internal: Total frames rendered.
     frame_count: Arc<std::sync::atomic::AtomicU64>,
-    /// Application-level shared state storage (TypeMap pattern).
-    state: Arc<RwLock<StateMap>>,
+    /// Application-level shared state, one value per type.
+    state: Arc<TypeMap>,
+    /// Time source; a `TestClock` makes time-driven components deterministic.
+    clock: Arc<dyn Clock>,
+    /// Keys handled before events reach the root component.
+    global_keys: Arc<RwLock<GlobalKeys>>,
+    /// Set while global bindings are turned off, see `suppress_global_keys`.
+    global_keys_suppressed: Arc<std::sync::atomic::AtomicBool>,
+    /// The active theme; observe it to re-render on theme changes.
+    theme: Entity<Theme>,
+    /// Themes available to `set_theme`, built-in ones first.
+    themes: Arc<RwLock<Vec<Theme>>>,
+    /// Colors the terminal can display; drawn colors are downsampled to fit.
+    color_depth: ColorDepth,
+    /// How drawn colors are presented, see `set_accessibility_mode`.
+    accessibility: watch::Sender<AccessibilityMode>,
+    /// Handlers for `Action::Custom`, by payload type.
+    action_handlers: Arc<RwLock<HashMap<TypeId, ActionHandler>>>,
+    /// Whether each page component (by type) is the active route; drives `spawn_page_task`.
+    page_activity: Arc<RwLock<HashMap<TypeId, watch::Sender<bool>>>>,
+    /// Callbacks registered with `Context::observe_with`.
+    observers: Arc<Mutex<Observers>>,
+    /// Messages sent with `MessageSender`s, waiting for their component.
+    mailboxes: Arc<Mutex<Mailboxes>>,
+    /// Area each component was last drawn into, for `Context::area`.
+    areas: Arc<Mutex<HashMap<ComponentKey, Rect>>>,
+    /// Width thresholds for `breakpoint`.
+    breakpoints: Breakpoints,
+    /// Terminal width of the frame being drawn (or last drawn).
+    screen_width: Arc<std::sync::atomic::AtomicU16>,
+    /// Terminal height of the frame being drawn (or last drawn).
+    screen_height: Arc<std::sync::atomic::AtomicU16>,
+    /// Frame timing for the debug overlay, see `enable_debug_overlay`.
+    debug_overlay: Arc<Mutex<DebugOverlay>>,
+    /// Draw timings, see `render_stats`.
+    render_stats: Arc<Mutex<RenderStats>>,
+    /// Tasks started with `spawn_cleanup`, awaited before the terminal is restored.
+    cleanup_tasks: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>>,
+    /// Bumped by `force_redraw` so `RenderCache`s rebuild.
+    cache_generation: Arc<std::sync::atomic::AtomicU64>,
+    /// Set by `force_redraw`; the next frame clears the terminal first.
+    full_redraw: Arc<std::sync::atomic::AtomicBool>,
+    /// Components drawn above the root, see `push_overlay`.
+    overlays: Arc<Mutex<OverlayStack>>,
+    /// Terminal modes to apply before the next frame, see `set_mouse_capture`.
+    terminal_modes: Arc<Mutex<TerminalModes>>,
+    /// Whether drawing is suspended, see `set_paused`.
+    paused: watch::Sender<bool>,
+    /// Frames per second redrawn without events, see `set_animation_fps`.
+    animation_fps: watch::Sender<u32>,
+    /// Calls waiting out their delay, see `debounce`.
+    debounce: crate::task::Debounce,
 }
 
 impl Clone for AppContext {
@@ -37,11 +136,73 @@ impl Clone for AppContext {
             re_render_tx: mpsc::UnboundedSender::clone(&self.re_render_tx),
             frame_count: Arc::clone(&self.frame_count),
             state: Arc::clone(&self.state),
+            clock: Arc::clone(&self.clock),
+            global_keys: Arc::clone(&self.global_keys),
+            global_keys_suppressed: Arc::clone(&self.global_keys_suppressed),
+            theme: Entity::clone(&self.theme),
+            themes: Arc::clone(&self.themes),
+            color_depth: self.color_depth,
+            accessibility: watch::Sender::clone(&self.accessibility),
+            action_handlers: Arc::clone(&self.action_handlers),
+            page_activity: Arc::clone(&self.page_activity),
+            observers: Arc::clone(&self.observers),
+            mailboxes: Arc::clone(&self.mailboxes),
+            areas: Arc::clone(&self.areas),
+            breakpoints: self.breakpoints,
+            screen_width: Arc::clone(&self.screen_width),
+            screen_height: Arc::clone(&self.screen_height),
+            debug_overlay: Arc::clone(&self.debug_overlay),
+            render_stats: Arc::clone(&self.render_stats),
+            cleanup_tasks: Arc::clone(&self.cleanup_tasks),
+            cache_generation: Arc::clone(&self.cache_generation),
+            full_redraw: Arc::clone(&self.full_redraw),
+            overlays: Arc::clone(&self.overlays),
+            terminal_modes: Arc::clone(&self.terminal_modes),
+            paused: watch::Sender::clone(&self.paused),
+            animation_fps: watch::Sender::clone(&self.animation_fps),
+            debounce: crate::task::Debounce::clone(&self.debounce),
         }
     }
 }
 
 impl AppContext {
+    /// Create a context with a fresh state store, returning the receiving end of the refresh channel.
+    pub(crate) fn create(clock: Arc<dyn Clock>) -> (Self, mpsc::UnboundedReceiver<()>) {
+        let (re_render_tx, re_render_rx) = mpsc::unbounded_channel();
+        let app = Self {
+            root: Arc::new(Mutex::new(None)),
+            re_render_tx,
+            frame_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            state: Arc::new(TypeMap::new()),
+            clock,
+            global_keys: Arc::new(RwLock::new(Vec::new())),
+            global_keys_suppressed: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            theme: Entity::new(Theme::default()),
+            themes: Arc::new(RwLock::new(Theme::builtin())),
+            color_depth: ColorDepth::TrueColor,
+            accessibility: watch::Sender::new(AccessibilityMode::Normal),
+            action_handlers: Arc::new(RwLock::new(HashMap::new())),
+            page_activity: Arc::new(RwLock::new(HashMap::new())),
+            observers: Arc::new(Mutex::new(HashMap::new())),
+            mailboxes: Arc::new(Mutex::new(HashMap::new())),
+            areas: Arc::new(Mutex::new(HashMap::new())),
+            breakpoints: Breakpoints::default(),
+            screen_width: Arc::new(std::sync::atomic::AtomicU16::new(0)),
+            screen_height: Arc::new(std::sync::atomic::AtomicU16::new(0)),
+            debug_overlay: Arc::new(Mutex::new(DebugOverlay::default())),
+            render_stats: Arc::new(Mutex::new(RenderStats::default())),
+            cleanup_tasks: Arc::new(Mutex::new(Vec::new())),
+            cache_generation: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            full_redraw: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            overlays: Arc::new(Mutex::new(OverlayStack::default())),
+            terminal_modes: Arc::new(Mutex::new(TerminalModes::default())),
+            paused: watch::Sender::new(false),
+            animation_fps: watch::Sender::new(0),
+            debounce: crate::task::Debounce::new(),
+        };
+        (app, re_render_rx)
+    }
+
     /// Create a new entity with the given value.
     pub fn new_entity<T>(&self, value: T) -> Entity<T>
     where
@@ -75,6 +236,121 @@ impl AppContext {
         crate::task::TaskHandle::new(join_handle.abort_handle())
     }
 
+    /// Run `f` once `delay` has passed without another `debounce` call under
+    /// the same `key`, e.g. to recompute search results when typing pauses
+    /// rather than on every keystroke. See `task::Debounce`.
+    pub fn debounce<K, F>(&self, key: K, delay: Duration, f: F)
+    where
+        K: std::hash::Hash,
+        F: FnOnce() + Send + 'static,
+    {
+        self.debounce.call(key, delay, f);
+    }
+
+    /// Run `f` every `period` of the application clock, starting right away,
+    /// until the returned handle is dropped or aborted. Tracking the handle
+    /// with a `TaskTracker` stops the interval along with the tracker's other
+    /// tasks instead. A tick that runs late delays the following ones instead
+    /// of causing a burst of catch-up ticks.
+    ///
+    /// ```ignore
+    /// self.tasks.track(cx.spawn_interval(Duration::from_secs(1), move |_| {
+    ///     let _ = stats.update(|s| s.sample());
+    /// }));
+    /// ```
+    pub fn spawn_interval<F>(&self, period: Duration, f: F) -> crate::task::IntervalHandle
+    where
+        F: FnMut(&AppContext) + Send + 'static,
+    {
+        crate::task::IntervalHandle::new(self.spawn_task(move |app| run_interval(app, period, f)))
+    }
+
+    /// Spawn a task and get back a handle to await its output, e.g. a move
+    /// from an AI opponent. The handle can also be aborted or tracked with
+    /// `TaskTracker` (see `TaskHandleResult::handle`); awaiting an aborted task
+    /// fails with `Error::Cancelled` instead of waiting forever.
+    ///
+    /// ```ignore
+    /// let search = cx.spawn_result(move |_| async move { agent.find_move(&board).await });
+    /// self.tasks.track(search.handle());
+    /// cx.spawn_detached(|app| async move {
+    ///     if let Ok(mv) = search.await { /* ... */ }
+    /// });
+    /// ```
+    pub fn spawn_result<T, F, Fut>(&self, f: F) -> crate::task::TaskHandleResult<T>
+    where
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let cx = AppContext::clone(self);
+        crate::task::TaskHandleResult::new(tokio::spawn(async move { f(cx).await }))
+    }
+
+    /// Run CPU-bound or blocking work (parsing a large file, decoding an image)
+    /// on tokio's blocking thread pool and await its result.
+    ///
+    /// Tasks from `spawn_task` share a few worker threads with the event loop, so
+    /// long synchronous work inside them stalls the UI; use this for anything that
+    /// doesn't `.await` for more than a few milliseconds. From a component,
+    /// `Context::spawn_blocking` hands the result straight back to it.
+    ///
+    /// ```ignore
+    /// cx.spawn_detached(|app| async move {
+    ///     if let Ok(index) = app.spawn_blocking(move || build_index(&path)).await {
+    ///         app.set(index);
+    ///         app.refresh();
+    ///     }
+    /// });
+    /// ```
+    pub fn spawn_blocking<T, F>(&self, f: F) -> crate::task::BlockingTask<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        crate::task::BlockingTask::new(tokio::task::spawn_blocking(f))
+    }
+
+    /// Spawn a task that the application waits for before it exits.
+    ///
+    /// Call this from `on_shutdown` to flush state to disk or notify a server:
+    /// the terminal is only restored once all cleanup tasks have finished or the
+    /// shutdown timeout (see `Application::with_shutdown_timeout`) has passed.
+    pub fn spawn_cleanup<F, Fut>(&self, f: F)
+    where
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let cx = AppContext::clone(self);
+        let join_handle = tokio::spawn(async move {
+            f(cx).await;
+        });
+        if let Ok(mut tasks) = self.cleanup_tasks.lock() {
+            tasks.push(join_handle);
+        }
+    }
+
+    /// Wait for the cleanup tasks, aborting those still running after `timeout`.
+    /// Returns false if any task had to be aborted.
+    pub(crate) async fn finish_cleanup(&self, timeout: Duration) -> bool {
+        let tasks = match self.cleanup_tasks.lock() {
+            Ok(mut tasks) => std::mem::take(&mut *tasks),
+            Err(_) => return false,
+        };
+        let aborts: Vec<_> = tasks.iter().map(|task| task.abort_handle()).collect();
+        let join_all = async {
+            for task in tasks {
+                let _ = task.await;
+            }
+        };
+        if tokio::time::timeout(timeout, join_all).await.is_ok() {
+            true
+        } else {
+            aborts.iter().for_each(|abort| abort.abort());
+            false
+        }
+    }
+
     /// Set the root component of the application.
     fn set_root_component(&self, root: Entity<dyn AnyComponent>) -> crate::Result<()> {
         let mut guard = self.root.lock().map_err(|_| crate::Error::LockPoisoned)?;
@@ -83,6 +359,12 @@ impl AppContext {
         Ok(())
     }
 
+    /// The root component set with `set_root`, if any. Use `Entity::downcast`
+    /// to reach it as its concrete type.
+    pub fn root(&self) -> Option<Entity<dyn AnyComponent>> {
+        self.root.lock().ok()?.as_ref().map(Entity::clone)
+    }
+
     /// Set the root component with automatic Arc/RwLock wrapping.
     /// This is a convenience method that handles the boilerplate of wrapping
     /// a component in Arc<RwLock<T>> and creating an Entity.
@@ -106,454 +388,2517 @@ impl AppContext {
         let _ = self.re_render_tx.send(());
     }
 
-    /// Get the total number of frames rendered.
-    pub fn frame_count(&self) -> u64 {
-        self.frame_count.load(std::sync::atomic::Ordering::Relaxed)
-    }
-
-    /// Store a value in the application state.
-    /// Use this to share state across components.
+    /// Run `f`, holding back the change notifications of the entities it
+    /// updates until it returns, then redraw once.
     ///
-    /// # Example
-    /// ```ignore
-    /// let shared = cx.new_entity(AppState::default());
-    /// cx.set(shared);  // Store for later retrieval
-    /// ```
-    pub fn set<T>(&self, value: T)
-    where
-        T: Send + Sync + 'static,
-    {
-        if let Ok(mut guard) = self.state.write() {
-            guard.insert(TypeId::of::<T>(), Arc::new(value));
-        }
-    }
-
-    /// Retrieve a value from the application state.
-    /// Returns None if the type was not previously stored.
+    /// An entity updated several times in `f` notifies its observers once,
+    /// and observers of different entities wake together, so a handler
+    /// touching several entities leads to a single frame. Only updates made on
+    /// this thread are held back; batches nest.
     ///
-    /// # Example
     /// ```ignore
-    /// let shared: Entity<AppState> = cx.get().expect("AppState not set");
+    /// cx.app().batch(|| {
+    ///     let _ = counter.update(|c| *c += 1);
+    ///     let _ = log.update(|log| log.push("incremented".into()));
+    /// });
     /// ```
-    pub fn get<T>(&self) -> Option<T>
-    where
-        T: Clone + Send + Sync + 'static,
-    {
-        self.state
-            .read()
-            .ok()
-            .and_then(|guard| guard.get(&TypeId::of::<T>()).cloned())
-            .and_then(|arc| arc.downcast::<T>().ok())
-            .map(|arc| (*arc).clone())
+    pub fn batch<R>(&self, f: impl FnOnce() -> R) -> R {
+        let res = crate::state::batch(f);
+        self.refresh();
+        res
     }
 
-    /// Check if a type is stored in the application state.
-    pub fn has<T: 'static>(&self) -> bool {
-        self.state
-            .read()
-            .map(|guard| guard.contains_key(&TypeId::of::<T>()))
-            .unwrap_or(false)
+    /// Turn mouse capture on or off, e.g. for a "selection mode" in which the
+    /// terminal handles the mouse so text can be selected and copied natively.
+    /// Takes effect before the next frame; capture is on when the application starts.
+    pub fn set_mouse_capture(&self, enabled: bool) {
+        if let Ok(mut modes) = self.terminal_modes.lock() {
+            modes.mouse_capture = enabled;
+        }
+        self.refresh();
     }
 
-    /// Get a value from application state, or return a default if not set.
-    /// This is safer than `get().expect()` - no panic possible.
-    ///
-    /// # Example
-    /// ```ignore
-    /// let shared: Entity<AppState> = cx.get_or_default();
-    /// // If AppState was never set, a default instance is created and stored
-    /// ```
-    pub fn get_or_default<T>(&self) -> Option<T>
-    where
-        T: Clone + Send + Sync + 'static + Default,
-    {
-        match self.get::<T>() {
-            Some(value) => Some(value),
-            None => {
-                let default = T::default();
-                self.set(default.clone());
-                Some(default)
-            }
+    /// Set the terminal window title, e.g. "rat-demo — Monitor". Takes effect
+    /// before the next frame. The previous title is restored on exit (unless
+    /// `Application::keep_title_on_exit`); terminals that don't support titles ignore this.
+    pub fn set_title(&self, title: &str) {
+        if let Ok(mut modes) = self.terminal_modes.lock() {
+            modes.title = Some(title.to_string());
         }
+        self.refresh();
     }
 
-    /// Get a value from application state, or create one using a closure if not set.
-    /// More flexible than `get_or_default()` when custom initialization is needed.
-    ///
-    /// # Example
-    /// ```ignore
-    /// let shared: Entity<AppState> = cx.get_or_insert_with(|| {
-    ///     AppState::new_with_config(config)
-    /// });
-    /// ```
-    pub fn get_or_insert_with<T, F>(&self, f: F) -> Option<T>
-    where
-        T: Clone + Send + Sync + 'static,
-        F: FnOnce() -> T,
-    {
-        match self.get::<T>() {
-            Some(value) => Some(value),
-            None => {
-                let value = f();
-                self.set(value.clone());
-                Some(value)
-            }
+    /// Cursor shape to use wherever an element shows the cursor, unless the
+    /// element requests its own with `request_cursor_shape` (a focused
+    /// `TextField` asks for a bar). Takes effect from the next frame.
+    pub fn set_cursor_style(&self, shape: CursorShape) {
+        if let Ok(mut modes) = self.terminal_modes.lock() {
+            modes.cursor_shape = shape;
         }
+        self.refresh();
     }
-}
 
-/// A specialized context passed to component methods.
-/// Inspired by GPUI's Context design - always bound to an entity.
-/// Note: For rendering area, use `frame.area()` instead.
-pub struct Context<V: ?Sized + Send + Sync> {
-    app: AppContext,
-    /// The entity this context is bound to. When the context is "cast" to another type
-    /// (for calling child components), this becomes None. Use `entity()` for self-reference
-    /// and `weak_entity()` for async operations.
-    handle: Option<WeakEntity<V>>,
-}
+    /// Allow or forbid the cursor. The cursor is only shown in frames where an
+    /// element places it; `show_cursor(false)` hides it even then.
+    pub fn show_cursor(&self, visible: bool) {
+        if let Ok(mut modes) = self.terminal_modes.lock() {
+            modes.cursor_visible = visible;
+        }
+        self.refresh();
+    }
 
-// Deref to AppContext for convenient access to app methods
-impl<V: ?Sized + Send + Sync> std::ops::Deref for Context<V> {
-    type Target = AppContext;
+    /// Whether mouse capture is on, see `set_mouse_capture`.
+    pub fn mouse_capture(&self) -> bool {
+        self.terminal_modes.lock().map(|modes| modes.mouse_capture).unwrap_or(true)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        &self.app
+    /// Repaint everything on the next frame: every `RenderCache` rebuilds and the
+    /// terminal is cleared before drawing. Done automatically on resize.
+    pub fn force_redraw(&self) {
+        self.cache_generation.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.full_redraw.store(true, std::sync::atomic::Ordering::Relaxed);
+        self.refresh();
     }
-}
 
-impl<V: ?Sized + Send + Sync> Context<V> {
-    /// Create a context bound to an entity. This is the primary constructor.
-    pub fn new(app: AppContext, handle: WeakEntity<V>) -> Self {
-        Self {
-            app,
-            handle: Some(handle),
+    /// Suspend or resume drawing. While paused, refresh requests are ignored
+    /// (events are still delivered); resuming redraws the whole screen.
+    ///
+    /// The application pauses itself while the terminal window is unfocused,
+    /// unless `Application::keep_drawing_when_unfocused` was called. Tasks that
+    /// animate or simulate should wait with `resumed` so they idle too.
+    pub fn set_paused(&self, paused: bool) {
+        let was_paused = self.paused.send_replace(paused);
+        if was_paused && !paused {
+            self.force_redraw();
         }
     }
 
-    /// Get a reference to the underlying AppContext.
-    /// Use this to access AppContext methods that are shadowed by Context methods
-    /// (like spawn/spawn_task for unbound async tasks).
-    pub fn app(&self) -> &AppContext {
-        &self.app
+    /// Whether drawing is paused, see `set_paused`.
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
     }
 
-    /// Observe an entity's changes.
-    /// Returns a TaskHandle that must be held or tracked to keep the observation active.
-    /// Dropping the handle cancels the observation.
-    pub fn observe<T>(&mut self, entity: &Entity<T>) -> crate::task::TaskHandle
-    where T: Send + Sync + 'static
-    {
-        let mut rx = entity.subscribe();
-        let tx = mpsc::UnboundedSender::clone(&self.app.re_render_tx);
-        let handle = tokio::spawn(async move {
-            while rx.changed().await.is_ok() {
-                let _ = tx.send(());
-            }
-        });
-        crate::task::TaskHandle::new(handle.abort_handle())
+    /// Redraw `fps` times per second even when nothing calls `refresh`, or only
+    /// on events and refreshes with 0 (the default).
+    ///
+    /// Input is handled as soon as it arrives either way; this only sets how
+    /// smoothly free-running animations (see `Animation`) are drawn. Every tick
+    /// renders the whole tree, so CPU use grows with the rate: keep it at 0
+    /// while nothing moves, and prefer `refresh` from the task that changes
+    /// state when updates are irregular. Takes effect immediately.
+    pub fn set_animation_fps(&self, fps: u32) {
+        self.animation_fps.send_replace(fps);
     }
 
-    /// Watch an entity: observe changes and read the current value.
-    /// This is a convenience method that combines `observe` and `entity.read`.
-    /// Returns (Value, TaskHandle).
-    pub fn watch<T, F, R>(&mut self, entity: &Entity<T>, f: F) -> (Option<R>, crate::task::TaskHandle)
-    where
-        T: Send + Sync + 'static,
-        F: FnOnce(&T) -> R,
-    {
-        let handle = self.observe(entity);
-        (entity.read(f).ok(), handle)
+    /// Rate set with `set_animation_fps`, 0 when drawing is event-driven only.
+    pub fn animation_fps(&self) -> u32 {
+        *self.animation_fps.borrow()
     }
 
-    /// Spawn an async task with access to the entity's WeakEntity.
-    /// This is the GPUI-style spawn that automatically provides a weak reference
-    /// to the entity for safe async access.
+    /// Wait until the application is not paused; returns at once if it isn't.
     ///
-    /// # Example
     /// ```ignore
-    /// fn save_data(&mut self, cx: &mut Context<Self>) {
-    ///     let data = self.data.clone();
-    ///     cx.spawn(|weak_self, app| async move {
-    ///         tokio::time::sleep(Duration::from_secs(1)).await;
-    ///         // Safe: if component was dropped, upgrade() returns None
-    ///         if let Some(entity) = weak_self.upgrade() {
-    ///             entity.update(|this| this.on_save_complete());
-    ///         }
-    ///         app.refresh();
-    ///     });
-    /// }
+    /// cx.spawn_page_task(move |app| async move {
+    ///     loop {
+    ///         app.resumed().await;
+    ///         state.update(|s| s.step());
+    ///         tokio::time::sleep(Duration::from_millis(33)).await;
+    ///     }
+    /// });
     /// ```
-    ///
-    /// # Panics
-    /// Panics if the context was not created with a handle (i.e., was cast from another context).
-    pub fn spawn<F, Fut>(&self, f: F)
-    where
-        V: 'static,
-        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
-    {
-        let weak = self.handle.as_ref().map(WeakEntity::clone)
-            .expect("Context::spawn requires a bound entity. Use AppContext::spawn for unbound contexts.");
-        let app = AppContext::clone(&self.app);
-        tokio::spawn(async move {
-            f(weak, app).await;
-        });
+    pub async fn resumed(&self) {
+        let mut paused = self.paused.subscribe();
+        let _ = paused.wait_for(|paused| !paused).await;
     }
 
-    /// Spawn a task and return a handle that can be used to cancel it.
-    /// Use this with `TaskTracker` for proper lifecycle management.
-    ///
-    /// # Panics
-    /// Panics if the context was not created with a handle.
-    pub fn spawn_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    /// Show `component` above the root and any open overlays. Until it is closed,
+    /// key, mouse and paste events go to it instead of the root; actions it
+    /// returns are handled by the root as usual. See the `overlay` module.
+    pub fn push_overlay<C>(&self, component: C) -> OverlayId
     where
-        V: 'static,
-        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
+        C: AnyComponent + 'static,
     {
-        let weak = self.handle.as_ref().map(WeakEntity::clone)
-            .expect("Context::spawn_task requires a bound entity. Use AppContext::spawn_task for unbound contexts.");
-        let app = AppContext::clone(&self.app);
-        let join_handle = tokio::spawn(async move {
-            f(weak, app).await;
-        });
-        crate::task::TaskHandle::new(join_handle.abort_handle())
+        self.open_overlay(component, true)
     }
 
-    /// Spawn an unbound async task (no WeakEntity reference).
-    /// Use this for background tasks that don't need to access the component.
-    /// Delegates to `AppContext::spawn`.
-    pub fn spawn_detached<F, Fut>(&self, f: F)
+    /// Show `component` above the root without sending it input, e.g. a toast.
+    pub fn push_passive_overlay<C>(&self, component: C) -> OverlayId
     where
-        F: FnOnce(AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
+        C: AnyComponent + 'static,
     {
-        self.app.spawn(f)
+        self.open_overlay(component, false)
     }
 
-    /// Spawn an unbound async task with cancellation handle.
-    /// Use this for background tasks that don't need to access the component.
-    /// Delegates to `AppContext::spawn_task`.
-    pub fn spawn_detached_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    fn open_overlay<C>(&self, component: C, modal: bool) -> OverlayId
     where
-        F: FnOnce(AppContext) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = ()> + Send + 'static,
+        C: AnyComponent + 'static,
     {
-        self.app.spawn_task(f)
+        let overlay = Entity::from_arc(Arc::new(RwLock::new(component)) as Arc<RwLock<dyn AnyComponent>>);
+        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(self), overlay.downgrade());
+        let _ = overlay.update(|comp| {
+            comp.on_mount_any(&mut cx);
+            comp.on_enter_any(&mut cx);
+        });
+        let id = self.overlay_stack().push(overlay, modal);
+        self.refresh();
+        id
     }
 
-    /// Cast this context to another view type.
+    /// Close the topmost overlay, returning its id.
+    pub fn pop_overlay(&self) -> Option<OverlayId> {
+        let mut overlays = self.overlay_stack();
+        let id = overlays.top()?;
+        overlays.close(id);
+        drop(overlays);
+        self.refresh();
+        Some(id)
+    }
+
+    /// Close an overlay, e.g. a toast whose time is up.
+    /// Returns false if it was already closed.
+    pub fn close_overlay(&self, id: OverlayId) -> bool {
+        let closed = self.overlay_stack().close(id);
+        if closed {
+            self.refresh();
+        }
+        closed
+    }
+
+    /// Close the overlay whose event handler is running, so an overlay can
+    /// dismiss itself. Elsewhere this closes the topmost overlay.
+    pub fn dismiss_overlay(&self) -> Option<OverlayId> {
+        let handling = self.overlay_stack().handling();
+        match handling {
+            Some(id) => self.close_overlay(id).then_some(id),
+            None => self.pop_overlay(),
+        }
+    }
+
+    /// Number of open overlays.
+    pub fn overlay_count(&self) -> usize {
+        self.overlay_stack().len()
+    }
+
+    fn overlay_stack(&self) -> std::sync::MutexGuard<'_, OverlayStack> {
+        self.overlays.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Call `on_exit` on the overlays closed since the last call.
+    fn finish_closed_overlays(&self) {
+        let closed = self.overlay_stack().take_closed();
+        for overlay in closed {
+            let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(self), overlay.downgrade());
+            let _ = overlay.update(|comp| comp.on_exit_any(&mut cx));
+        }
+    }
+
+    /// Get the current time from the application clock.
+    /// Prefer this over `Instant::now()` so tests can control time.
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Sleep for the given duration according to the application clock.
+    /// Prefer this over `tokio::time::sleep` in component tasks so tests can control time.
+    pub async fn sleep(&self, duration: Duration) {
+        self.clock.sleep(duration).await
+    }
+
+    /// Get the application clock.
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&self.clock)
+    }
+
+    /// Bind a key to an action for the whole application.
+    ///
+    /// Global bindings are checked before the event reaches the root component,
+    /// so pages don't have to handle keys like quit or "back to menu" themselves.
+    /// A component can keep a key for itself by returning true from `Component::captures_key`,
+    /// or turn all bindings off for a while with `suppress_global_keys`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// cx.bind_global(KeyCode::Char('q'), Action::Quit);
+    /// cx.bind_global(KeyCode::Char('m'), Action::Navigate("menu".to_string()));
+    /// ```
+    pub fn bind_global(&self, key: KeyCode, action: Action) {
+        self.bind_global_with(key, KeyModifiers::NONE, action);
+    }
+
+    /// Bind a key with modifiers (e.g. Ctrl+C) to an action for the whole application.
+    ///
+    /// Quit, back and navigation bindings are listed in the help overlay as
+    /// "Quit", "Back" and the route's name; use `bind_global_described` to
+    /// list others, such as custom actions.
+    pub fn bind_global_with(&self, key: KeyCode, modifiers: KeyModifiers, action: Action) {
+        let description = match &action {
+            Action::Quit => Some("Quit".to_string()),
+            Action::Back => Some("Back".to_string()),
+            Action::Navigate(route) | Action::Replace(route) | Action::Reset(route) => Some(route.clone()),
+            Action::NavigateTo(route) => Some(format!("{route:?}")),
+            _ => None,
+        };
+        self.insert_global(GlobalBinding { key, modifiers, action, description });
+    }
+
+    /// Like `bind_global_with`, listed in the help overlay with `description`.
+    pub fn bind_global_described(&self, key: KeyCode, modifiers: KeyModifiers, action: Action, description: impl Into<String>) {
+        let description = Some(description.into());
+        self.insert_global(GlobalBinding { key, modifiers, action, description });
+    }
+
+    /// Add a global binding, replacing the one for the same keys in place.
+    fn insert_global(&self, binding: GlobalBinding) {
+        if let Ok(mut keys) = self.global_keys.write() {
+            match keys.iter_mut().find(|b| (b.key, b.modifiers) == (binding.key, binding.modifiers)) {
+                Some(existing) => *existing = binding,
+                None => keys.push(binding),
+            }
+        }
+    }
+
+    /// The described global bindings, in the order they were bound, e.g. to
+    /// list them next to a page's own `key_bindings`. `define_app!` adds them
+    /// to its help overlay.
+    pub fn global_key_bindings(&self) -> KeyBindings {
+        let mut bindings = KeyBindings::new();
+        if let Ok(keys) = self.global_keys.read() {
+            for b in keys.iter() {
+                if let Some(description) = &b.description {
+                    bindings = bindings.bind_with(b.key, b.modifiers, description.clone());
+                }
+            }
+        }
+        bindings
+    }
+
+    /// Remove a global binding added with `bind_global`.
+    pub fn unbind_global(&self, key: KeyCode) {
+        self.unbind_global_with(key, KeyModifiers::NONE);
+    }
+
+    /// Remove a global binding added with `bind_global_with`.
+    pub fn unbind_global_with(&self, key: KeyCode, modifiers: KeyModifiers) {
+        if let Ok(mut keys) = self.global_keys.write() {
+            keys.retain(|b| (b.key, b.modifiers) != (key, modifiers));
+        }
+    }
+
+    /// Turn all global bindings off or back on, e.g. while a text input deep
+    /// in the tree has focus and its owner can't easily answer `captures_key`.
+    /// While suppressed, bound keys are delivered to the root like any other.
+    pub fn suppress_global_keys(&self, suppressed: bool) {
+        self.global_keys_suppressed.store(suppressed, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Whether global bindings are turned off with `suppress_global_keys`.
+    pub fn global_keys_suppressed(&self) -> bool {
+        self.global_keys_suppressed.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Let `key` toggle an overlay with FPS, frame count, render time, task and
+    /// entity counts in the top-right corner. Meant for development builds.
+    pub fn enable_debug_overlay(&self, key: KeyCode) {
+        self.bind_global(key, Action::custom(ToggleDebugOverlay));
+        self.on_action(|_: &ToggleDebugOverlay, cx| cx.toggle_debug_overlay());
+    }
+
+    /// Let `key` (e.g. Ctrl+P) open `palette` over the current page.
+    pub fn enable_command_palette(&self, palette: crate::palette::CommandPalette, key: KeyCode, modifiers: KeyModifiers) {
+        self.bind_global_with(key, modifiers, Action::custom(OpenCommandPalette));
+        self.on_action(move |_: &OpenCommandPalette, cx| {
+            palette.open(cx);
+        });
+    }
+
+    /// Show or hide the debug overlay.
+    pub fn toggle_debug_overlay(&self) {
+        if let Ok(mut overlay) = self.debug_overlay.lock() {
+            overlay.toggle();
+        }
+        self.refresh();
+    }
+
+    /// The statistics the debug overlay shows. FPS is only measured while the
+    /// overlay is visible.
+    pub fn debug_stats(&self) -> DebugStats {
+        let fps = self.debug_overlay.lock().map(|overlay| overlay.fps()).unwrap_or(0.0);
+        DebugStats {
+            fps,
+            frame_count: self.frame_count(),
+            tasks: tokio::runtime::Handle::try_current()
+                .map(|handle| handle.metrics().num_alive_tasks())
+                .unwrap_or(0),
+            entities: crate::state::entities_created(),
+            poisoned: crate::state::entities_poisoned(),
+            render_time: self.render_stats().last,
+        }
+    }
+
+    /// Last, average and maximum time spent drawing a frame (building the
+    /// element tree, rendering and flushing to the terminal).
+    pub fn render_stats(&self) -> RenderStats {
+        self.render_stats.lock().map(|stats| *stats).unwrap_or_default()
+    }
+
+    /// Start measuring render times afresh, e.g. after a warm-up period.
+    pub fn reset_render_stats(&self) {
+        if let Ok(mut stats) = self.render_stats.lock() {
+            *stats = RenderStats::default();
+        }
+    }
+
+    /// Mark the page component `V` as the active route (or not).
+    /// `define_app!` calls this around `on_enter`/`on_exit`; hand-written roots should too
+    /// if their pages use `spawn_page_task`.
+    pub fn set_page_active<V: ?Sized + 'static>(&self, active: bool) {
+        let type_id = TypeId::of::<V>();
+        if let Ok(mut pages) = self.page_activity.write() {
+            match pages.get(&type_id) {
+                Some(tx) => {
+                    tx.send_replace(active);
+                }
+                None => {
+                    pages.insert(type_id, watch::channel(active).0);
+                }
+            }
+        }
+    }
+
+    /// Whether the page component `V` is the active route.
+    /// Components that were never marked (e.g. a plain root component) count as active.
+    pub fn is_page_active<V: ?Sized + 'static>(&self) -> bool {
+        *self.page_activity_rx(TypeId::of::<V>()).borrow()
+    }
+
+    fn page_activity_rx(&self, type_id: TypeId) -> watch::Receiver<bool> {
+        match self.page_activity.write() {
+            Ok(mut pages) => pages.entry(type_id).or_insert_with(|| watch::channel(true).0).subscribe(),
+            // Poisoned: behave as if always active
+            Err(_) => watch::channel(true).1,
+        }
+    }
+
+    /// Handle `Action::Custom(T)` actions that reach the top of the component tree.
+    /// Registering a handler for the same type again replaces it. Custom actions
+    /// without a handler are dropped, failing a debug assertion.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // In a page
+    /// return Some(Action::custom(GameOver { score }));
+    ///
+    /// // At startup
+    /// cx.on_action(|over: &GameOver, cx| {
+    ///     if let Some(scores) = cx.get::<Entity<HighScores>>() {
+    ///         let _ = scores.update(|s| s.record(over.score));
+    ///     }
+    /// });
+    /// ```
+    pub fn on_action<T, F>(&self, handler: F)
+    where
+        T: Any + Send + Sync,
+        F: Fn(&T, &AppContext) + Send + Sync + 'static,
+    {
+        let handler: ActionHandler = Arc::new(move |action, cx| {
+            if let Some(value) = action.downcast_ref::<T>() {
+                handler(value, cx);
+            }
+        });
+        if let Ok(mut handlers) = self.action_handlers.write() {
+            handlers.insert(TypeId::of::<T>(), handler);
+        }
+    }
+
+    /// Perform an action returned by the root component.
+    /// Returns true if the application should quit.
+    fn perform_root_action(&self, action: Action) -> bool {
+        for action in action.flatten() {
+            match action {
+                Action::Quit => return true,
+                Action::Custom(custom) => {
+                    let handler = self.action_handlers
+                        .read()
+                        .ok()
+                        .and_then(|handlers| handlers.get(&custom.as_any().type_id()).cloned());
+                    // Printing would corrupt the terminal, so unhandled ones are dropped
+                    match handler {
+                        Some(handler) => handler(&custom, self),
+                        None => debug_assert!(false, "Unhandled custom action: {custom:?}"),
+                    }
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Look up the global action bound to a key event, if any.
+    fn global_action(&self, key: &KeyEvent) -> Option<Action> {
+        if self.global_keys_suppressed() {
+            return None;
+        }
+        // Shift is implied by the character itself ('?' vs '/'), so it is ignored for chars
+        let modifiers = match key.code {
+            KeyCode::Char(_) => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+        let keys = self.global_keys.read().ok()?;
+        keys.iter()
+            .find(|b| (b.key, b.modifiers) == (key.code, modifiers))
+            .map(|b| b.action.clone())
+    }
+
+    /// The active theme.
+    /// Observe it with `cx.observe(&cx.theme())` to re-render when it changes.
+    pub fn theme(&self) -> Entity<Theme> {
+        Entity::clone(&self.theme)
+    }
+
+    /// Make a theme available to `set_theme` and `next_theme`.
+    /// A theme with the same name replaces the registered one.
+    pub fn register_theme(&self, theme: Theme) {
+        if let Ok(mut themes) = self.themes.write() {
+            match themes.iter_mut().find(|t| t.name == theme.name) {
+                Some(existing) => *existing = theme,
+                None => themes.push(theme),
+            }
+        }
+    }
+
+    /// Names of all registered themes, in registration order.
+    pub fn theme_names(&self) -> Vec<String> {
+        self.themes
+            .read()
+            .map(|themes| themes.iter().map(|t| t.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Switch to a registered theme by name. Returns false if no such theme exists.
+    pub fn set_theme(&self, name: &str) -> bool {
+        let theme = self.themes
+            .read()
+            .ok()
+            .and_then(|themes| themes.iter().find(|t| t.name == name).cloned());
+        match theme {
+            Some(theme) => {
+                let _ = self.theme.update(|current| *current = theme);
+                self.refresh();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Switch to the registered theme after the active one, wrapping around.
+    pub fn next_theme(&self) {
+        let current = self.theme.read(|t| t.name.clone()).unwrap_or_default();
+        let names = self.theme_names();
+        let next = names
+            .iter()
+            .position(|name| *name == current)
+            .map_or(0, |i| (i + 1) % names.len());
+        if let Some(name) = names.get(next) {
+            self.set_theme(name);
+        }
+    }
+
+    /// The color depth detected for the terminal (or forced with `Application::with_color_depth`).
+    /// RGB colors are downsampled automatically; this is for components that want to pick
+    /// different colors up front, e.g. avoiding gradients on 16-color terminals.
+    pub fn color_depth(&self) -> ColorDepth {
+        self.color_depth
+    }
+
+    /// Present colors for limited terminals or users who need more contrast or
+    /// no color at all. Applies to everything drawn, so components need no
+    /// changes; emphasis they convey with theme colors becomes bold, dim,
+    /// underline or reverse video. Defaults to `NoColor` when `NO_COLOR` is set.
+    pub fn set_accessibility_mode(&self, mode: AccessibilityMode) {
+        if self.accessibility.send_replace(mode) != mode {
+            self.refresh();
+        }
+    }
+
+    /// The mode set with `set_accessibility_mode`.
+    pub fn accessibility_mode(&self) -> AccessibilityMode {
+        *self.accessibility.borrow()
+    }
+
+    /// Let `key` cycle through the accessibility modes.
+    pub fn enable_accessibility_toggle(&self, key: KeyCode) {
+        self.bind_global(key, Action::custom(CycleAccessibilityMode));
+        self.on_action(|_: &CycleAccessibilityMode, cx| {
+            cx.set_accessibility_mode(cx.accessibility_mode().next());
+        });
+    }
+
+    /// The breakpoint thresholds set with `Application::with_breakpoints`.
+    pub fn breakpoints(&self) -> Breakpoints {
+        self.breakpoints
+    }
+
+    /// Width class of the terminal. During rendering this reflects the frame being
+    /// drawn; before the first frame it is `Small`.
+    pub fn breakpoint(&self) -> Breakpoint {
+        let width = self.screen_width.load(std::sync::atomic::Ordering::Relaxed);
+        self.breakpoints.classify(width)
+    }
+
+    /// The whole frame, for sizing against the terminal rather than the area a
+    /// component was given (see `Context::area`). During rendering this is the
+    /// frame being drawn, so it doesn't lag a resize; before the first frame it
+    /// is empty.
+    pub fn screen_area(&self) -> Rect {
+        Rect::new(
+            0,
+            0,
+            self.screen_width.load(std::sync::atomic::Ordering::Relaxed),
+            self.screen_height.load(std::sync::atomic::Ordering::Relaxed),
+        )
+    }
+
+    /// Whether the terminal is at the `Large` breakpoint.
+    pub fn is_wide(&self) -> bool {
+        self.breakpoint() == Breakpoint::Large
+    }
+
+    /// Get the total number of frames rendered.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Store a value in the application state, replacing any previous value of
+    /// the same type. Use this to share services and state across components.
+    ///
+    /// The state holds one value per type for as long as the application runs;
+    /// every `AppContext` (and every `Context`) sees the same values. Replacing a
+    /// value doesn't affect clones fetched earlier, so store shared mutable state
+    /// as an `Entity` (see `set_entity`) rather than replacing it.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared = cx.new_entity(AppState::default());
+    /// cx.set(shared);  // Store for later retrieval
+    /// ```
+    pub fn set<T>(&self, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        self.state.insert(value);
+    }
+
+    /// Retrieve a clone of a value from the application state.
+    /// Returns None if the type was not previously stored.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared: Entity<AppState> = cx.get().expect("AppState not set");
+    /// ```
+    pub fn get<T>(&self) -> Option<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.state.get::<T>()
+    }
+
+    /// Check if a type is stored in the application state.
+    pub fn has<T: 'static>(&self) -> bool {
+        self.state.contains::<T>()
+    }
+
+    /// Remove a value from the application state, returning it.
+    pub fn remove<T>(&self) -> Option<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.state.remove::<T>()
+    }
+
+    /// Get a value from application state, or return a default if not set.
+    /// This is safer than `get().expect()` - no panic possible.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared: Entity<AppState> = cx.get_or_default();
+    /// // If AppState was never set, a default instance is created and stored
+    /// ```
+    pub fn get_or_default<T>(&self) -> Option<T>
+    where
+        T: Clone + Send + Sync + 'static + Default,
+    {
+        Some(self.state.get_or_insert_with(T::default))
+    }
+
+    /// Get a value from application state, or create one using a closure if not set.
+    /// More flexible than `get_or_default()` when custom initialization is needed.
+    /// The closure runs at most once, even if several tasks ask at the same time.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let shared: Entity<AppState> = cx.get_or_insert_with(|| {
+    ///     AppState::new_with_config(config)
+    /// });
+    /// ```
+    pub fn get_or_insert_with<T, F>(&self, f: F) -> Option<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        Some(self.state.get_or_insert_with(f))
+    }
+
+    /// Wrap `value` in an entity and store it as shared state, returning the entity.
+    /// Fetch it elsewhere with `entity::<T>()`.
+    pub fn set_entity<T>(&self, value: T) -> Entity<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        let entity = Entity::new(value);
+        self.set(Entity::clone(&entity));
+        entity
+    }
+
+    /// The shared `Entity<T>` stored with `set_entity` (or `set`).
+    pub fn entity<T>(&self) -> Option<Entity<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.get::<Entity<T>>()
+    }
+
+    /// The shared `Entity<T>`, creating it from `f()` if there is none yet.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let settings = cx.entity_or_insert_with(Settings::load);
+    /// ```
+    pub fn entity_or_insert_with<T, F>(&self, f: F) -> Entity<T>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        self.state.get_or_insert_with(|| Entity::new(f()))
+    }
+}
+
+/// A specialized context passed to component methods.
+/// Inspired by GPUI's Context design - always bound to an entity.
+/// Note: For rendering area, use `frame.area()` instead.
+pub struct Context<V: ?Sized + Send + Sync> {
+    app: AppContext,
+    /// The entity this context is bound to. When the context is "cast" to another type
+    /// (for calling child components), this becomes None. Use `entity()` for self-reference
+    /// and `weak_entity()` for async operations.
+    handle: Option<WeakEntity<V>>,
+    /// The id of `handle`'s entity. Unlike the handle, it survives the cast from
+    /// `dyn AnyComponent` to the component's own type (see `downcast`).
+    entity_id: Option<EntityId>,
+}
+
+// Deref to AppContext for convenient access to app methods
+impl<V: ?Sized + Send + Sync> std::ops::Deref for Context<V> {
+    type Target = AppContext;
+
+    fn deref(&self) -> &Self::Target {
+        &self.app
+    }
+}
+
+impl<V: ?Sized + Send + Sync> Context<V> {
+    /// Create a context bound to an entity. This is the primary constructor.
+    pub fn new(app: AppContext, handle: WeakEntity<V>) -> Self {
+        Self {
+            app,
+            entity_id: Some(handle.entity_id()),
+            handle: Some(handle),
+        }
+    }
+
+    /// Get a reference to the underlying AppContext.
+    /// Use this to access AppContext methods that are shadowed by Context methods
+    /// (like spawn/spawn_task for unbound async tasks).
+    pub fn app(&self) -> &AppContext {
+        &self.app
+    }
+
+    /// Observe an entity's changes.
+    /// Returns a TaskHandle that must be held or tracked to keep the observation active.
+    /// Dropping the handle cancels the observation.
+    pub fn observe<T>(&mut self, entity: &Entity<T>) -> crate::task::TaskHandle
+    where T: Send + Sync + 'static
+    {
+        let mut rx = entity.subscribe();
+        let tx = mpsc::UnboundedSender::clone(&self.app.re_render_tx);
+        let handle = tokio::spawn(async move {
+            while rx.changed().await.is_ok() {
+                let _ = tx.send(());
+            }
+        });
+        crate::task::TaskHandle::new(handle.abort_handle())
+    }
+
+    /// Run the `observe_with` callbacks of `component` whose entities changed since the last call.
+    /// The framework calls this right before rendering a component; hand-written roots that
+    /// render child components themselves should call it for each child, e.g.
+    /// `cx.cast::<Child>().flush_observers(&mut self.child)`.
+    pub fn flush_observers(&self, component: &mut V)
+    where
+        V: Sized + 'static,
+    {
+        let key = self.component_key();
+        // Take the callbacks out so they can register new observers without deadlocking
+        let Some(mut observers) = self.app.observers.lock().ok().and_then(|mut map| map.remove(&key)) else {
+            return;
+        };
+        observers.retain_mut(|observer| match observer.pending.upgrade() {
+            Some(pending) => {
+                if pending.swap(false, std::sync::atomic::Ordering::AcqRel) {
+                    (observer.callback)(component);
+                }
+                true
+            }
+            None => false,
+        });
+        if let Ok(mut map) = self.app.observers.lock() {
+            let added = map.remove(&key).unwrap_or_default();
+            observers.extend(added);
+            map.insert(key, observers);
+        }
+    }
+
+    /// Observe an entity and run `f` on this component when it changes.
+    ///
+    /// The callback runs between frames, right before the component is rendered, with
+    /// the entity's current value; several changes in one frame result in one call.
+    /// Use it to keep derived fields in sync instead of recomputing them in every handler.
+    /// Callbacks belong to the component's entity, so each instance of a component gets
+    /// its own; `define_app!` pages, which have none, are told apart by type. Aborting the
+    /// returned handle unregisters the callback.
+    ///
+    /// # Example
+    /// ```ignore
+    /// self.tasks.track(cx.observe_with(&self.query, |this: &mut Self, query: &String| {
+    ///     this.results = this.search(query);
+    /// }));
+    /// ```
+    pub fn observe_with<T, F>(&mut self, entity: &Entity<T>, mut f: F) -> crate::task::TaskHandle
+    where
+        V: Sized + 'static,
+        T: Send + Sync + 'static,
+        F: FnMut(&mut V, &T) + Send + 'static,
+    {
+        let pending = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let source = Entity::clone(entity);
+        let observer = PendingObserver {
+            pending: Arc::downgrade(&pending),
+            callback: Box::new(move |component: &mut dyn Any| {
+                if let Some(component) = component.downcast_mut::<V>() {
+                    let _ = source.read(|value| f(component, value));
+                }
+            }),
+        };
+        if let Ok(mut map) = self.app.observers.lock() {
+            map.entry(self.component_key()).or_default().push(observer);
+        }
+
+        let mut rx = entity.subscribe();
+        let tx = mpsc::UnboundedSender::clone(&self.app.re_render_tx);
+        let handle = tokio::spawn(async move {
+            while rx.changed().await.is_ok() {
+                pending.store(true, std::sync::atomic::Ordering::Release);
+                let _ = tx.send(());
+            }
+        });
+        crate::task::TaskHandle::new(handle.abort_handle())
+    }
+
+    /// A sender that tasks can use to hand values to this component's
+    /// `Component::on_message`, instead of upgrading a `WeakEntity` to mutate it.
+    ///
+    /// Messages are queued and delivered between frames, right before the
+    /// component renders (pages of `define_app!` get theirs whether shown or
+    /// not), each sending a request to redraw. They arrive in the order they
+    /// were sent, across all senders for the component; ones sent from
+    /// `on_message` itself wait for the next frame. Nothing is delivered while
+    /// the application is paused. Messages are addressed to the component's
+    /// entity, so each instance receives only its own, like `observe_with`.
+    ///
+    /// There is no backpressure: `send` never blocks, and the queue grows
+    /// until the next frame. A task producing faster than frames are drawn
+    /// should batch its values, or check `MessageSender::pending`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let results = cx.message_sender::<SearchResults>();
+    /// cx.spawn_page_task(move |_| async move {
+    ///     results.send(search(&query).await);
+    /// });
+    ///
+    /// fn on_message(&mut self, message: Message, _cx: &mut Context<Self>) {
+    ///     if let Ok(results) = message.downcast::<SearchResults>() {
+    ///         self.results = results;
+    ///     }
+    /// }
+    /// ```
+    pub fn message_sender<M: Send + 'static>(&self) -> MessageSender<M>
+    where
+        V: Component,
+    {
+        MessageSender {
+            mailboxes: Arc::clone(&self.app.mailboxes),
+            target: self.component_key(),
+            re_render_tx: mpsc::UnboundedSender::clone(&self.app.re_render_tx),
+            _message: PhantomData,
+        }
+    }
+
+    /// Deliver the messages queued for `component` to its `on_message`. The
+    /// framework calls this right before rendering a component; hand-written
+    /// roots that render child components themselves should call it for each
+    /// child, e.g. `cx.cast::<Child>().flush_messages(&mut self.child)`.
+    pub fn flush_messages(&mut self, component: &mut V)
+    where
+        V: Component,
+    {
+        // Take the queue out so `on_message` can send more without deadlocking
+        let queued = match self.app.mailboxes.lock() {
+            Ok(mut mailboxes) => mailboxes.get_mut(&self.component_key()).map(std::mem::take),
+            Err(_) => None,
+        };
+        for message in queued.unwrap_or_default() {
+            component.on_message(message, self);
+        }
+    }
+
+    /// Like `observe`, but re-render at most once per `interval` for this entity.
+    /// See `Entity::throttle`.
+    pub fn observe_throttled<T>(&mut self, entity: &Entity<T>, interval: Duration) -> crate::task::TaskHandle
+    where T: Send + Sync + 'static
+    {
+        let mut rx = entity.throttle(interval);
+        let tx = mpsc::UnboundedSender::clone(&self.app.re_render_tx);
+        let handle = tokio::spawn(async move {
+            while rx.changed().await.is_ok() {
+                let _ = tx.send(());
+            }
+        });
+        crate::task::TaskHandle::new(handle.abort_handle())
+    }
+
+    /// Observe an `EntityList`: re-render when items are inserted, removed or moved,
+    /// and when any item currently in the list changes.
+    pub fn observe_list<T>(&mut self, list: &EntityList<T>) -> crate::task::TaskHandle
+    where T: Send + Sync + 'static
+    {
+        let list = EntityList::clone(list);
+        let tx = mpsc::UnboundedSender::clone(&self.app.re_render_tx);
+        let handle = tokio::spawn(async move {
+            let mut list_rx = list.subscribe();
+            loop {
+                // Forward item changes into one channel; the forwarders end once it is dropped
+                let (item_tx, mut item_rx) = mpsc::unbounded_channel();
+                for entity in list.entities() {
+                    let mut rx = entity.subscribe();
+                    let item_tx = mpsc::UnboundedSender::clone(&item_tx);
+                    tokio::spawn(async move {
+                        while rx.changed().await.is_ok() {
+                            if item_tx.send(()).is_err() {
+                                break;
+                            }
+                        }
+                    });
+                }
+                drop(item_tx);
+
+                loop {
+                    tokio::select! {
+                        changed = list_rx.changed() => {
+                            if changed.is_err() {
+                                return;
+                            }
+                            let _ = tx.send(());
+                            break; // Resubscribe to the new set of items
+                        }
+                        Some(()) = item_rx.recv() => {
+                            let _ = tx.send(());
+                        }
+                    }
+                }
+            }
+        });
+        crate::task::TaskHandle::new(handle.abort_handle())
+    }
+
+    /// Watch an entity: observe changes and read the current value.
+    /// This is a convenience method that combines `observe` and `entity.read`.
+    /// Returns (Value, TaskHandle).
+    pub fn watch<T, F, R>(&mut self, entity: &Entity<T>, f: F) -> (Option<R>, crate::task::TaskHandle)
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&T) -> R,
+    {
+        let handle = self.observe(entity);
+        (entity.read(f).ok(), handle)
+    }
+
+    /// Spawn an async task with access to the entity's WeakEntity.
+    /// This is the GPUI-style spawn that automatically provides a weak reference
+    /// to the entity for safe async access.
+    ///
+    /// # Example
+    /// ```ignore
+    /// fn save_data(&mut self, cx: &mut Context<Self>) {
+    ///     let data = self.data.clone();
+    ///     cx.spawn(|weak_self, app| async move {
+    ///         tokio::time::sleep(Duration::from_secs(1)).await;
+    ///         // Safe: if component was dropped, upgrade() returns None
+    ///         if let Some(entity) = weak_self.upgrade() {
+    ///             entity.update(|this| this.on_save_complete());
+    ///         }
+    ///         app.refresh();
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the context was not created with a handle (i.e., was cast from another context).
+    pub fn spawn<F, Fut>(&self, f: F)
+    where
+        V: 'static,
+        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let weak = self.handle.as_ref().map(WeakEntity::clone)
+            .expect("Context::spawn requires a bound entity. Use AppContext::spawn for unbound contexts.");
+        let app = AppContext::clone(&self.app);
+        tokio::spawn(async move {
+            f(weak, app).await;
+        });
+    }
+
+    /// Spawn a task and return a handle that can be used to cancel it.
+    /// Use this with `TaskTracker` for proper lifecycle management.
+    ///
+    /// # Panics
+    /// Panics if the context was not created with a handle.
+    pub fn spawn_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    where
+        V: 'static,
+        F: FnOnce(WeakEntity<V>, AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let weak = self.handle.as_ref().map(WeakEntity::clone)
+            .expect("Context::spawn_task requires a bound entity. Use AppContext::spawn_task for unbound contexts.");
+        let app = AppContext::clone(&self.app);
+        let join_handle = tokio::spawn(async move {
+            f(weak, app).await;
+        });
+        crate::task::TaskHandle::new(join_handle.abort_handle())
+    }
+
+    /// Run `work` on the blocking thread pool (see `AppContext::spawn_blocking`)
+    /// and pass its result to `on_done` on this component, then re-render.
+    ///
+    /// `on_done` is skipped if the component was dropped or `work` panicked.
+    /// Track the returned handle to drop results that arrive after `on_exit`.
+    ///
+    /// ```ignore
+    /// let path = self.path.clone();
+    /// let handle = cx.spawn_blocking(move || std::fs::read_to_string(path), |this: &mut Self, text| {
+    ///     this.contents = text.ok();
+    /// });
+    /// self.tasks.track(handle);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the context was not created with a handle.
+    pub fn spawn_blocking<T, W, D>(&self, work: W, on_done: D) -> crate::task::TaskHandle
+    where
+        V: Sized + 'static,
+        T: Send + 'static,
+        W: FnOnce() -> T + Send + 'static,
+        D: FnOnce(&mut V, T) + Send + 'static,
+    {
+        let weak = self.handle.clone()
+            .expect("Context::spawn_blocking requires a bound entity. Use AppContext::spawn_blocking for unbound contexts.");
+        let task = self.app.spawn_blocking(work);
+        self.app.spawn_task(move |app| async move {
+            let Ok(value) = task.await else {
+                return;
+            };
+            if let Some(entity) = weak.upgrade() {
+                let _ = entity.update(|this| on_done(this, value));
+                app.refresh();
+            }
+        })
+    }
+
+    /// Spawn an unbound async task (no WeakEntity reference).
+    /// Use this for background tasks that don't need to access the component.
+    /// Delegates to `AppContext::spawn`.
+    pub fn spawn_detached<F, Fut>(&self, f: F)
+    where
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.app.spawn(f)
+    }
+
+    /// Spawn an unbound async task with cancellation handle.
+    /// Use this for background tasks that don't need to access the component.
+    /// Delegates to `AppContext::spawn_task`.
+    pub fn spawn_detached_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    where
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.app.spawn_task(f)
+    }
+
+    /// Like `AppContext::spawn_interval`, but ticking only while this page is
+    /// active, see `spawn_page_task`. Ticks missed while it was hidden are skipped.
+    pub fn spawn_page_interval<F>(&self, period: Duration, f: F) -> crate::task::IntervalHandle
+    where
+        V: 'static,
+        F: FnMut(&AppContext) + Send + 'static,
+    {
+        crate::task::IntervalHandle::new(self.spawn_page_task(move |app| run_interval(app, period, f)))
+    }
+
+    /// Spawn a background task that only runs while this page is the active route
+    /// and the application isn't paused (see `AppContext::set_paused`).
+    ///
+    /// The task is suspended at its next `.await` when the page is navigated away from
+    /// and picks up where it left off when the page is entered again, so game loops and
+    /// generators don't burn CPU in the background. Spawn it once in `on_mount`; there is
+    /// no need to abort it in `on_exit`.
+    pub fn spawn_page_task<F, Fut>(&self, f: F) -> crate::task::TaskHandle
+    where
+        V: 'static,
+        F: FnOnce(AppContext) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let mut active = self.app.page_activity_rx(TypeId::of::<V>());
+        // The task holds an `AppContext`, so this channel stays open while it runs
+        let mut paused = self.app.paused.subscribe();
+        self.app.spawn_task(move |app| async move {
+            let task = f(app);
+            tokio::pin!(task);
+            loop {
+                // Don't poll the task at all while the page is hidden or the app paused
+                while !*active.borrow_and_update() || *paused.borrow_and_update() {
+                    tokio::select! {
+                        changed = active.changed() => {
+                            if changed.is_err() {
+                                return;
+                            }
+                        }
+                        _ = paused.changed() => {}
+                    }
+                }
+                tokio::select! {
+                    _ = &mut task => return,
+                    changed = active.changed() => {
+                        if changed.is_err() {
+                            // Nobody can pause us anymore; run to completion
+                            task.await;
+                            return;
+                        }
+                    }
+                    _ = paused.changed() => {}
+                }
+            }
+        })
+    }
+
+    /// Cast this context to another view type.
     /// Note: The cast context will NOT have a handle. Use `entity.update_with_cx(cx, ...)`
     /// pattern for proper child component lifecycle.
     pub fn cast<U: ?Sized + Send + Sync + 'static>(&self) -> Context<U> {
         Context {
             app: AppContext::clone(&self.app),
             handle: None,
+            entity_id: None,
+        }
+    }
+
+    /// Who `observe_with` callbacks, messages and the area tracked through this
+    /// context belong to.
+    fn component_key(&self) -> ComponentKey
+    where
+        V: 'static,
+    {
+        match self.entity_id {
+            Some(id) => ComponentKey::Entity(id),
+            None => ComponentKey::Type(TypeId::of::<V>()),
+        }
+    }
+
+    /// Wrap the element rendered by this component so the area it is drawn into
+    /// becomes available from `area`. The framework does this for every component
+    /// rendered through `Component` and for `define_app!` pages.
+    pub fn track_area(&self, element: Box<dyn Element>) -> Box<dyn Element>
+    where
+        V: 'static,
+    {
+        Box::new(AreaTracker {
+            inner: element,
+            key: self.component_key(),
+            areas: Arc::clone(&self.app.areas),
+        })
+    }
+
+    /// The area this component was drawn into in the previous frame.
+    ///
+    /// Element trees are built before they are laid out, so this is always one frame
+    /// behind: it is empty before the first frame and lags a resize by one frame
+    /// (which re-renders immediately). Use it for layout decisions such as stacking
+    /// panes vertically on narrow terminals; `AppContext::screen_area` has the
+    /// size of the frame being drawn.
+    pub fn area(&self) -> Rect
+    where
+        V: 'static,
+    {
+        self.app
+            .areas
+            .lock()
+            .ok()
+            .and_then(|areas| areas.get(&self.component_key()).copied())
+            .unwrap_or_default()
+    }
+
+    /// Get the entity ID of the component this context is bound to.
+    /// Returns None if the context was cast from another type.
+    pub fn entity_id(&self) -> Option<EntityId> {
+        self.entity_id
+    }
+
+    /// Get a weak handle to the component this context is bound to.
+    /// Returns None if the context was cast from another type.
+    /// Use this for async operations to safely check if the entity still exists.
+    pub fn weak_entity(&self) -> Option<WeakEntity<V>> {
+        self.handle.as_ref().map(WeakEntity::clone)
+    }
+
+    /// Get a strong handle to the component this context is bound to.
+    /// Returns None if the context was cast or if the entity was dropped.
+    pub fn entity(&self) -> Option<Entity<V>> {
+        self.handle.as_ref().and_then(|h| h.upgrade())
+    }
+
+    /// Explicitly trigger a re-render.
+    pub fn notify(&self) {
+        self.app.refresh();
+    }
+}
+
+impl Context<dyn AnyComponent> {
+    /// Cast to the concrete type of the component this context is bound to. Unlike
+    /// `cast`, the result keeps the entity id, so the component's own registrations
+    /// (such as `observe_with` callbacks) are found from either context.
+    pub(crate) fn downcast<U: Component>(&self) -> Context<U> {
+        Context {
+            app: AppContext::clone(&self.app),
+            handle: None,
+            entity_id: self.entity_id,
+        }
+    }
+}
+
+/// EventContext for event handling, currently identical to Context but renamed for clarity.
+pub type EventContext<V> = Context<V>;
+
+/// Sends `M`s to a component's `Component::on_message`, see `Context::message_sender`.
+pub struct MessageSender<M> {
+    mailboxes: Arc<Mutex<Mailboxes>>,
+    target: ComponentKey,
+    re_render_tx: mpsc::UnboundedSender<()>,
+    _message: PhantomData<fn(M)>,
+}
+
+impl<M: Send + 'static> MessageSender<M> {
+    /// Queue `message` for the component and request a frame. Returns false,
+    /// dropping the message, once the application has shut down.
+    pub fn send(&self, message: M) -> bool {
+        if self.re_render_tx.is_closed() {
+            return false;
+        }
+        if let Ok(mut mailboxes) = self.mailboxes.lock() {
+            mailboxes.entry(self.target).or_default().push_back(Message::new(message));
+        }
+        self.re_render_tx.send(()).is_ok()
+    }
+
+    /// Number of messages queued for the component and not yet delivered,
+    /// including ones from other senders.
+    pub fn pending(&self) -> usize {
+        self.mailboxes
+            .lock()
+            .map(|mailboxes| mailboxes.get(&self.target).map_or(0, VecDeque::len))
+            .unwrap_or(0)
+    }
+}
+
+impl<M> Clone for MessageSender<M> {
+    fn clone(&self) -> Self {
+        Self {
+            mailboxes: Arc::clone(&self.mailboxes),
+            target: self.target,
+            re_render_tx: mpsc::UnboundedSender::clone(&self.re_render_tx),
+            _message: PhantomData,
+        }
+    }
+}
+
+/// Largest `Event::Paste` delivered in one piece, unless configured.
+const DEFAULT_MAX_PASTE_LEN: usize = 64 * 1024;
+
+/// How long cleanup tasks may run after the application quits, unless configured.
+pub(crate) const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Custom action bound by `AppContext::enable_command_palette`.
+struct OpenCommandPalette;
+
+/// Custom action bound by `AppContext::enable_accessibility_toggle`.
+struct CycleAccessibilityMode;
+
+/// Main application handle.
+pub struct Application {
+    clock: Arc<dyn Clock>,
+    /// Overrides the detected color depth.
+    color_depth: Option<ColorDepth>,
+    /// Overrides the accessibility mode detected from `NO_COLOR`.
+    accessibility: Option<AccessibilityMode>,
+    breakpoints: Breakpoints,
+    /// How long to wait for cleanup tasks when the application quits.
+    shutdown_timeout: Duration,
+    /// Longest paste delivered as a single `Event::Paste`, in bytes.
+    max_paste_len: usize,
+    /// Whether a title set with `AppContext::set_title` outlives the application.
+    keep_title: bool,
+    /// Whether the last frame is printed to the normal screen on exit.
+    keep_output: bool,
+    /// Whether focus events pause and resume drawing, see `AppContext::set_paused`.
+    pause_when_unfocused: bool,
+    /// How often the root's `on_tick` runs, `None` for never.
+    tick_rate: Option<Duration>,
+    /// File to record input events to, if recording was requested.
+    #[cfg(feature = "record")]
+    record_path: Option<std::path::PathBuf>,
+}
+
+impl Default for Application {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Application {
+    /// Create a new application instance.
+    pub fn new() -> Self {
+        Self {
+            clock: Arc::new(RealClock),
+            color_depth: None,
+            accessibility: None,
+            breakpoints: Breakpoints::default(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            max_paste_len: DEFAULT_MAX_PASTE_LEN,
+            keep_title: false,
+            keep_output: false,
+            pause_when_unfocused: true,
+            tick_rate: None,
+            #[cfg(feature = "record")]
+            record_path: None,
+        }
+    }
+
+    /// Use a custom clock, e.g. a `TestClock` for deterministic tests and replays.
+    pub fn with_clock(mut self, clock: impl Clock) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Skip color detection and render for the given color depth.
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.color_depth = Some(depth);
+        self
+    }
+
+    /// Start in the given accessibility mode instead of checking `NO_COLOR`.
+    pub fn with_accessibility_mode(mut self, mode: AccessibilityMode) -> Self {
+        self.accessibility = Some(mode);
+        self
+    }
+
+    /// Set the terminal widths at which `AppContext::breakpoint` switches to
+    /// `Medium` and `Large` (80 and 120 columns by default).
+    pub fn with_breakpoints(mut self, breakpoints: Breakpoints) -> Self {
+        self.breakpoints = breakpoints;
+        self
+    }
+
+    /// Set how long to wait for tasks started with `AppContext::spawn_cleanup`
+    /// before giving up on them (3 seconds by default).
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Call `Component::on_tick` on the root every `rate`, each time followed
+    /// by a frame. Off by default, so apps only redraw on events and refreshes.
+    pub fn with_tick_rate(mut self, rate: Duration) -> Self {
+        self.tick_rate = Some(rate);
+        self
+    }
+
+    /// Split pastes longer than `max_len` bytes into several `Event::Paste`s
+    /// (64 KiB by default), so a huge paste can't stall a single event handler.
+    pub fn with_max_paste_len(mut self, max_len: usize) -> Self {
+        self.max_paste_len = max_len;
+        self
+    }
+
+    /// Leave the title set with `AppContext::set_title` in place when the
+    /// application exits, instead of restoring the terminal's previous title.
+    pub fn keep_title_on_exit(mut self) -> Self {
+        self.keep_title = true;
+        self
+    }
+
+    /// Leave the last frame in the terminal after exit, like `git log` does,
+    /// instead of restoring the screen as it was. The application still runs on
+    /// the alternate screen; on exit its last frame (without blank rows at the
+    /// bottom) is printed to the normal screen, where it stays in the scrollback,
+    /// and the shell prompt continues below it.
+    pub fn keep_output_on_exit(mut self) -> Self {
+        self.keep_output = true;
+        self
+    }
+
+    /// Keep drawing while the terminal window is unfocused, instead of pausing
+    /// until it regains focus. Terminals that don't report focus never pause.
+    pub fn keep_drawing_when_unfocused(mut self) -> Self {
+        self.pause_when_unfocused = false;
+        self
+    }
+
+    /// Record every input event (with its frame number and timestamp) to a file.
+    /// The recording can be fed back with `Application::replay`.
+    #[cfg(feature = "record")]
+    pub fn record_events(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.record_path = Some(path.into());
+        self
+    }
+
+    /// Replay a recorded session against a fresh component without a terminal.
+    ///
+    /// Each event is delivered at its recorded time using a `TestClock`, so
+    /// clock-driven components behave exactly as they did while recording.
+    /// Returns the buffer rendered after each event; replay stops early on `Action::Quit`.
+    #[cfg(feature = "record")]
+    pub fn replay<C>(path: impl AsRef<std::path::Path>, component: C, width: u16, height: u16) -> anyhow::Result<Vec<ratatui::buffer::Buffer>>
+    where
+        C: AnyComponent + 'static,
+    {
+        let events = crate::replay::load_events(path)?;
+        let rt = Runtime::new().map_err(|e| anyhow::anyhow!("Failed to start tokio: {}", e))?;
+        let clock = crate::clock::TestClock::new();
+        let _guard = rt.enter();
+        let (app, _re_render_rx) = AppContext::create(Arc::new(clock.clone()));
+        let root = Entity::from_arc(Arc::new(RwLock::new(component)) as Arc<RwLock<dyn AnyComponent>>);
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(width, height))?;
+
+        let result = rt.block_on(async {
+            mount_root(&app, &root)?;
+            resize_root(&app, &root, width, height)?;
+            let mut frames = Vec::with_capacity(events.len());
+            for recorded in events {
+                let target = Duration::from_millis(recorded.elapsed_ms);
+                if target > clock.elapsed() {
+                    clock.advance(target - clock.elapsed());
+                }
+                // Let clock-driven tasks observe the new time before the event arrives
+                tokio::task::yield_now().await;
+
+                let action = dispatch_event(&app, &root, recorded.event)?;
+                terminal.draw(|frame| render_root(&app, &root, frame))?;
+                frames.push(terminal.backend().buffer().clone());
+
+                if action.is_some_and(|action| app.perform_root_action(action)) && quit_confirmed(&app, &root)? {
+                    shutdown_root(&app, &root)?;
+                    app.finish_cleanup(DEFAULT_SHUTDOWN_TIMEOUT).await;
+                    break;
+                }
+            }
+            Ok(frames)
+        });
+        drop(_guard);
+        rt.shutdown_timeout(Duration::from_millis(100));
+        result
+    }
+
+    /// Mount a component and render a single frame without a terminal.
+    ///
+    /// Runs with a `TestClock` on a private runtime; tasks spawned by the component
+    /// are dropped afterwards. See the `testing` module for snapshot assertions.
+    pub fn render_headless<C>(component: C, width: u16, height: u16) -> anyhow::Result<ratatui::buffer::Buffer>
+    where
+        C: AnyComponent + 'static,
+    {
+        let rt = Runtime::new().map_err(|e| anyhow::anyhow!("Failed to start tokio: {}", e))?;
+        let _guard = rt.enter();
+        let (app, _re_render_rx) = AppContext::create(Arc::new(crate::clock::TestClock::new()));
+        let root = Entity::from_arc(Arc::new(RwLock::new(component)) as Arc<RwLock<dyn AnyComponent>>);
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(width, height))?;
+
+        let result = rt.block_on(async {
+            mount_root(&app, &root)?;
+            resize_root(&app, &root, width, height)?;
+            terminal.draw(|frame| render_root(&app, &root, frame))?;
+            shutdown_root(&app, &root)?;
+            app.finish_cleanup(DEFAULT_SHUTDOWN_TIMEOUT).await;
+            Ok(terminal.backend().buffer().clone())
+        });
+        drop(_guard);
+        rt.shutdown_timeout(Duration::from_millis(100));
+        result
+    }
+
+    /// Run the application with the given closure that receives a context.
+    pub fn run<F>(self, setup: F) -> anyhow::Result<()>
+    where
+        F: FnOnce(&AppContext) -> anyhow::Result<()>,
+    {
+        let rt = Runtime::new().map_err(|e| anyhow::anyhow!("Failed to start tokio: {}", e))?;
+        let (mut app_context, re_render_rx) = AppContext::create(Arc::clone(&self.clock));
+        app_context.color_depth = self.color_depth.unwrap_or_else(ColorDepth::detect);
+        app_context.accessibility.send_replace(self.accessibility.unwrap_or_else(AccessibilityMode::detect));
+        app_context.breakpoints = self.breakpoints;
+        let root = Arc::clone(&app_context.root);
+
+        let _guard = rt.enter();
+        setup(&app_context)?;
+        drop(_guard);
+
+        let actual_root: Entity<dyn AnyComponent> = {
+            let guard = root.lock().map_err(|_| anyhow::anyhow!("Root mutex poisoned"))?;
+            guard.as_ref().map(Entity::clone).unwrap_or_else(|| {
+                Entity::from_arc(Arc::new(RwLock::new(DummyView)) as Arc<RwLock<dyn AnyComponent>>)
+            })
+        };
+
+        let result = rt.block_on(async move {
+            self.run_loop(app_context, actual_root, re_render_rx).await
+        });
+
+        // Ensure we don't hang forever on background tasks (like infinite loops in components)
+        rt.shutdown_timeout(Duration::from_millis(100));
+
+        result
+    }
+
+    async fn run_loop(&self, app: AppContext, root: Entity<dyn AnyComponent>, re_render_rx: mpsc::UnboundedReceiver<()>) -> anyhow::Result<()> {
+        enable_raw_mode()?;
+        let mut stdout = stdout();
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture, event::EnableFocusChange, event::EnableBracketedPaste)?;
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
+
+        mount_root(&app, &root)?;
+        let size = terminal.size()?;
+        resize_root(&app, &root, size.width, size.height)?;
+
+        let mut terminal_sync = TerminalSync::new(TerminalModes::default());
+        let mut last_frame = None;
+        let result = self.run_app_loop(app, &mut terminal, &mut terminal_sync, &mut last_frame, root, re_render_rx).await;
+
+        // Reset every mode, whatever the application changed at runtime
+        if !self.keep_title {
+            terminal_sync.restore_title(terminal.backend_mut())?;
+        }
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            SetCursorStyle::DefaultUserShape,
+            event::DisableFocusChange,
+            event::DisableBracketedPaste
+        )?;
+        terminal.show_cursor()?;
+        if let Some(frame) = last_frame {
+            // Still in raw mode, which the inline viewport needs to find the cursor
+            let mut scrollback = crate::terminal::scrollback_terminal(CrosstermBackend::new(io::stdout()))?;
+            crate::terminal::print_to_scrollback(&mut scrollback, &frame)?;
+        }
+        disable_raw_mode()?;
+
+        result
+    }
+
+    async fn run_app_loop(
+        &self,
+        app: AppContext,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        terminal_sync: &mut TerminalSync,
+        last_frame: &mut Option<ratatui::buffer::Buffer>,
+        root: Entity<dyn AnyComponent>,
+        mut re_render_rx: mpsc::UnboundedReceiver<()>,
+    ) -> anyhow::Result<()> {
+        // Initial render
+        let _ = app.re_render_tx.send(());
+
+        #[cfg(feature = "record")]
+        let mut recorder = match &self.record_path {
+            Some(path) => Some(crate::replay::EventRecorder::create(path, app.now())?),
+            None => None,
+        };
+        #[cfg(feature = "image")]
+        let mut kitty_images = crate::element::image::KittyImages::default();
+
+        // Dedicated event polling task to avoid blocking the main loop
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        tokio::task::spawn_blocking(move || {
+            loop {
+                // Check if the main loop is still interested in events
+                if event_tx.is_closed() {
+                    break;
+                }
+
+                // Input is read as soon as it arrives; the timeout only bounds how
+                // long it takes to notice the main loop has exited
+                match event::poll(Duration::from_millis(16)) {
+                    Ok(true) => {
+                        if let Ok(e) = event::read() {
+                            if event_tx.send(e).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(_) => break,
+                }
+            }
+        });
+
+        // Redraws independent of input, see `AppContext::set_animation_fps`
+        let mut animation_fps = app.animation_fps.subscribe();
+        let mut ticker = animation_ticker(*animation_fps.borrow_and_update());
+        let mut tick_ticker = self.tick_rate.map(|rate| {
+            let mut ticker = tokio::time::interval(rate);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker
+        });
+
+        loop {
+            tokio::select! {
+                // Prioritize event handling for lower latency
+                biased;
+
+                Some(crossterm_event) = event_rx.recv() => {
+                    for event in translate_event(crossterm_event, self.max_paste_len) {
+                        #[cfg(feature = "record")]
+                        if let Some(recorder) = recorder.as_mut() {
+                            recorder.record(app.frame_count(), app.now(), &event)?;
+                        }
+
+                        if self.pause_when_unfocused {
+                            match event {
+                                Event::FocusLost => app.set_paused(true),
+                                Event::FocusGained => app.set_paused(false),
+                                _ => {}
+                            }
+                        }
+
+                        let action = dispatch_event(&app, &root, event)?;
+
+                        app.refresh(); // Trigger refresh after any event handling
+
+                        // A vetoed quit keeps running; the refresh above draws e.g. the prompt the root opened
+                        if action.is_some_and(|action| app.perform_root_action(action)) && quit_confirmed(&app, &root)? {
+                            shutdown_root(&app, &root)?;
+                            app.finish_cleanup(self.shutdown_timeout).await;
+                            return Ok(());
+                        }
+                    }
+                }
+
+                Ok(()) = animation_fps.changed() => {
+                    ticker = animation_ticker(*animation_fps.borrow_and_update());
+                }
+
+                _ = async { ticker.as_mut().expect("checked by the precondition").tick().await }, if ticker.is_some() => {
+                    app.refresh();
+                }
+
+                _ = async { tick_ticker.as_mut().expect("checked by the precondition").tick().await }, if tick_ticker.is_some() => {
+                    if !app.is_paused() {
+                        tick_root(&app, &root)?;
+                        app.refresh();
+                    }
+                }
+
+                _ = re_render_rx.recv() => {
+                    // Drain all pending refresh requests to compact them into a single frame
+                    while re_render_rx.try_recv().is_ok() {}
+
+                    if app.is_paused() {
+                        // Resuming forces a redraw, so nothing is lost by dropping these
+                        continue;
+                    }
+
+                    let modes = app.terminal_modes.lock().map(|modes| modes.clone()).unwrap_or_default();
+                    terminal_sync.apply(&modes, terminal.backend_mut())?;
+                    if app.full_redraw.swap(false, std::sync::atomic::Ordering::Relaxed) {
+                        terminal.clear()?;
+                    }
+                    let start = Instant::now();
+                    let frame = terminal.draw(|frame| render_root(&app, &root, frame))?;
+                    if self.keep_output {
+                        *last_frame = Some(frame.buffer.clone());
+                    }
+                    #[cfg(feature = "image")]
+                    kitty_images.track(frame.buffer);
+                    let shape = crate::terminal::take_requested_cursor_shape().unwrap_or(modes.cursor_shape);
+                    terminal_sync.apply_cursor_shape(shape, terminal.backend_mut())?;
+                    #[cfg(feature = "image")]
+                    kitty_images.delete_stale(terminal.backend_mut())?;
+                    if !modes.cursor_visible {
+                        terminal.hide_cursor()?;
+                    }
+                    if let Ok(mut stats) = app.render_stats.lock() {
+                        stats.record(start.elapsed());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Call `f` every `period` of the app's clock forever, see `AppContext::spawn_interval`.
+async fn run_interval(app: AppContext, period: Duration, mut f: impl FnMut(&AppContext)) {
+    let clock = app.clock();
+    let mut deadline = clock.now();
+    loop {
+        let now = clock.now();
+        if deadline > now {
+            clock.sleep(deadline - now).await;
+        }
+        f(&app);
+        deadline += period;
+        // A late tick delays the following ones instead of causing a burst of catch-up ticks
+        let now = clock.now();
+        if deadline <= now {
+            deadline = now + period;
+        }
+    }
+}
+
+/// A ticker for `AppContext::set_animation_fps`, `None` when ticking is off.
+fn animation_ticker(fps: u32) -> Option<tokio::time::Interval> {
+    if fps == 0 {
+        return None;
+    }
+    let mut ticker = tokio::time::interval(Duration::from_secs(1) / fps);
+    // A slow frame delays the next tick instead of causing a burst of catch-up frames
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    Some(ticker)
+}
+
+/// Ask the root whether to go ahead with a quit, see `Component::on_before_quit`.
+pub(crate) fn quit_confirmed(app: &AppContext, root: &Entity<dyn AnyComponent>) -> anyhow::Result<bool> {
+    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+    root.update(|comp| comp.on_before_quit_any(&mut cx))
+        .map_err(|_| anyhow::anyhow!("Root mutex poisoned during on_before_quit"))
+}
+
+/// Run the root's `on_tick`, see `Application::with_tick_rate`.
+pub(crate) fn tick_root(app: &AppContext, root: &Entity<dyn AnyComponent>) -> anyhow::Result<()> {
+    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+    root.update(|comp| comp.on_tick_any(&mut cx))
+        .map_err(|_| anyhow::anyhow!("Root mutex poisoned during on_tick"))
+}
+
+/// Lifecycle: call on_mount (first time) and on_enter (entering view) on the root component.
+pub(crate) fn mount_root(app: &AppContext, root: &Entity<dyn AnyComponent>) -> anyhow::Result<()> {
+    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+    root.update(|comp| {
+        comp.on_mount_any(&mut cx);
+        comp.on_enter_any(&mut cx);
+    }).map_err(|_| anyhow::anyhow!("Root mutex poisoned during on_mount"))
+}
+
+/// Deliver an event to the root component and return the resulting action.
+/// Input events go to the topmost modal overlay instead, if one is open.
+pub(crate) fn dispatch_event(app: &AppContext, root: &Entity<dyn AnyComponent>, event: Event) -> anyhow::Result<Option<Action>> {
+    let mut cx = EventContext::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+    if let Event::Resize(width, height) = event {
+        app.force_redraw();
+        resize_root(app, root, width, height)?;
+    }
+    let is_input = matches!(event, Event::Key(_) | Event::Mouse(_) | Event::Paste(_));
+    let overlay = if is_input { app.overlay_stack().top_modal() } else { None };
+
+    let action = match overlay {
+        Some((id, overlay)) => {
+            let mut overlay_cx = EventContext::<dyn AnyComponent>::new(AppContext::clone(app), overlay.downgrade());
+            app.overlay_stack().set_handling(Some(id));
+            let action = overlay.update(|comp| deliver_event(app, comp, event, &mut overlay_cx));
+            app.overlay_stack().set_handling(None);
+            match action.map_err(|_| anyhow::anyhow!("Overlay mutex poisoned during event"))? {
+                // Actions from an overlay are handled by the root like its own
+                Some(action) => root.update(|comp| comp.handle_action_any(action, &mut cx))
+                    .map_err(|_| anyhow::anyhow!("Root mutex poisoned during event"))?,
+                None => None,
+            }
+        }
+        None => {
+            // Wheel events and clicks go to the element under the pointer, if it handles them
+            if let Event::Mouse(mouse) = &event {
+                if crate::element::scroll::dispatch(mouse) || crate::element::click::dispatch(mouse) {
+                    return Ok(None);
+                }
+            }
+            root.update(|comp| deliver_event(app, comp, event, &mut cx))
+                .map_err(|_| anyhow::anyhow!("Root mutex poisoned during event"))?
+        }
+    };
+    app.finish_closed_overlays();
+    Ok(action)
+}
+
+/// Hand an event to a component. Keys with a global binding are turned into
+/// their action instead, unless the component captures them.
+fn deliver_event(app: &AppContext, comp: &mut dyn AnyComponent, event: Event, cx: &mut EventContext<dyn AnyComponent>) -> Option<Action> {
+    if let Event::Key(key) = &event {
+        if let Some(action) = app.global_action(key) {
+            if !comp.captures_key_any(key) {
+                return comp.handle_action_any(action, cx);
+            }
+        }
+    }
+    comp.handle_event_any(event, cx)
+}
+
+/// Convert a terminal event into the events delivered to components.
+/// Key releases and repeats are dropped; long pastes are split into chunks.
+fn translate_event(event: CrosstermEvent, max_paste_len: usize) -> Vec<Event> {
+    match event {
+        CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => vec![Event::Key(key)],
+        CrosstermEvent::Mouse(mouse) => vec![Event::Mouse(mouse)],
+        CrosstermEvent::Resize(w, h) => vec![Event::Resize(w, h)],
+        CrosstermEvent::FocusGained => vec![Event::FocusGained],
+        CrosstermEvent::FocusLost => vec![Event::FocusLost],
+        CrosstermEvent::Paste(text) => paste_chunks(&text, max_paste_len).into_iter().map(Event::Paste).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Split `text` into pieces of at most `max_len` bytes, ending pieces after a
+/// line break when there is one and never inside a character.
+fn paste_chunks(mut text: &str, max_len: usize) -> Vec<String> {
+    // Every piece must fit at least one character
+    let max_len = max_len.max(4);
+    let mut chunks = Vec::new();
+    while text.len() > max_len {
+        let mut end = max_len;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if let Some(newline) = text[..end].rfind('\n') {
+            end = newline + 1;
+        }
+        chunks.push(text[..end].to_string());
+        text = &text[end..];
+    }
+    chunks.push(text.to_string());
+    chunks
+}
+
+/// Lifecycle: call on_resize on the root component.
+pub(crate) fn resize_root(app: &AppContext, root: &Entity<dyn AnyComponent>, width: u16, height: u16) -> anyhow::Result<()> {
+    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+    root.update(|comp| comp.on_resize_any(width, height, &mut cx))
+        .map_err(|_| anyhow::anyhow!("Root mutex poisoned during resize"))?;
+    let overlays = app.overlay_stack().components();
+    for overlay in overlays {
+        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), overlay.downgrade());
+        let _ = overlay.update(|comp| comp.on_resize_any(width, height, &mut cx));
+    }
+    Ok(())
+}
+
+/// Lifecycle: call on_shutdown on the open overlays, topmost first, then on the root component.
+pub(crate) fn shutdown_root(app: &AppContext, root: &Entity<dyn AnyComponent>) -> anyhow::Result<()> {
+    let overlays = app.overlay_stack().components();
+    for overlay in overlays.into_iter().rev() {
+        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), overlay.downgrade());
+        let _ = overlay.update(|comp| comp.on_shutdown_any(&mut cx));
+    }
+    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+    root.update(|comp| comp.on_shutdown_any(&mut cx))
+        .map_err(|_| anyhow::anyhow!("Root mutex poisoned during shutdown"))
+}
+
+/// Render the root component's element tree, then the overlays, into the whole frame.
+pub(crate) fn render_root(app: &AppContext, root: &Entity<dyn AnyComponent>, frame: &mut Frame) {
+    let start = Instant::now();
+    app.finish_closed_overlays();
+    app.frame_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    theme::set_current(app.theme.read(|t| t.clone()).unwrap_or_default());
+    crate::element::key::clear();
+    crate::element::scroll::clear();
+    crate::element::click::clear();
+    crate::terminal::take_requested_cursor_shape();
+    crate::element::cache::set_generation(app.cache_generation.load(std::sync::atomic::Ordering::Relaxed));
+    let width = frame.area().width;
+    app.screen_width.store(width, std::sync::atomic::Ordering::Relaxed);
+    app.screen_height.store(frame.area().height, std::sync::atomic::Ordering::Relaxed);
+    breakpoint::set_current(app.breakpoints.classify(width));
+    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), root.downgrade());
+    let mut element = root.update(|comp| comp.render_any(&mut cx))
+        .expect("Root mutex poisoned during render");
+    element.render(frame, frame.area());
+
+    let overlays = app.overlay_stack().components();
+    for overlay in overlays {
+        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(app), overlay.downgrade());
+        if let Ok(mut element) = overlay.update(|comp| comp.render_any(&mut cx)) {
+            element.render(frame, frame.area());
         }
     }
 
-    /// Get the entity ID of the component this context is bound to.
-    /// Returns None if the context was cast from another type.
-    pub fn entity_id(&self) -> Option<EntityId> {
-        self.handle.as_ref().map(|h| h.entity_id())
+    let debug_visible = match app.debug_overlay.lock() {
+        Ok(mut overlay) if overlay.is_visible() => {
+            overlay.record_frame(start);
+            true
+        }
+        _ => false,
+    };
+    if debug_visible {
+        DebugOverlay::draw(&app.debug_stats(), frame, frame.area());
     }
+    // Before downsampling, which would hide the theme colors it looks for
+    app.accessibility_mode().apply_buffer(frame.buffer_mut(), &theme::current());
+    app.color_depth.adapt_buffer(frame.buffer_mut());
+}
 
-    /// Get a weak handle to the component this context is bound to.
-    /// Returns None if the context was cast from another type.
-    /// Use this for async operations to safely check if the entity still exists.
-    pub fn weak_entity(&self) -> Option<WeakEntity<V>> {
-        self.handle.as_ref().map(WeakEntity::clone)
+/// Records the area a component's element is drawn into.
+struct AreaTracker {
+    inner: Box<dyn Element>,
+    key: ComponentKey,
+    areas: Arc<Mutex<HashMap<ComponentKey, Rect>>>,
+}
+
+impl Element for AreaTracker {
+    fn width(&self) -> Constraint {
+        self.inner.width()
     }
 
-    /// Get a strong handle to the component this context is bound to.
-    /// Returns None if the context was cast or if the entity was dropped.
-    pub fn entity(&self) -> Option<Entity<V>> {
-        self.handle.as_ref().and_then(|h| h.upgrade())
+    fn height(&self) -> Constraint {
+        self.inner.height()
     }
 
-    /// Explicitly trigger a re-render.
-    pub fn notify(&self) {
-        self.app.refresh();
+    fn measure_height(&self, width: u16) -> Option<u16> {
+        self.inner.measure_height(width)
+    }
+
+    fn measure_width(&self) -> Option<u16> {
+        self.inner.measure_width()
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Ok(mut areas) = self.areas.lock() {
+            areas.insert(self.key, area);
+        }
+        self.inner.render(frame, area);
     }
 }
 
-/// EventContext for event handling, currently identical to Context but renamed for clarity.
-pub type EventContext<V> = Context<V>;
+struct DummyView;
 
-/// Main application handle.
-pub struct Application;
+impl Component for DummyView {
+    fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+        crate::element::text("No component set").align_center()
+    }
+}
 
-impl Application {
-    /// Create a new application instance.
-    pub fn new() -> Self {
-        Self
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct GameOver {
+        score: u32,
     }
 
-    /// Run the application with the given closure that receives a context.
-    pub fn run<F>(self, setup: F) -> anyhow::Result<()>
-    where
-        F: FnOnce(&AppContext) -> anyhow::Result<()>,
-    {
-        let rt = Runtime::new().map_err(|e| anyhow::anyhow!("Failed to start tokio: {}", e))?;
-        let (re_render_tx, re_render_rx) = mpsc::unbounded_channel();
-        let root = Arc::new(Mutex::new(None));
-        let app_context = AppContext {
-            root: Arc::clone(&root),
-            re_render_tx,
-            frame_count: Arc::new(std::sync::atomic::AtomicU64::new(0)),
-            state: Arc::new(RwLock::new(HashMap::new())),
-        };
+    #[test]
+    fn custom_actions_reach_registered_handlers_in_batch_order() {
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        let best = Arc::new(AtomicU32::new(0));
+        let seen = Arc::clone(&best);
+        app.on_action(move |over: &GameOver, _cx| {
+            seen.fetch_max(over.score, Ordering::Relaxed);
+        });
 
-        let _guard = rt.enter();
-        setup(&app_context)?;
-        drop(_guard);
+        assert!(!app.perform_root_action(Action::custom(GameOver { score: 12 })));
+        assert_eq!(best.load(Ordering::Relaxed), 12);
 
-        let actual_root: Entity<dyn AnyComponent> = {
-            let guard = root.lock().map_err(|_| anyhow::anyhow!("Root mutex poisoned"))?;
-            guard.as_ref().map(Entity::clone).unwrap_or_else(|| {
-                Entity::from_arc(Arc::new(RwLock::new(DummyView)) as Arc<RwLock<dyn AnyComponent>>)
-            })
-        };
+        // Actions after a quit are not performed
+        let batch = Action::Batch(vec![Action::Quit, Action::custom(GameOver { score: 40 })]);
+        assert!(app.perform_root_action(batch));
+        assert_eq!(best.load(Ordering::Relaxed), 12);
+    }
 
-        let result = rt.block_on(async move {
-            self.run_loop(app_context, actual_root, re_render_rx).await
+    #[test]
+    fn the_root_can_veto_a_quit_until_it_is_confirmed() {
+        struct Editor {
+            prompted: bool,
+        }
+
+        impl Component for Editor {
+            fn on_before_quit(&mut self, _cx: &mut Context<Self>) -> bool {
+                // The first quit opens the prompt, the second goes through
+                std::mem::replace(&mut self.prompted, true)
+            }
+
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::text(if self.prompted { "Quit? q again" } else { "editing" })
+            }
+        }
+
+        let mut harness = crate::testing::TestHarness::new(Editor { prompted: false }, 16, 1).unwrap();
+        assert!(!harness.quit().unwrap());
+        crate::testing::assert_buffer_glyphs(harness.render().unwrap(), "Quit? q again");
+        assert!(harness.quit().unwrap());
+    }
+
+    #[tokio::test]
+    async fn intervals_tick_with_the_app_clock_until_dropped() {
+        let clock = crate::clock::TestClock::new();
+        let (app, _rx) = AppContext::create(Arc::new(clock.clone()));
+        let ticks = Arc::new(AtomicU32::new(0));
+        let counted = Arc::clone(&ticks);
+        let handle = app.spawn_interval(Duration::from_millis(100), move |_| {
+            counted.fetch_add(1, Ordering::Relaxed);
         });
+        let advance = |ms| {
+            clock.advance(Duration::from_millis(ms));
+            async {
+                for _ in 0..4 {
+                    tokio::task::yield_now().await;
+                }
+            }
+        };
+        advance(0).await;
+        assert_eq!(ticks.load(Ordering::Relaxed), 1);
+        advance(50).await;
+        assert_eq!(ticks.load(Ordering::Relaxed), 1);
+        advance(50).await;
+        advance(100).await;
+        assert_eq!(ticks.load(Ordering::Relaxed), 3);
 
-        // Ensure we don't hang forever on background tasks (like infinite loops in components)
-        rt.shutdown_timeout(Duration::from_millis(100));
+        drop(handle);
+        advance(500).await;
+        assert_eq!(ticks.load(Ordering::Relaxed), 3);
+    }
 
-        result
+    #[test]
+    fn suppressed_global_keys_fall_through_to_the_root() {
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        app.bind_global(KeyCode::Char('q'), Action::Quit);
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(app.global_action(&q), Some(Action::Quit));
+
+        app.suppress_global_keys(true);
+        assert!(app.global_keys_suppressed());
+        assert_eq!(app.global_action(&q), None);
+        app.suppress_global_keys(false);
+        assert_eq!(app.global_action(&q), Some(Action::Quit));
+
+        // Custom actions are only listed for help with a description
+        app.bind_global(KeyCode::Char('m'), Action::Navigate("menu".to_string()));
+        app.bind_global(KeyCode::F(12), Action::custom(ToggleDebugOverlay));
+        app.bind_global_described(KeyCode::F(2), KeyModifiers::NONE, Action::custom(ToggleDebugOverlay), "Debug");
+        assert_eq!(app.global_key_bindings().hint_line(), "Q Quit │ M menu │ F2 Debug");
     }
 
-    async fn run_loop(&self, app: AppContext, root: Entity<dyn AnyComponent>, re_render_rx: mpsc::UnboundedReceiver<()>) -> anyhow::Result<()> {
-        enable_raw_mode()?;
-        let mut stdout = stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture, event::EnableFocusChange)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+    #[test]
+    fn cleanup_tasks_spawned_on_shutdown_finish_before_exit() {
+        struct Saver(Arc<AtomicU32>);
+
+        impl Component for Saver {
+            fn on_shutdown(&mut self, cx: &mut Context<Self>) {
+                let saved = Arc::clone(&self.0);
+                cx.spawn_cleanup(|_| async move {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    saved.store(1, Ordering::Relaxed);
+                });
+            }
+
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::text("saving")
+            }
+        }
+
+        let saved = Arc::new(AtomicU32::new(0));
+        Application::render_headless(Saver(Arc::clone(&saved)), 10, 1).unwrap();
+        assert_eq!(saved.load(Ordering::Relaxed), 1);
+    }
 
-        // Lifecycle: Call on_mount (first time) and on_enter (entering view) on the root component
-        {
-            let weak = root.downgrade();
-            let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-            root.update(|comp| {
-                comp.on_mount_any(&mut cx);
-                comp.on_enter_any(&mut cx);
-            }).map_err(|_| anyhow::anyhow!("Root mutex poisoned during on_mount"))?;
+    #[test]
+    fn large_pastes_arrive_in_order_as_bounded_chunks() {
+        struct Editor {
+            pastes: Arc<Mutex<Vec<String>>>,
         }
 
-        let result = self.run_app_loop(app, &mut terminal, root, re_render_rx).await;
+        impl Component for Editor {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::text("")
+            }
 
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture,
-            event::DisableFocusChange
-        )?;
-        terminal.show_cursor()?;
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Event::Paste(text) = event {
+                    self.pastes.lock().unwrap().push(text);
+                }
+                None
+            }
+        }
 
-        result
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        let pastes = Arc::new(Mutex::new(Vec::new()));
+        let editor = Editor { pastes: Arc::clone(&pastes) };
+        let root = Entity::from_arc(Arc::new(RwLock::new(editor)) as Arc<RwLock<dyn AnyComponent>>);
+        let block = "fn main() {}\n".repeat(8) + "ünïcödé";
+        for event in translate_event(CrosstermEvent::Paste(block.clone()), 40) {
+            dispatch_event(&app, &root, event).unwrap();
+        }
+
+        let pastes = pastes.lock().unwrap().clone();
+        assert!(pastes.len() > 1);
+        assert!(pastes.iter().all(|p| p.len() <= 40));
+        assert!(pastes[..pastes.len() - 1].iter().all(|p| p.ends_with('\n')));
+        assert_eq!(pastes.concat(), block);
+
+        assert_eq!(paste_chunks("ééé", 5), ["éé", "é"]);
+        assert_eq!(translate_event(CrosstermEvent::Paste("short".into()), 40).len(), 1);
     }
 
-    async fn run_app_loop(
-        &self,
-        app: AppContext,
-        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-        root: Entity<dyn AnyComponent>,
-        mut re_render_rx: mpsc::UnboundedReceiver<()>,
-    ) -> anyhow::Result<()> {
-        // Initial render
-        let _ = app.re_render_tx.send(());
+    #[test]
+    fn on_resize_runs_before_the_first_frame_and_before_resize_events() {
+        struct Sized {
+            log: Arc<Mutex<Vec<String>>>,
+        }
 
-        // Dedicated event polling task to avoid blocking the main loop
-        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
-        tokio::task::spawn_blocking(move || {
-            loop {
-                // Check if the main loop is still interested in events
-                if event_tx.is_closed() {
-                    break;
+        impl Component for Sized {
+            fn on_resize(&mut self, width: u16, height: u16, _cx: &mut Context<Self>) {
+                self.log.lock().unwrap().push(format!("on_resize {width}x{height}"));
+            }
+
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                self.log.lock().unwrap().push("render".to_string());
+                crate::element::text("")
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Event::Resize(width, height) = event {
+                    self.log.lock().unwrap().push(format!("event {width}x{height}"));
                 }
+                None
+            }
+        }
 
-                // Poll at ~60fps (16.67ms) for smooth animations
-                match event::poll(Duration::from_millis(16)) {
-                    Ok(true) => {
-                        if let Ok(e) = event::read() {
-                            if event_tx.send(e).is_err() {
-                                break;
-                            }
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut harness = crate::testing::TestHarness::new(Sized { log: Arc::clone(&log) }, 30, 5).unwrap();
+        harness.render().unwrap();
+        assert_eq!(*log.lock().unwrap(), ["on_resize 30x5", "render"]);
+
+        log.lock().unwrap().clear();
+        harness.send(Event::Resize(80, 24)).unwrap();
+        assert_eq!(*log.lock().unwrap(), ["on_resize 80x24", "event 80x24"]);
+    }
+
+    #[test]
+    fn overlays_take_input_until_they_dismiss_themselves() {
+        type Log = Arc<Mutex<Vec<String>>>;
+
+        struct Dialog {
+            name: &'static str,
+            log: Log,
+        }
+
+        impl Component for Dialog {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::text(self.name)
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Event::Key(key) = event {
+                    self.log.lock().unwrap().push(format!("{} {:?}", self.name, key.code));
+                    match key.code {
+                        KeyCode::Esc => {
+                            cx.dismiss_overlay();
                         }
+                        KeyCode::Char('p') => {
+                            cx.push_overlay(Dialog { name: "picker", log: Arc::clone(&self.log) });
+                        }
+                        _ => {}
                     }
-                    Ok(false) => {}
-                    Err(_) => break,
+                }
+                None
+            }
+        }
+
+        struct Page {
+            log: Log,
+        }
+
+        impl Component for Page {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::text("page content")
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                if let Event::Key(key) = event {
+                    self.log.lock().unwrap().push(format!("page {:?}", key.code));
+                    if key.code == KeyCode::Char('o') {
+                        cx.push_overlay(Dialog { name: "dialog", log: Arc::clone(&self.log) });
+                        cx.push_passive_overlay(Dialog { name: "toast", log: Arc::clone(&self.log) });
+                    }
+                }
+                None
+            }
+        }
+
+        let log: Log = Arc::default();
+        let mut harness = crate::testing::TestHarness::new(Page { log: Arc::clone(&log) }, 12, 1).unwrap();
+        harness.key(KeyCode::Char('o')).unwrap();
+        harness.key(KeyCode::Char('p')).unwrap();
+        assert_eq!(harness.app().overlay_count(), 3);
+        assert_eq!(crate::testing::buffer_lines(harness.render().unwrap()), ["pickerontent"]);
+
+        for code in [KeyCode::Esc, KeyCode::Esc, KeyCode::Char('x')] {
+            harness.key(code).unwrap();
+        }
+        assert_eq!(harness.app().overlay_count(), 1, "only the toast is left");
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["page Char('o')", "dialog Char('p')", "picker Esc", "dialog Esc", "page Char('x')"]
+        );
+    }
+
+    #[test]
+    fn define_app_roots_draw_element_and_framed_pages() {
+        use crate::component::{FrameComponent, Framed};
+        use crate::define_app;
+
+        #[derive(Default)]
+        struct Menu;
+
+        impl Component for Menu {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::div()
+                    .child(crate::element::text("menu").h(1))
+                    .child(crate::element::text("press enter"))
+            }
+
+            fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+                match event {
+                    Event::Key(key) if key.code == KeyCode::Enter => Some(Action::Navigate("legacy".into())),
+                    _ => None,
                 }
             }
+        }
+
+        #[derive(Default)]
+        struct Legacy;
+
+        impl FrameComponent for Legacy {
+            fn render(&mut self, frame: &mut Frame, area: Rect, _cx: &mut Context<Self>) {
+                frame.render_widget(ratatui::widgets::Paragraph::new("legacy"), area);
+            }
+
+            fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+                // Dispatched through two casts: the root's and the adapter's
+                if let Event::Key(key) = event {
+                    cx.set(key.code);
+                }
+                Some(Action::Back)
+            }
+        }
+
+        define_app! {
+            #[Root(default=Menu)]
+            pub struct Root {
+                Menu => menu: Menu,
+                Legacy => legacy: Framed<Legacy>,
+            }
+        }
+
+        let mut root = Root::new();
+        root.navigate(RootRoute::Legacy);
+        assert_eq!(root.breadcrumb().len(), 2);
+        assert!(root.go_back());
+        assert_eq!((*root.current_route(), *root.deps()), (RootRoute::Menu, ()));
+
+        let mut harness = crate::testing::TestHarness::new(root, 12, 2).unwrap();
+        let draw = |harness: &mut crate::testing::TestHarness| crate::testing::buffer_lines(harness.render().unwrap());
+
+        assert_eq!(draw(&mut harness), ["menu", "press enter"]);
+        harness.key(KeyCode::Enter).unwrap();
+        assert_eq!(draw(&mut harness), ["legacy", ""]);
+        harness.key(KeyCode::Char('x')).unwrap();
+        assert_eq!(harness.app().get::<KeyCode>(), Some(KeyCode::Char('x')));
+        assert_eq!(draw(&mut harness), ["menu", "press enter"]);
+    }
+
+    #[tokio::test]
+    async fn blocking_work_delivers_its_result_to_the_component() {
+        #[derive(Default)]
+        struct Loader {
+            total: Option<u64>,
+        }
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        assert_eq!(app.spawn_blocking(|| (1..=10_u64).sum::<u64>()).await.unwrap(), 55);
+        assert!(app.spawn_blocking(|| panic!("corrupt file")).await.is_err());
+
+        let loader = Entity::new(Loader::default());
+        let cx = Context::new(AppContext::clone(&app), loader.downgrade());
+        let handle = cx.spawn_blocking(|| (1..=100_u64).sum::<u64>(), |this: &mut Loader, total| this.total = Some(total));
+        while !handle.is_finished() {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        assert_eq!(loader.read(|l| l.total).unwrap(), Some(5050));
+    }
+
+    #[tokio::test]
+    async fn cleanup_tasks_are_aborted_after_the_timeout() {
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        app.spawn_cleanup(|_| std::future::pending());
+        assert!(!app.finish_cleanup(Duration::from_millis(10)).await);
+        app.spawn_cleanup(|_| async {});
+        assert!(app.finish_cleanup(Duration::from_millis(10)).await);
+    }
+
+    #[tokio::test]
+    async fn observe_with_runs_once_per_flush_after_changes() {
+        #[derive(Default)]
+        struct Page {
+            derived: usize,
+            calls: usize,
+        }
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        let handle = Entity::new(Page::default());
+        let mut cx = Context::new(AppContext::clone(&app), handle.downgrade());
+        let query = Entity::new(String::new());
+        let _task = cx.observe_with(&query, |page: &mut Page, query: &String| {
+            page.derived = query.len();
+            page.calls += 1;
         });
 
-        loop {
-            tokio::select! {
-                // Prioritize event handling for lower latency
-                biased;
+        let mut page = Page::default();
+        let _ = query.update(|q| q.push_str("abc"));
+        let _ = query.update(|q| q.push_str("de"));
+        tokio::time::sleep(Duration::from_millis(10)).await;
 
-                Some(crossterm_event) = event_rx.recv() => {
-                    let internal_event = match crossterm_event {
-                        CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => Some(Event::Key(key)),
-                        CrosstermEvent::Mouse(mouse) => Some(Event::Mouse(mouse)),
-                        CrosstermEvent::Resize(w, h) => Some(Event::Resize(w, h)),
-                        CrosstermEvent::FocusGained => Some(Event::FocusGained),
-                        CrosstermEvent::FocusLost => Some(Event::FocusLost),
-                        CrosstermEvent::Paste(s) => Some(Event::Paste(s)),
-                        _ => None,
-                    };
-
-                    if let Some(event) = internal_event {
-                        let weak = root.downgrade();
-                        let mut cx = EventContext::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-
-                        let action = root.update(|comp| {
-                            comp.handle_event_any(event, &mut cx)
-                        }).map_err(|_| anyhow::anyhow!("Root mutex poisoned during event"))?;
+        cx.flush_observers(&mut page);
+        assert_eq!((page.derived, page.calls), (5, 1));
+        cx.flush_observers(&mut page);
+        assert_eq!(page.calls, 1);
+    }
 
-                        app.refresh(); // Trigger refresh after any event handling
+    #[tokio::test]
+    async fn observe_with_runs_on_the_instance_that_registered_it() {
+        struct Row {
+            seen: u32,
+        }
+        impl Component for Row {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::empty()
+            }
+        }
 
-                        if let Some(action) = action {
-                            match action {
-                                Action::Quit => {
-                                    let weak = root.downgrade();
-                                    let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-                                    root.update(|comp| comp.on_shutdown_any(&mut cx))
-                                        .map_err(|_| anyhow::anyhow!("Root mutex poisoned during shutdown"))?;
-                                    return Ok(());
-                                }
-                                _ => {}
-                            }
-                        }
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        let rows: Vec<Entity<dyn AnyComponent>> = (0..2)
+            .map(|_| Entity::from_arc(Arc::new(RwLock::new(Row { seen: 0 })) as Arc<RwLock<dyn AnyComponent>>))
+            .collect();
+        let context = |row: &Entity<dyn AnyComponent>| Context::<dyn AnyComponent>::new(AppContext::clone(&app), row.downgrade());
+        let watched = Entity::new(0u32);
+        let _task = context(&rows[0]).downcast::<Row>().observe_with(&watched, |row: &mut Row, n: &u32| row.seen = *n);
+        let _ = watched.update(|n| *n = 7);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // The other row renders first, and must not pick up the first row's callback
+        for row in rows.iter().rev() {
+            row.update(|component| drop(component.render_any(&mut context(row)))).unwrap();
+        }
+        let seen = |row: &Entity<dyn AnyComponent>| row.downcast::<Row>().unwrap().read(|row| row.seen).unwrap();
+        assert_eq!((seen(&rows[0]), seen(&rows[1])), (7, 0));
+    }
+
+    #[test]
+    fn messages_from_tasks_arrive_in_order_before_the_next_render() {
+        #[derive(Default)]
+        struct Page {
+            received: Vec<String>,
+        }
+        impl Component for Page {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                crate::element::empty()
+            }
+
+            fn on_message(&mut self, message: Message, cx: &mut Context<Self>) {
+                match message.downcast::<String>() {
+                    Ok(text) if text == "echo" => {
+                        cx.message_sender().send("echoed".to_string());
                     }
+                    Ok(text) => self.received.push(text),
+                    Err(other) => self.received.push(format!("{:?}", other.downcast::<u32>().ok())),
                 }
+            }
+        }
 
-                _ = re_render_rx.recv() => {
-                    // Drain all pending refresh requests to compact them into a single frame
-                    while re_render_rx.try_recv().is_ok() {}
+        let (app, mut rx) = AppContext::create(Arc::new(RealClock));
+        let handle = Entity::new(Page::default());
+        let mut cx = Context::new(AppContext::clone(&app), handle.downgrade());
+        let text = cx.message_sender::<String>();
+        let numbers = cx.message_sender::<u32>();
+        text.send("first".to_string());
+        numbers.send(2);
+        text.clone().send("echo".to_string());
+        assert_eq!((text.pending(), numbers.pending()), (3, 3));
+        assert!(rx.try_recv().is_ok());
 
-                    let weak = root.downgrade();
-                    terminal.draw(|frame| {
-                        app.frame_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                        let mut cx = Context::<dyn AnyComponent>::new(AppContext::clone(&app), weak);
-                        let mut element = root.update(|comp| comp.render_any(&mut cx))
-                            .expect("Root mutex poisoned during render");
-                        element.render(frame, frame.area());
-                    })?;
-                }
+        // Another instance of the component has a mailbox of its own
+        let other = Entity::new(Page::default());
+        let mut other_cx = Context::new(AppContext::clone(&app), other.downgrade());
+        let mut other_page = Page::default();
+        other_cx.flush_messages(&mut other_page);
+        assert!(other_page.received.is_empty());
+        assert_eq!(text.pending(), 3);
+
+        let mut page = Page::default();
+        cx.flush_messages(&mut page);
+        assert_eq!(page.received, ["first", "Some(2)"]);
+        // Sent while delivering, so left for the next frame
+        assert_eq!(text.pending(), 1);
+        cx.flush_messages(&mut page);
+        assert_eq!(page.received.last().map(String::as_str), Some("echoed"));
+
+        drop(rx);
+        assert!(!text.send("too late".to_string()));
+    }
+
+    #[test]
+    fn area_reports_where_the_component_was_last_drawn() {
+        struct Page;
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        let (page, other) = (Entity::new(Page), Entity::new(Page));
+        let cx: Context<Page> = Context::new(AppContext::clone(&app), page.downgrade());
+        let other_cx: Context<Page> = Context::new(AppContext::clone(&app), other.downgrade());
+        assert_eq!(cx.area(), Rect::default());
+
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(30, 4)).unwrap();
+        let mut element = cx.track_area(Box::new(crate::element::text("hi")));
+        let mut other_element = other_cx.track_area(Box::new(crate::element::text("there")));
+        terminal.draw(|frame| {
+            element.render(frame, Rect::new(2, 1, 20, 3));
+            other_element.render(frame, Rect::new(22, 0, 8, 1));
+        }).unwrap();
+        // Each instance of the component sees its own area
+        assert_eq!(cx.area(), Rect::new(2, 1, 20, 3));
+        assert_eq!(other_cx.area(), Rect::new(22, 0, 8, 1));
+    }
+
+    #[test]
+    fn screen_area_is_the_frame_being_drawn() {
+        struct Measured(Arc<Mutex<Rect>>);
+        impl Component for Measured {
+            fn render(&mut self, cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
+                *self.0.lock().unwrap() = cx.screen_area();
+                crate::element::text("sized")
             }
         }
+
+        let seen = Arc::new(Mutex::new(Rect::default()));
+        let mut harness = crate::testing::TestHarness::new(Measured(Arc::clone(&seen)), 30, 4).unwrap();
+        assert_eq!(harness.app().screen_area(), Rect::default());
+        harness.render().unwrap();
+        assert_eq!(*seen.lock().unwrap(), Rect::new(0, 0, 30, 4));
+        assert_eq!(harness.app().screen_area(), Rect::new(0, 0, 30, 4));
     }
-}
 
-struct DummyView;
+    #[tokio::test]
+    async fn page_tasks_pause_while_page_is_inactive() {
+        struct Page;
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        let page = Entity::new(Page);
+        let cx = Context::new(AppContext::clone(&app), page.downgrade());
 
-impl Component for DummyView {
-    fn render(&mut self, _cx: &mut Context<Self>) -> impl crate::element::IntoElement + 'static {
-        crate::element::text("No component set").align_center()
+        let ticks = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&ticks);
+        let _task = cx.spawn_page_task(move |_| async move {
+            loop {
+                counter.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(ticks.load(Ordering::Relaxed) > 0);
+
+        app.set_page_active::<Page>(false);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let paused = ticks.load(Ordering::Relaxed);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(ticks.load(Ordering::Relaxed), paused);
+
+        app.set_page_active::<Page>(true);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(ticks.load(Ordering::Relaxed) > paused);
+    }
+
+    #[tokio::test]
+    async fn animation_fps_sets_the_ticker_period() {
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        assert_eq!(app.animation_fps(), 0);
+        assert!(animation_ticker(app.animation_fps()).is_none());
+
+        app.set_animation_fps(120);
+        assert_eq!(app.animation_fps(), 120);
+        let ticker = animation_ticker(app.animation_fps()).unwrap();
+        assert_eq!(ticker.period(), Duration::from_nanos(8_333_333));
+    }
+
+    #[tokio::test]
+    async fn pausing_the_app_suspends_page_tasks_and_resuming_redraws() {
+        struct Page;
+        let (app, _rx) = AppContext::create(Arc::new(RealClock));
+        let page = Entity::new(Page);
+        let cx = Context::new(AppContext::clone(&app), page.downgrade());
+
+        let ticks = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&ticks);
+        let _task = cx.spawn_page_task(move |_| async move {
+            loop {
+                counter.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        app.set_paused(true);
+        assert!(app.is_paused());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let paused = ticks.load(Ordering::Relaxed);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(ticks.load(Ordering::Relaxed), paused);
+        assert!(tokio::time::timeout(Duration::from_millis(5), app.resumed()).await.is_err());
+
+        app.set_paused(false);
+        tokio::time::timeout(Duration::from_millis(5), app.resumed()).await.unwrap();
+        assert!(app.full_redraw.load(Ordering::Relaxed));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(ticks.load(Ordering::Relaxed) > paused);
     }
 }
@@ -0,0 +1,94 @@
+//! Responsive layout breakpoints.
+//!
+//! The terminal width is classified into a `Breakpoint` every frame, so
+//! components can pick a layout for the space they have:
+//!
+//! ```ignore
+//! fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+//!     div()
+//!         .responsive(|div, bp| match bp {
+//!             Breakpoint::Small => div.flex_col(),
+//!             _ => div.flex_row(),
+//!         })
+//!         .child(sidebar)
+//!         .child(content)
+//! }
+//! ```
+//!
+//! The thresholds are set with `Application::with_breakpoints`.
+
+use std::cell::Cell;
+
+/// Width class of the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Breakpoint {
+    #[default]
+    Small,
+    Medium,
+    Large,
+}
+
+/// Terminal widths (in columns) at which the larger breakpoints start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Breakpoints {
+    /// Narrowest width considered `Medium`.
+    pub medium: u16,
+    /// Narrowest width considered `Large`.
+    pub large: u16,
+}
+
+impl Breakpoints {
+    pub fn new(medium: u16, large: u16) -> Self {
+        Self { medium, large }
+    }
+
+    /// Classify a width.
+    pub fn classify(&self, width: u16) -> Breakpoint {
+        if width >= self.large {
+            Breakpoint::Large
+        } else if width >= self.medium {
+            Breakpoint::Medium
+        } else {
+            Breakpoint::Small
+        }
+    }
+}
+
+impl Default for Breakpoints {
+    /// `Medium` from 80 columns, `Large` from 120.
+    fn default() -> Self {
+        Self::new(80, 120)
+    }
+}
+
+thread_local! {
+    /// Breakpoint of the frame being drawn.
+    static CURRENT: Cell<Breakpoint> = const { Cell::new(Breakpoint::Small) };
+}
+
+/// The breakpoint of the frame currently being rendered.
+/// Outside of rendering (or without an application) this is `Small`.
+pub fn current() -> Breakpoint {
+    CURRENT.with(Cell::get)
+}
+
+/// Install the breakpoint elements see while rendering the next frame.
+pub(crate) fn set_current(breakpoint: Breakpoint) {
+    CURRENT.with(|current| current.set(breakpoint));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths_are_classified_by_threshold() {
+        let bps = Breakpoints::default();
+        assert_eq!(bps.classify(0), Breakpoint::Small);
+        assert_eq!(bps.classify(79), Breakpoint::Small);
+        assert_eq!(bps.classify(80), Breakpoint::Medium);
+        assert_eq!(bps.classify(120), Breakpoint::Large);
+        assert_eq!(Breakpoints::new(40, 60).classify(100), Breakpoint::Large);
+        assert!(Breakpoint::Large > Breakpoint::Medium);
+    }
+}
@@ -0,0 +1,139 @@
+//! Time source abstraction.
+//!
+//! `AppContext::now()` and `AppContext::sleep()` go through a `Clock` so that
+//! time-driven components can be tested deterministically. Applications use
+//! `RealClock` by default; tests install a `TestClock` and advance it manually.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// A boxed future returned by `Clock::sleep`.
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A source of time for the application.
+pub trait Clock: Send + Sync + 'static {
+    /// The current instant.
+    fn now(&self) -> Instant;
+
+    /// Wait until `duration` has passed according to this clock.
+    fn sleep(&self, duration: Duration) -> SleepFuture;
+}
+
+/// The system clock, backed by `Instant::now` and `tokio::time::sleep`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// A manually driven clock for tests and replays.
+///
+/// Time only moves when `advance` is called. Pending `sleep` futures resolve
+/// once the clock has been advanced past their deadline.
+///
+/// # Example
+/// ```ignore
+/// let clock = TestClock::new();
+/// let app = Application::new().with_clock(clock.clone());
+/// // ...
+/// clock.advance(Duration::from_millis(500));
+/// ```
+#[derive(Clone)]
+pub struct TestClock {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+    tx: watch::Sender<Duration>,
+}
+
+impl TestClock {
+    /// Create a clock frozen at the current instant.
+    pub fn new() -> Self {
+        let (tx, _) = watch::channel(Duration::ZERO);
+        Self {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+            tx,
+        }
+    }
+
+    /// Move the clock forward and wake any sleepers whose deadline has passed.
+    pub fn advance(&self, duration: Duration) {
+        let elapsed = {
+            let mut guard = self.elapsed.lock().unwrap_or_else(|e| e.into_inner());
+            *guard += duration;
+            *guard
+        };
+        self.tx.send_replace(elapsed);
+    }
+
+    /// Total time advanced since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for TestClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        let deadline = self.elapsed() + duration;
+        let mut rx = self.tx.subscribe();
+        Box::pin(async move {
+            while *rx.borrow_and_update() < deadline {
+                if rx.changed().await.is_err() {
+                    return;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_only_moves_when_advanced() {
+        let clock = TestClock::new();
+        let t0 = clock.now();
+        assert_eq!(clock.now(), t0);
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(clock.now() - t0, Duration::from_millis(250));
+        assert_eq!(clock.elapsed(), Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn test_clock_sleep_resolves_after_advance() {
+        let clock = TestClock::new();
+        let sleeper = tokio::spawn(clock.sleep(Duration::from_millis(100)));
+
+        clock.advance(Duration::from_millis(50));
+        tokio::task::yield_now().await;
+        assert!(!sleeper.is_finished());
+
+        clock.advance(Duration::from_millis(50));
+        tokio::time::timeout(Duration::from_secs(1), sleeper)
+            .await
+            .expect("sleep should resolve once the deadline is reached")
+            .unwrap();
+    }
+}
@@ -0,0 +1,308 @@
+//! Terminal color capability detection and fallback.
+//!
+//! Rendering with `Color::Rgb` on a terminal without truecolor support produces
+//! wrong (sometimes unreadable) colors. The application probes the terminal at
+//! startup and, when needed, maps every drawn color to the nearest one the
+//! terminal can show, so components can use RGB freely.
+//!
+//! On top of that, an `AccessibilityMode` can trade colors for contrast or
+//! drop them altogether, keeping the emphasis the theme's colors carried.
+
+use crate::theme::Theme;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+
+/// How many colors the terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// The 16 basic ANSI colors.
+    Ansi16,
+    /// The xterm 256-color palette.
+    Ansi256,
+    /// 24-bit RGB.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Guess the color depth from `COLORTERM` and `TERM`.
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        let term = std::env::var("TERM").unwrap_or_default().to_lowercase();
+        Self::from_env(&colorterm, &term)
+    }
+
+    fn from_env(colorterm: &str, term: &str) -> Self {
+        if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else if term.is_empty() && cfg!(windows) {
+            // Windows Terminal and recent conhost don't set TERM but do support RGB
+            ColorDepth::TrueColor
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Map a color to the nearest one available at this depth.
+    pub fn adapt(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(i)) if i >= 16 => {
+                let (r, g, b) = indexed_to_rgb(i);
+                nearest_16(r, g, b)
+            }
+            (ColorDepth::Ansi16, Color::Indexed(i)) => ANSI_16[i as usize].0,
+            _ => color,
+        }
+    }
+
+    /// Map the foreground, background and underline colors of a style.
+    pub fn adapt_style(self, style: Style) -> Style {
+        Style {
+            fg: style.fg.map(|c| self.adapt(c)),
+            bg: style.bg.map(|c| self.adapt(c)),
+            underline_color: style.underline_color.map(|c| self.adapt(c)),
+            ..style
+        }
+    }
+
+    /// Map every cell of a rendered buffer. Does nothing for truecolor terminals.
+    pub fn adapt_buffer(self, buffer: &mut Buffer) {
+        if self == ColorDepth::TrueColor {
+            return;
+        }
+        for cell in buffer.content.iter_mut() {
+            cell.fg = self.adapt(cell.fg);
+            cell.bg = self.adapt(cell.bg);
+            cell.underline_color = self.adapt(cell.underline_color);
+        }
+    }
+}
+
+/// How drawn colors are presented, for limited terminals and users who need
+/// more contrast or no color at all. See `AppContext::set_accessibility_mode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AccessibilityMode {
+    /// Colors as drawn.
+    #[default]
+    Normal,
+    /// Only the 16 ANSI colors on the terminal's background, muted text
+    /// brightened, and text in the theme's accent and status colors in bold.
+    /// Colored backgrounds are shown in reverse video.
+    HighContrast,
+    /// No colors. Text in the theme's accent colors is bold, errors are also
+    /// underlined, muted text is dim and colored backgrounds such as list
+    /// selections are shown in reverse video.
+    NoColor,
+}
+
+impl AccessibilityMode {
+    /// `NoColor` when the `NO_COLOR` environment variable is set to a
+    /// non-empty value (see no-color.org), `Normal` otherwise.
+    pub fn detect() -> Self {
+        Self::from_no_color(std::env::var_os("NO_COLOR"))
+    }
+
+    fn from_no_color(no_color: Option<std::ffi::OsString>) -> Self {
+        match no_color {
+            Some(value) if !value.is_empty() => AccessibilityMode::NoColor,
+            _ => AccessibilityMode::Normal,
+        }
+    }
+
+    /// The mode after this one, cycling Normal, HighContrast, NoColor.
+    pub fn next(self) -> Self {
+        match self {
+            AccessibilityMode::Normal => AccessibilityMode::HighContrast,
+            AccessibilityMode::HighContrast => AccessibilityMode::NoColor,
+            AccessibilityMode::NoColor => AccessibilityMode::Normal,
+        }
+    }
+
+    /// Rewrite every cell of a rendered buffer for this mode. `theme` tells
+    /// which colors stand for emphasis. Does nothing in `Normal` mode.
+    pub fn apply_buffer(self, buffer: &mut Buffer, theme: &Theme) {
+        if self == AccessibilityMode::Normal {
+            return;
+        }
+        for cell in buffer.content.iter_mut() {
+            cell.modifier |= self.emphasis(cell.fg, theme);
+            // Text on a colored background may clash with it; reverse video
+            // keeps it distinguishable with the terminal's own colors
+            if cell.bg != Color::Reset {
+                cell.modifier |= Modifier::REVERSED;
+                cell.bg = Color::Reset;
+            }
+            if self == AccessibilityMode::NoColor {
+                cell.fg = Color::Reset;
+                cell.underline_color = Color::Reset;
+            } else {
+                cell.fg = match ColorDepth::Ansi16.adapt(cell.fg) {
+                    Color::DarkGray => Color::Gray,
+                    Color::Black => Color::Reset,
+                    color => color,
+                };
+                cell.underline_color = ColorDepth::Ansi16.adapt(cell.underline_color);
+            }
+        }
+    }
+
+    /// Modifiers standing in for the meaning of a foreground color.
+    fn emphasis(self, fg: Color, theme: &Theme) -> Modifier {
+        if fg == Color::Reset {
+            Modifier::empty()
+        } else if fg == theme.error {
+            match self {
+                AccessibilityMode::NoColor => Modifier::BOLD | Modifier::UNDERLINED,
+                _ => Modifier::BOLD,
+            }
+        } else if [theme.primary, theme.accent, theme.warning].contains(&fg) {
+            Modifier::BOLD
+        } else if fg == theme.muted && self == AccessibilityMode::NoColor {
+            Modifier::DIM
+        } else {
+            Modifier::empty()
+        }
+    }
+}
+
+/// The 16 ANSI colors with the RGB values xterm uses for them.
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 color cube (indices 16..=231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let dr = r1 as i32 - r2 as i32;
+    let dg = g1 as i32 - g2 as i32;
+    let db = b1 as i32 - b2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+fn nearest_cube_level(v: u8) -> usize {
+    match v {
+        0..48 => 0,
+        48..115 => 1,
+        _ => ((v - 35) / 40) as usize,
+    }
+}
+
+/// Nearest xterm 256-color index, choosing between the color cube and the gray ramp.
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let (ri, gi, bi) = (nearest_cube_level(r), nearest_cube_level(g), nearest_cube_level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // Gray ramp: 232..=255 covers 8, 18, ..., 238
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray_value = 8 + 10 * gray_step;
+    let gray = (gray_value, gray_value, gray_value);
+
+    if distance((r, g, b), gray) < distance((r, g, b), cube) {
+        232 + gray_step
+    } else {
+        cube_index as u8
+    }
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+/// RGB value of a 256-color palette index (16..=255).
+fn indexed_to_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => ANSI_16[i as usize].1,
+        16..=231 => {
+            let i = (i - 16) as usize;
+            (CUBE_LEVELS[i / 36], CUBE_LEVELS[(i / 6) % 6], CUBE_LEVELS[i % 6])
+        }
+        _ => {
+            let v = 8 + 10 * (i - 232);
+            (v, v, v)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_depth_from_environment() {
+        assert_eq!(ColorDepth::from_env("truecolor", "xterm-256color"), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::from_env("", "xterm-direct"), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::from_env("", "screen-256color"), ColorDepth::Ansi256);
+        assert_eq!(ColorDepth::from_env("", "linux"), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn downsamples_rgb_to_nearest_palette_color() {
+        assert_eq!(ColorDepth::Ansi256.adapt(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(ColorDepth::Ansi256.adapt(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(ColorDepth::Ansi16.adapt(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(ColorDepth::Ansi16.adapt(Color::Indexed(21)), Color::Blue);
+        // Named colors are left alone
+        assert_eq!(ColorDepth::Ansi16.adapt(Color::Cyan), Color::Cyan);
+        assert_eq!(ColorDepth::TrueColor.adapt(Color::Rgb(1, 2, 3)), Color::Rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn accessibility_modes_replace_colors_with_emphasis() {
+        assert_eq!(AccessibilityMode::from_no_color(Some("1".into())), AccessibilityMode::NoColor);
+        assert_eq!(AccessibilityMode::from_no_color(Some("".into())), AccessibilityMode::Normal);
+        assert_eq!(AccessibilityMode::from_no_color(None), AccessibilityMode::Normal);
+
+        let theme = Theme::dark();
+        let mut drawn = Buffer::empty(ratatui::layout::Rect::new(0, 0, 4, 1));
+        drawn.set_string(0, 0, "t", Style::default().fg(theme.primary));
+        drawn.set_string(1, 0, "e", Style::default().fg(theme.error));
+        drawn.set_string(2, 0, "m", Style::default().fg(theme.muted));
+        drawn.set_string(3, 0, "s", Style::default().fg(Color::Rgb(20, 20, 20)).bg(Color::Rgb(40, 40, 40)));
+
+        let mut buffer = drawn.clone();
+        AccessibilityMode::NoColor.apply_buffer(&mut buffer, &theme);
+        let cells: Vec<_> = buffer.content.iter().map(|c| (c.fg, c.bg, c.modifier)).collect();
+        assert_eq!(cells, [
+            (Color::Reset, Color::Reset, Modifier::BOLD),
+            (Color::Reset, Color::Reset, Modifier::BOLD | Modifier::UNDERLINED),
+            (Color::Reset, Color::Reset, Modifier::DIM),
+            (Color::Reset, Color::Reset, Modifier::REVERSED),
+        ]);
+
+        let mut buffer = drawn.clone();
+        AccessibilityMode::HighContrast.apply_buffer(&mut buffer, &theme);
+        assert_eq!((buffer[(0, 0)].fg, buffer[(0, 0)].modifier), (Color::LightBlue, Modifier::BOLD));
+        assert_eq!((buffer[(3, 0)].fg, buffer[(3, 0)].bg, buffer[(3, 0)].modifier), (Color::Reset, Color::Reset, Modifier::REVERSED));
+        assert_eq!(buffer[(2, 0)].fg, Color::Gray);
+
+        let mut buffer = drawn.clone();
+        AccessibilityMode::Normal.apply_buffer(&mut buffer, &theme);
+        assert_eq!(buffer, drawn);
+    }
+}
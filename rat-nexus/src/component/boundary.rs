@@ -0,0 +1,208 @@
+//! Recoverable errors in components.
+//!
+//! A `TryComponent` is a component whose render and event handling can fail.
+//! Wrapping it in an `ErrorBoundary` turns it into a regular `Component`: the
+//! first error replaces the child's output with a fallback panel showing the
+//! error, and pressing the retry key gives the child another go.
+//!
+//! ```ignore
+//! struct Report { raw: String }
+//!
+//! impl TryComponent for Report {
+//!     fn render(&mut self, _cx: &mut Context<Self>) -> anyhow::Result<impl IntoElement + 'static> {
+//!         let summary: Summary = serde_json::from_str(&self.raw)?;
+//!         Ok(text(summary.title))
+//!     }
+//! }
+//!
+//! let page = ErrorBoundary::new(Report { raw });
+//! ```
+
+use crate::application::{Context, EventContext};
+use crate::component::traits::{Action, Component, Event};
+use crate::element::{div, text, Div, Element, IntoElement};
+use crate::keymap::{key_label, KeyBindings};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::style::Style;
+
+/// A component whose lifecycle methods, render and event handling return `Result`.
+///
+/// Use it through `ErrorBoundary`, which shows a fallback instead of the child
+/// once one of these methods has failed.
+pub trait TryComponent: Send + Sync + 'static {
+    /// Called once when the boundary is mounted.
+    fn on_mount(&mut self, cx: &mut Context<Self>) -> anyhow::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// Called when the user asks to retry after an error, before the next render.
+    /// Use this to reset or reload whatever state caused the failure.
+    fn on_retry(&mut self, cx: &mut Context<Self>) -> anyhow::Result<()> {
+        let _ = cx;
+        Ok(())
+    }
+
+    /// Render the component into an Element tree.
+    fn render(&mut self, cx: &mut Context<Self>) -> anyhow::Result<impl IntoElement + 'static>;
+
+    /// Handle an event, returning an optional action.
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> anyhow::Result<Option<Action>> {
+        let _ = event;
+        let _ = cx;
+        Ok(None)
+    }
+
+    /// Return true to keep a key away from global bindings and the help overlay.
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        let _ = key;
+        false
+    }
+
+    /// Describe the keys this component responds to.
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+    }
+}
+
+/// Shows a fallback with the error and a retry key when its child fails.
+///
+/// While the fallback is shown, events other than the retry key are not passed
+/// to the child; global key bindings keep working.
+pub struct ErrorBoundary<C> {
+    child: C,
+    error: Option<anyhow::Error>,
+    retry_key: KeyCode,
+}
+
+impl<C: TryComponent> ErrorBoundary<C> {
+    pub fn new(child: C) -> Self {
+        Self {
+            child,
+            error: None,
+            retry_key: KeyCode::Char('r'),
+        }
+    }
+
+    /// The key that clears the error and renders the child again. Defaults to `r`.
+    pub fn retry_key(mut self, key: KeyCode) -> Self {
+        self.retry_key = key;
+        self
+    }
+
+    /// The error being shown, if the child has failed.
+    pub fn error(&self) -> Option<&anyhow::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn child(&self) -> &C {
+        &self.child
+    }
+
+    pub fn child_mut(&mut self) -> &mut C {
+        &mut self.child
+    }
+
+    /// Clear the error and call the child's `on_retry`.
+    pub fn retry(&mut self, cx: &mut Context<Self>) {
+        self.error = None;
+        let mut child_cx = cx.cast::<C>();
+        if let Err(e) = self.child.on_retry(&mut child_cx) {
+            self.error = Some(e);
+        }
+        cx.notify();
+    }
+
+    fn fallback(&self) -> Div {
+        let theme = crate::theme::current();
+        let message = self.error.as_ref().map(|e| format!("{e:#}")).unwrap_or_default();
+        let hint = format!("Press {} to retry", key_label(self.retry_key, KeyModifiers::NONE));
+        div()
+            .border_all()
+            .border_style(Style::default().fg(theme.error))
+            .title(" Error ")
+            .child(text(message).fg(theme.error).h(1))
+            .child(text(hint).fg(theme.muted).h(1))
+    }
+}
+
+impl<C: TryComponent> Component for ErrorBoundary<C> {
+    fn on_mount(&mut self, cx: &mut Context<Self>) {
+        let mut child_cx = cx.cast::<C>();
+        if let Err(e) = self.child.on_mount(&mut child_cx) {
+            self.error = Some(e);
+        }
+    }
+
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        if self.error.is_none() {
+            let mut child_cx = cx.cast::<C>();
+            match TryComponent::render(&mut self.child, &mut child_cx) {
+                Ok(element) => return Box::new(element.into_element()) as Box<dyn Element>,
+                Err(e) => self.error = Some(e),
+            }
+        }
+        Box::new(self.fallback())
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        if self.error.is_some() {
+            if let Event::Key(key) = &event {
+                if key.kind == KeyEventKind::Press && key.code == self.retry_key {
+                    self.retry(cx);
+                }
+            }
+            return None;
+        }
+        let mut child_cx = cx.cast::<C>();
+        match TryComponent::handle_event(&mut self.child, event, &mut child_cx) {
+            Ok(action) => action,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                None
+            }
+        }
+    }
+
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        self.error.is_none() && self.child.captures_key(key)
+    }
+
+    fn key_bindings(&self) -> KeyBindings {
+        if self.error.is_some() {
+            KeyBindings::new().bind(self.retry_key, "Retry")
+        } else {
+            self.child.key_bindings()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::buffer_lines;
+    use crate::application::Application;
+
+    struct Parser {
+        input: &'static str,
+    }
+
+    impl TryComponent for Parser {
+        fn render(&mut self, _cx: &mut Context<Self>) -> anyhow::Result<impl IntoElement + 'static> {
+            let n: u32 = self.input.parse()?;
+            Ok(text(format!("n = {n}")))
+        }
+    }
+
+    #[test]
+    fn failing_render_shows_the_error_and_retry_hint() {
+        let ok = Application::render_headless(ErrorBoundary::new(Parser { input: "7" }), 30, 4).unwrap();
+        assert_eq!(buffer_lines(&ok)[0], "n = 7");
+
+        let failed = Application::render_headless(ErrorBoundary::new(Parser { input: "x" }), 30, 4).unwrap();
+        let lines = buffer_lines(&failed);
+        assert!(lines[1].contains("invalid digit"), "{lines:?}");
+        assert!(lines[2].contains("Press R to retry"), "{lines:?}");
+    }
+}
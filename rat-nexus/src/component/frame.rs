@@ -0,0 +1,218 @@
+//! Components that draw straight into the frame.
+//!
+//! `Component::render` returns an element tree; that is the one render
+//! signature the application, the router and `define_app!` work with. Code
+//! written against the older frame-based form, which drew widgets itself,
+//! implements `FrameComponent` instead and is wrapped in `Framed`, which
+//! turns it into a regular `Component` whose output is a `canvas`. Framed
+//! pages compose with element-based ones, in layouts and in `define_app!`.
+//!
+//! ```ignore
+//! impl FrameComponent for Legacy {
+//!     fn render(&mut self, frame: &mut Frame, area: Rect, cx: &mut Context<Self>) {
+//!         frame.render_widget(Paragraph::new(self.text.as_str()), area);
+//!     }
+//! }
+//!
+//! define_app! {
+//!     #[Root(default=Main)]
+//!     pub struct Root {
+//!         Main => main: Framed<Legacy>,
+//!         Logs => logs: LogsPage,
+//!     }
+//! }
+//! ```
+//!
+//! # Migrating to `Component`
+//!
+//! - Change `impl FrameComponent` to `impl Component` and drop the `Framed` wrapper.
+//! - Return the drawing code as `canvas(move |frame, area| ...)` from `render`,
+//!   cloning what it needs, or replace it with `div`/`text` elements.
+//! - The other methods keep their signatures.
+
+use crate::application::{Context, EventContext};
+use crate::component::traits::{Action, Component, Event};
+use crate::element::{canvas, IntoElement};
+use crate::keymap::KeyBindings;
+use crossterm::event::KeyEvent;
+use ratatui::prelude::{Frame, Rect};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A component that draws widgets into the frame instead of returning elements.
+///
+/// Use it through `Framed`, which makes it a `Component`.
+pub trait FrameComponent: Send + Sync + 'static {
+    /// Called once when the component is first mounted.
+    fn on_mount(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
+    /// Called each time the component becomes the active view.
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
+    /// Called when the component is removed from the active view.
+    fn on_exit(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
+    /// Called with the terminal size before the first frame and on each resize,
+    /// see `Component::on_resize`.
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        let _ = (width, height, cx);
+    }
+
+    /// Called at the rate set with `Application::with_tick_rate`, see `Component::on_tick`.
+    fn on_tick(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
+    /// Draw the component into `area`, the part of the frame it was laid out in.
+    /// Draw into `area` rather than `frame.area()`, so the component can be nested.
+    fn render(&mut self, frame: &mut Frame, area: Rect, cx: &mut Context<Self>);
+
+    /// Handle an event, returning an optional action.
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        let _ = event;
+        let _ = cx;
+        None
+    }
+
+    /// Return true to keep a key away from global bindings and the help overlay.
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        let _ = key;
+        false
+    }
+
+    /// Describe the keys this component responds to.
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+    }
+}
+
+/// Adapts a `FrameComponent` to `Component` by drawing it in a full-size `canvas`.
+///
+/// The child is drawn when the element tree is laid out, after `render` has
+/// returned, so it is kept behind a lock; use `lock` to reach it.
+pub struct Framed<C> {
+    inner: Arc<Mutex<C>>,
+}
+
+impl<C: FrameComponent> Framed<C> {
+    pub fn new(child: C) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(child)),
+        }
+    }
+
+    /// The wrapped component.
+    pub fn lock(&self) -> MutexGuard<'_, C> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<C: FrameComponent + Default> Default for Framed<C> {
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C: FrameComponent> Component for Framed<C> {
+    fn on_mount(&mut self, cx: &mut Context<Self>) {
+        self.lock().on_mount(&mut cx.cast::<C>());
+    }
+
+    fn on_enter(&mut self, cx: &mut Context<Self>) {
+        self.lock().on_enter(&mut cx.cast::<C>());
+    }
+
+    fn on_exit(&mut self, cx: &mut Context<Self>) {
+        self.lock().on_exit(&mut cx.cast::<C>());
+    }
+
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        self.lock().on_resize(width, height, &mut cx.cast::<C>());
+    }
+
+    fn on_tick(&mut self, cx: &mut Context<Self>) {
+        self.lock().on_tick(&mut cx.cast::<C>());
+    }
+
+    fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        let inner = Arc::clone(&self.inner);
+        let mut child_cx = cx.cast::<C>();
+        canvas(move |frame, area| {
+            let mut child = inner.lock().unwrap_or_else(|e| e.into_inner());
+            child.render(frame, area, &mut child_cx);
+        })
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        self.lock().handle_event(event, &mut cx.cast::<C>())
+    }
+
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        self.lock().captures_key(key)
+    }
+
+    fn key_bindings(&self) -> KeyBindings {
+        self.lock().key_bindings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::Application;
+    use crate::element::{div, text};
+    use crate::testing::buffer_lines;
+    use ratatui::widgets::Paragraph;
+
+    struct Legacy;
+
+    impl FrameComponent for Legacy {
+        fn render(&mut self, frame: &mut Frame, area: Rect, _cx: &mut Context<Self>) {
+            frame.render_widget(Paragraph::new(format!("legacy {}x{}", area.width, area.height)), area);
+        }
+    }
+
+    struct Page {
+        legacy: Framed<Legacy>,
+    }
+
+    impl Component for Page {
+        fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+            let legacy = self.legacy.render(&mut cx.cast());
+            div().child(text("header").h(1)).child(legacy)
+        }
+    }
+
+    #[test]
+    fn framed_components_draw_into_their_layout_area() {
+        let page = Page { legacy: Framed::new(Legacy) };
+        let buffer = Application::render_headless(page, 20, 3).unwrap();
+        let lines = buffer_lines(&buffer);
+        assert_eq!(lines[0], "header");
+        assert_eq!(lines[1], "legacy 20x2");
+    }
+
+    #[derive(Default)]
+    struct Resized(Option<(u16, u16)>);
+
+    impl FrameComponent for Resized {
+        fn on_resize(&mut self, width: u16, height: u16, _cx: &mut Context<Self>) {
+            self.0 = Some((width, height));
+        }
+
+        fn render(&mut self, frame: &mut Frame, area: Rect, _cx: &mut Context<Self>) {
+            frame.render_widget(Paragraph::new(format!("{:?}", self.0)), area);
+        }
+    }
+
+    #[test]
+    fn framed_components_get_resize_hooks() {
+        let buffer = Application::render_headless(Framed::<Resized>::default(), 20, 1).unwrap();
+        assert_eq!(buffer_lines(&buffer), ["Some((20, 1))"]);
+    }
+}
@@ -3,5 +3,9 @@
 //! Defines the `Component` trait and related utilities.
 
 pub mod traits;
+pub mod boundary;
+pub mod frame;
 
-pub use traits::Component;
+pub use traits::{Component, TypedEntity};
+pub use boundary::{ErrorBoundary, TryComponent};
+pub use frame::{FrameComponent, Framed};
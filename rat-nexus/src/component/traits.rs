@@ -1,15 +1,28 @@
 use crate::application::{Context, EventContext};
 use crate::element::{Element, IntoElement};
+use crate::keymap::KeyBindings;
+use crate::state::Entity;
+use crossterm::event::KeyEvent;
 use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Event type for component interactions.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "record", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     Key(crossterm::event::KeyEvent),
     Mouse(crossterm::event::MouseEvent),
     Resize(u16, u16),
     FocusGained,
     FocusLost,
+    /// Text pasted into the terminal, delivered as one event per paste.
+    ///
+    /// Bracketed paste is enabled while the application runs, so a pasted block
+    /// arrives whole instead of as a burst of key events (on terminals that
+    /// support it). Pastes longer than `Application::with_max_paste_len`
+    /// (64 KiB by default) arrive as several consecutive `Paste` events, split
+    /// after a line break where possible and never inside a character.
     Paste(String),
     Custom(String),
 }
@@ -17,21 +30,230 @@ pub enum Event {
 /// Action that a component can return after handling an event.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Action<R = String> {
+    /// Go to a route by name; `define_app!` matches it case-insensitively.
     Navigate(R),
+    /// Go to a route of the application's route enum, e.g.
+    /// `Action::navigate_to(RootRoute::Settings)`, so that a misspelt route
+    /// doesn't compile.
+    NavigateTo(TypedRoute),
+    /// Go to a route without adding the current one to the history, e.g. from
+    /// a login screen to home, so that `Back` doesn't return to it.
+    Replace(R),
+    /// Go to a route and clear the history, e.g. after logging out.
+    ///
+    /// For this and `Replace`, an unknown route fails a debug assertion in `define_app!`
+    /// and is ignored in release builds.
+    Reset(R),
     Back,
     Quit,
     Noop,
+    /// Several actions, performed in order. Processing stops at `Quit`.
+    Batch(Vec<Action<R>>),
+    /// An application-defined action, e.g. `Action::custom(GameOver { score })`.
+    ///
+    /// `define_app!` passes custom actions up unchanged; the application handles them
+    /// with `AppContext::on_action`, or a hand-written root can `downcast_ref` them itself.
+    Custom(CustomAction),
+}
+
+impl From<&str> for Action {
+    /// `Action::Navigate` to the route with this name, as before typed routes.
+    fn from(route: &str) -> Self {
+        Action::Navigate(route.to_string())
+    }
+}
+
+/// Payload of `Action::Custom`: any value, shared so the action stays cheap to clone.
+#[derive(Clone)]
+pub struct CustomAction(Arc<dyn Any + Send + Sync>);
+
+impl CustomAction {
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// The payload, if it is a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+
+    /// The payload as `&dyn Any`, for type-based dispatch.
+    pub fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        &*self.0
+    }
+}
+
+impl std::fmt::Debug for CustomAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CustomAction(..)")
+    }
+}
+
+impl PartialEq for CustomAction {
+    /// Custom actions compare by identity: clones are equal, separately created ones are not.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Payload of `Action::NavigateTo`: a value of a route enum such as `RootRoute`.
+#[derive(Clone)]
+pub struct TypedRoute {
+    route: Arc<dyn Any + Send + Sync>,
+    eq: fn(&dyn Any, &dyn Any) -> bool,
+    debug: fn(&dyn Any, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+}
+
+impl TypedRoute {
+    pub fn new<R: crate::AppRoute>(route: R) -> Self {
+        fn eq<R: Any + PartialEq>(a: &dyn Any, b: &dyn Any) -> bool {
+            matches!((a.downcast_ref::<R>(), b.downcast_ref::<R>()), (Some(a), Some(b)) if a == b)
+        }
+        fn debug<R: Any + std::fmt::Debug>(route: &dyn Any, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match route.downcast_ref::<R>() {
+                Some(route) => route.fmt(f),
+                None => Ok(()),
+            }
+        }
+        Self { route: Arc::new(route), eq: eq::<R>, debug: debug::<R> }
+    }
+
+    /// The route, if it is an `R`.
+    pub fn downcast_ref<R: Any>(&self) -> Option<&R> {
+        self.route.downcast_ref::<R>()
+    }
+}
+
+impl std::fmt::Debug for TypedRoute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self.debug)(&*self.route, f)
+    }
+}
+
+impl PartialEq for TypedRoute {
+    /// Routes compare by value; routes of different types are never equal.
+    fn eq(&self, other: &Self) -> bool {
+        (self.eq)(&*self.route, &*other.route)
+    }
+}
+
+/// A value sent to a component through a `MessageSender`, see `Component::on_message`.
+pub struct Message(Box<dyn Any + Send>);
+
+impl Message {
+    pub(crate) fn new<T: Any + Send>(value: T) -> Self {
+        Self(Box::new(value))
+    }
+
+    /// The value, if it is a `T`; otherwise the message is handed back.
+    pub fn downcast<T: Any>(self) -> Result<T, Message> {
+        self.0.downcast::<T>().map(|value| *value).map_err(Message)
+    }
+
+    /// The value, if it is a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref::<T>()
+    }
+
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+}
+
+impl std::fmt::Debug for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Message(..)")
+    }
+}
+
+impl<R> Action<R> {
+    /// Go to `route` of the application's route enum, see `Action::NavigateTo`.
+    ///
+    /// Only `RootRoute`s generated by `define_app!` are accepted:
+    ///
+    /// ```compile_fail
+    /// let _ = rat_nexus::Action::<String>::navigate_to("Settings");
+    /// ```
+    pub fn navigate_to<T: crate::AppRoute>(route: T) -> Self {
+        Action::NavigateTo(TypedRoute::new(route))
+    }
+
+    /// Wrap an application-defined value in `Action::Custom`.
+    pub fn custom<T: Any + Send + Sync>(value: T) -> Self {
+        Action::Custom(CustomAction::new(value))
+    }
+
+    /// The custom payload, if this is an `Action::Custom` holding a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match self {
+            Action::Custom(custom) => custom.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+
+    /// How deeply batches may be nested before the inner ones are dropped.
+    pub const MAX_BATCH_DEPTH: usize = 8;
+
+    /// Combine actions into one, skipping `Noop`s. A single action is returned as is.
+    pub fn batch(actions: impl IntoIterator<Item = Action<R>>) -> Self {
+        let mut actions: Vec<_> = actions
+            .into_iter()
+            .filter(|a| !matches!(a, Action::Noop))
+            .collect();
+        match actions.len() {
+            0 => Action::Noop,
+            1 => actions.remove(0),
+            _ => Action::Batch(actions),
+        }
+    }
+
+    /// Expand nested batches into the plain actions they contain, in order.
+    /// Batches nested deeper than `MAX_BATCH_DEPTH` are dropped, so a
+    /// self-referential construction can't make the dispatcher loop forever.
+    pub fn flatten(self) -> Vec<Action<R>> {
+        fn walk<R>(action: Action<R>, depth: usize, out: &mut Vec<Action<R>>) {
+            match action {
+                Action::Batch(actions) if depth < Action::<R>::MAX_BATCH_DEPTH => {
+                    for action in actions {
+                        walk(action, depth + 1, out);
+                    }
+                }
+                // Dropped quietly; printing would corrupt the terminal
+                Action::Batch(_) => {}
+                action => out.push(action),
+            }
+        }
+        let mut out = Vec::new();
+        walk(self, 0, &mut out);
+        out
+    }
+
+    /// True for `Quit`, or a batch that contains it.
+    pub fn is_quit(&self) -> bool {
+        match self {
+            Action::Quit => true,
+            Action::Batch(actions) => actions.iter().any(Action::is_quit),
+            _ => false,
+        }
+    }
 }
 
 /// The core Component trait for implementers.
 pub trait Component: Sized + Send + Sync + 'static {
-    /// Called once when the component is first mounted (created and added to the tree).
+    /// Called once when the component is first mounted (created and added to the tree),
+    /// before its first render and its first `on_enter`. `define_app!` pages are all
+    /// mounted at startup, whichever route is shown first, and never again.
     /// Use this for one-time initialization like spawning background tasks.
     fn on_mount(&mut self, cx: &mut Context<Self>) {
         let _ = cx;
     }
 
     /// Called each time the component becomes the active view (navigation entry).
+    /// Every `on_enter` is followed by exactly one `on_exit` before the next one.
     /// Use this for per-visit initialization like resetting transient state.
     fn on_enter(&mut self, cx: &mut Context<Self>) {
         let _ = cx;
@@ -49,6 +271,29 @@ pub trait Component: Sized + Send + Sync + 'static {
         let _ = cx;
     }
 
+    /// Called on the root component when it is asked to quit with `Action::Quit`,
+    /// before anything is shut down. Return false to keep running, e.g. to ask
+    /// "Quit without saving?" first and quit on the next `Quit` once confirmed.
+    fn on_before_quit(&mut self, cx: &mut Context<Self>) -> bool {
+        let _ = cx;
+        true
+    }
+
+    /// Called with the terminal size before the first frame and whenever the
+    /// terminal is resized, before the next render. Use this to recompute
+    /// size-dependent state. The `Event::Resize` is still delivered to `handle_event`.
+    fn on_resize(&mut self, width: u16, height: u16, cx: &mut Context<Self>) {
+        let _ = (width, height, cx);
+    }
+
+    /// Called at the rate set with `Application::with_tick_rate`, while the
+    /// component is the active view and drawing isn't paused. Each tick is
+    /// followed by a frame. Use it to advance games and animations without a
+    /// spawned loop; without a tick rate it is never called.
+    fn on_tick(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
     /// Render the component into an Element tree.
     fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static;
 
@@ -58,6 +303,34 @@ pub trait Component: Sized + Send + Sync + 'static {
         let _ = cx;
         None
     }
+
+    /// Perform an action that didn't come from this component's `handle_event`,
+    /// such as one bound with `AppContext::bind_global`.
+    /// Returns the action to pass up; the default passes it through unchanged.
+    fn handle_action(&mut self, action: Action, cx: &mut EventContext<Self>) -> Option<Action> {
+        let _ = cx;
+        Some(action)
+    }
+
+    /// Receive a message sent with a `MessageSender` from `Context::message_sender`,
+    /// e.g. a result from a background task. Messages arrive between frames,
+    /// right before the component renders, in the order they were sent.
+    fn on_message(&mut self, message: Message, cx: &mut Context<Self>) {
+        let _ = (message, cx);
+    }
+
+    /// Return true to keep a key away from global bindings and the help overlay,
+    /// e.g. while a text field is being edited.
+    fn captures_key(&self, key: &KeyEvent) -> bool {
+        let _ = key;
+        false
+    }
+
+    /// Describe the keys this component responds to.
+    /// Used by the help overlay and for footer hints.
+    fn key_bindings(&self) -> KeyBindings {
+        KeyBindings::new()
+    }
 }
 
 /// A dyn-compatible version of the Component trait.
@@ -66,39 +339,214 @@ pub trait AnyComponent: Any + Send + Sync + 'static {
     fn on_enter_any(&mut self, cx: &mut Context<dyn AnyComponent>);
     fn on_exit_any(&mut self, cx: &mut Context<dyn AnyComponent>);
     fn on_shutdown_any(&mut self, cx: &mut Context<dyn AnyComponent>);
+    fn on_before_quit_any(&mut self, cx: &mut Context<dyn AnyComponent>) -> bool;
+    fn on_resize_any(&mut self, width: u16, height: u16, cx: &mut Context<dyn AnyComponent>);
+    fn on_tick_any(&mut self, cx: &mut Context<dyn AnyComponent>);
+    /// Build the component's element tree. The application draws the root's tree
+    /// into the whole frame; parents lay out the trees of their children.
     fn render_any(&mut self, cx: &mut Context<dyn AnyComponent>) -> Box<dyn Element>;
     fn handle_event_any(&mut self, event: Event, cx: &mut EventContext<dyn AnyComponent>) -> Option<Action>;
+    fn handle_action_any(&mut self, action: Action, cx: &mut EventContext<dyn AnyComponent>) -> Option<Action>;
+    fn captures_key_any(&self, key: &KeyEvent) -> bool;
+    fn key_bindings_any(&self) -> KeyBindings;
+    /// The concrete component, for `downcast_ref`.
+    fn as_any(&self) -> &dyn Any;
+    /// The concrete component, for `downcast_mut`.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
 impl<T: Component> AnyComponent for T {
     fn on_mount_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.on_mount(&mut cx);
     }
 
     fn on_enter_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.on_enter(&mut cx);
     }
 
     fn on_exit_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.on_exit(&mut cx);
     }
 
     fn on_shutdown_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.on_shutdown(&mut cx);
     }
 
+    fn on_before_quit_any(&mut self, cx: &mut Context<dyn AnyComponent>) -> bool {
+        let mut cx = cx.downcast::<Self>();
+        self.on_before_quit(&mut cx)
+    }
+
+    fn on_resize_any(&mut self, width: u16, height: u16, cx: &mut Context<dyn AnyComponent>) {
+        let mut cx = cx.downcast::<Self>();
+        self.on_resize(width, height, &mut cx);
+    }
+
+    fn on_tick_any(&mut self, cx: &mut Context<dyn AnyComponent>) {
+        let mut cx = cx.downcast::<Self>();
+        self.on_tick(&mut cx);
+    }
+
     fn render_any(&mut self, cx: &mut Context<dyn AnyComponent>) -> Box<dyn Element> {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
+        cx.flush_messages(self);
+        cx.flush_observers(self);
         let element = self.render(&mut cx);
-        Box::new(element.into_element())
+        cx.track_area(Box::new(element.into_element()))
     }
 
     fn handle_event_any(&mut self, event: Event, cx: &mut EventContext<dyn AnyComponent>) -> Option<Action> {
-        let mut cx = cx.cast::<Self>();
+        let mut cx = cx.downcast::<Self>();
         self.handle_event(event, &mut cx)
     }
+
+    fn handle_action_any(&mut self, action: Action, cx: &mut EventContext<dyn AnyComponent>) -> Option<Action> {
+        let mut cx = cx.downcast::<Self>();
+        self.handle_action(action, &mut cx)
+    }
+
+    fn captures_key_any(&self, key: &KeyEvent) -> bool {
+        self.captures_key(key)
+    }
+
+    fn key_bindings_any(&self) -> KeyBindings {
+        self.key_bindings()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl Entity<dyn AnyComponent> {
+    /// Whether the component is a `T`.
+    pub fn is<T: Component>(&self) -> bool {
+        self.read(|component| component.as_any().is::<T>()).unwrap_or(false)
+    }
+
+    /// A handle to the component as its concrete type, or `None` if it isn't a `T`,
+    /// e.g. for a task to hand data to the root without going through actions:
+    ///
+    /// ```ignore
+    /// if let Some(root) = app.root().and_then(|root| root.downcast::<Root>()) {
+    ///     root.update(|root| root.monitor.set_hosts(hosts))?;
+    /// }
+    /// ```
+    ///
+    /// The handle shares the entity: its `read` and `update` take the same lock,
+    /// so they fail with `Error::Reentrant` when called from inside the component
+    /// itself (its `render` or `handle_event`), and updates notify its subscribers.
+    pub fn downcast<T: Component>(&self) -> Option<TypedEntity<T>> {
+        self.is::<T>().then(|| TypedEntity {
+            entity: Entity::clone(self),
+            _type: PhantomData,
+        })
+    }
+}
+
+/// A type-erased component entity known to hold a `T`, see `Entity::downcast`.
+pub struct TypedEntity<T> {
+    entity: Entity<dyn AnyComponent>,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: Component> TypedEntity<T> {
+    /// Read the component, like `Entity::read`.
+    pub fn read<R>(&self, f: impl FnOnce(&T) -> R) -> crate::Result<R> {
+        self.entity.read(|component| f(Self::cast(component.as_any())))
+    }
+
+    /// Update the component and notify subscribers, like `Entity::update`.
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> crate::Result<R> {
+        self.entity.update(|component| f(Self::cast_mut(component.as_any_mut())))
+    }
+
+    /// The type-erased entity.
+    pub fn entity(&self) -> &Entity<dyn AnyComponent> {
+        &self.entity
+    }
+
+    // The component behind an entity never changes type, so the check in `downcast` holds
+    fn cast(component: &dyn Any) -> &T {
+        component.downcast_ref::<T>().expect("checked by Entity::downcast")
+    }
+
+    fn cast_mut(component: &mut dyn Any) -> &mut T {
+        component.downcast_mut::<T>().expect("checked by Entity::downcast")
+    }
+}
+
+impl<T> Clone for TypedEntity<T> {
+    fn clone(&self) -> Self {
+        Self {
+            entity: Entity::clone(&self.entity),
+            _type: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_flatten_in_order_and_drop_excessive_nesting() {
+        let action: Action = Action::Batch(vec![
+            Action::Navigate("a".to_string()),
+            Action::Batch(vec![Action::Back, Action::Quit]),
+        ]);
+        assert!(action.is_quit());
+        assert_eq!(
+            action.flatten(),
+            [Action::Navigate("a".to_string()), Action::Back, Action::Quit]
+        );
+
+        let mut nested: Action = Action::Back;
+        for _ in 0..=Action::<String>::MAX_BATCH_DEPTH {
+            nested = Action::Batch(vec![nested]);
+        }
+        assert!(nested.flatten().is_empty());
+
+        assert_eq!(Action::<String>::batch([Action::Noop, Action::Back]), Action::Back);
+    }
+
+    #[test]
+    fn type_erased_components_downcast_to_their_concrete_type() {
+        struct Page {
+            data: Vec<u32>,
+        }
+
+        impl Component for Page {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+                crate::element::text(format!("{} items", self.data.len()))
+            }
+        }
+
+        struct Other;
+
+        impl Component for Other {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+                crate::element::text("other")
+            }
+        }
+
+        let page = Entity::from_arc(Arc::new(std::sync::RwLock::new(Page { data: Vec::new() })) as Arc<std::sync::RwLock<dyn AnyComponent>>);
+        let changes = page.subscribe();
+        assert!(page.is::<Page>() && !page.is::<Other>());
+        assert!(page.downcast::<Other>().is_none());
+
+        let typed = page.downcast::<Page>().unwrap();
+        typed.update(|page| page.data.push(7)).unwrap();
+        assert!(changes.has_changed().unwrap());
+        assert_eq!(typed.clone().read(|page| page.data.clone()).unwrap(), [7]);
+        assert_eq!(typed.entity().entity_id(), page.entity_id());
+    }
 }
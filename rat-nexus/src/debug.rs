@@ -0,0 +1,171 @@
+//! Developer diagnostics: render timings and an overlay with frame and runtime statistics.
+//!
+//! Draw times are always measured and available from `AppContext::render_stats`.
+//! Enable the overlay during setup and toggle it with the chosen key:
+//!
+//! ```ignore
+//! app.run(|cx| {
+//!     cx.enable_debug_overlay(KeyCode::F(12));
+//!     cx.set_root(root)
+//! })
+//! ```
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, BorderType, Clear, Paragraph};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Payload of the global key action that shows or hides the overlay.
+pub(crate) struct ToggleDebugOverlay;
+
+/// Statistics shown by the debug overlay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugStats {
+    /// Frames per second over the last second.
+    pub fps: f64,
+    pub frame_count: u64,
+    /// Tasks alive on the tokio runtime (0 outside of a runtime).
+    pub tasks: usize,
+    /// Entities created since the application started.
+    pub entities: u64,
+    /// Entities whose lock was poisoned by a panic during an update, see
+    /// `Entity::read_or_recover`.
+    pub poisoned: u64,
+    /// Time spent building and drawing the previous frame.
+    pub render_time: Duration,
+}
+
+/// How long drawing frames took, measured around each terminal draw.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Number of frames measured.
+    pub frames: u64,
+    pub last: Duration,
+    pub max: Duration,
+    total: Duration,
+}
+
+impl RenderStats {
+    /// Mean draw time over all measured frames.
+    pub fn average(&self) -> Duration {
+        if self.frames == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(self.frames as f64)
+        }
+    }
+
+    pub(crate) fn record(&mut self, duration: Duration) {
+        self.frames += 1;
+        self.last = duration;
+        self.max = self.max.max(duration);
+        self.total += duration;
+    }
+}
+
+/// Frame timing collected while the overlay is visible.
+#[derive(Default)]
+pub(crate) struct DebugOverlay {
+    visible: bool,
+    /// Start instants of the frames drawn in the last second.
+    frames: VecDeque<Instant>,
+}
+
+impl DebugOverlay {
+    /// How far back frames count towards the FPS.
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    pub(crate) fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub(crate) fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.frames.clear();
+    }
+
+    /// Note a frame that started drawing at `start`.
+    pub(crate) fn record_frame(&mut self, start: Instant) {
+        while self.frames.front().is_some_and(|&t| start.duration_since(t) >= Self::WINDOW) {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(start);
+    }
+
+    /// Frames per second, from the deltas between the recorded frames.
+    pub(crate) fn fps(&self) -> f64 {
+        match (self.frames.front(), self.frames.back()) {
+            (Some(first), Some(last)) if self.frames.len() > 1 => {
+                let span = last.duration_since(*first).as_secs_f64();
+                if span > 0.0 {
+                    (self.frames.len() - 1) as f64 / span
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Draw the statistics in the top-right corner of `area`.
+    pub(crate) fn draw(stats: &DebugStats, frame: &mut Frame, area: Rect) {
+        let theme = crate::theme::current();
+        let lines = vec![
+            format!("FPS      {:>8.1}", stats.fps),
+            format!("Frames   {:>8}", stats.frame_count),
+            format!("Render   {:>6.2}ms", stats.render_time.as_secs_f64() * 1000.0),
+            format!("Tasks    {:>8}", stats.tasks),
+            format!("Entities {:>8}", stats.entities),
+            format!("Poisoned {:>8}", stats.poisoned),
+        ];
+        let width = (lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16 + 4).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let corner = Rect {
+            x: area.x + area.width - width,
+            y: area.y,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(" Debug ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent));
+        let text: Vec<Line> = lines.into_iter().map(Line::from).collect();
+        frame.render_widget(Clear, corner);
+        frame.render_widget(Paragraph::new(text).block(block).style(Style::default().fg(theme.text)), corner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fps_is_computed_from_frame_deltas_in_the_window() {
+        let mut overlay = DebugOverlay::default();
+        let start = Instant::now();
+        for i in 0..=10 {
+            overlay.record_frame(start + Duration::from_millis(50 * i));
+        }
+        assert!((overlay.fps() - 20.0).abs() < 1e-6);
+
+        // Old frames fall out of the window
+        overlay.record_frame(start + Duration::from_millis(1400));
+        assert!(overlay.frames.len() < 11);
+    }
+
+    #[test]
+    fn render_stats_track_last_max_and_average() {
+        let mut stats = RenderStats::default();
+        assert_eq!(stats.average(), Duration::ZERO);
+        for ms in [4, 10, 1] {
+            stats.record(Duration::from_millis(ms));
+        }
+        assert_eq!(stats.frames, 3);
+        assert_eq!(stats.last, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(10));
+        assert_eq!(stats.average(), Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,104 @@
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use crate::element::Element;
+
+/// A one-line trail of routes such as "Menu › Settings › Network", with the
+/// last (current) one highlighted.
+///
+/// # Example
+/// ```ignore
+/// // In a `define_app!` root or any component holding a `Router`
+/// breadcrumb(self.router.breadcrumb())
+/// ```
+pub struct Breadcrumb {
+    items: Vec<String>,
+    separator: String,
+    style: Option<Style>,
+    current_style: Option<Style>,
+}
+
+pub fn breadcrumb<I>(items: I) -> Breadcrumb
+where
+    I: IntoIterator,
+    I::Item: std::fmt::Display,
+{
+    Breadcrumb {
+        items: items.into_iter().map(|item| item.to_string()).collect(),
+        separator: " › ".to_string(),
+        style: None,
+        current_style: None,
+    }
+}
+
+impl Breadcrumb {
+    /// Text between items. Defaults to " › ".
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Style of the earlier items and separators. Defaults to the theme's muted color.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Style of the current item. Defaults to bold in the theme's primary color.
+    pub fn current_style(mut self, style: Style) -> Self {
+        self.current_style = Some(style);
+        self
+    }
+
+    fn line(&self) -> Line<'_> {
+        let theme = crate::theme::current();
+        let style = self.style.unwrap_or_else(|| Style::default().fg(theme.muted));
+        let current_style = self.current_style
+            .unwrap_or_else(|| Style::default().fg(theme.primary).add_modifier(Modifier::BOLD));
+        let last = self.items.len().saturating_sub(1);
+        let mut spans = Vec::with_capacity(self.items.len() * 2);
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(self.separator.as_str(), style));
+            }
+            spans.push(Span::styled(item.as_str(), if i == last { current_style } else { style }));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Element for Breadcrumb {
+    fn height(&self) -> Constraint {
+        Constraint::Length(1)
+    }
+
+    fn measure_width(&self) -> Option<u16> {
+        Some(self.line().width().min(u16::MAX as usize) as u16)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Paragraph::new(self.line()), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::Router;
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn router_history_renders_as_a_trail() {
+        let mut router = Router::new("Menu");
+        router.navigate("Settings");
+        router.navigate("Network");
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 1)).unwrap();
+        let mut trail = breadcrumb(router.breadcrumb()).current_style(Style::default().bold());
+        terminal.draw(|frame| trail.render(frame, frame.area())).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer_lines(buffer), ["Menu › Settings › Network"]);
+        assert!(buffer[(18, 0)].modifier.contains(Modifier::BOLD));
+        assert!(!buffer[(0, 0)].modifier.contains(Modifier::BOLD));
+    }
+}
@@ -0,0 +1,277 @@
+//! Cached subtrees for data-heavy views.
+//!
+//! Every refresh re-runs `render` for the whole component tree. ratatui only
+//! writes changed cells to the terminal, but building the elements (and cloning
+//! the state they show) still happens each frame. A `RenderCache` kept by the
+//! component skips that for a subtree: the subtree is rebuilt only when one of
+//! the entities it watches changed, it was invalidated, or it is drawn into a
+//! different area. Otherwise the cells drawn last time are copied back.
+//!
+//! ```ignore
+//! fn on_mount(&mut self, cx: &mut Context<Self>) {
+//!     self.chart_cache = RenderCache::new().watch(&self.samples);
+//! }
+//!
+//! fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+//!     let samples = Entity::clone(&self.samples);
+//!     div()
+//!         .child(text(format!("Frame {}", cx.frame_count())))
+//!         .child(self.chart_cache.view(move || chart(&samples)))
+//! }
+//! ```
+//!
+//! Mouse wheel handlers and element keys registered inside the subtree are kept
+//! with the cached cells. The terminal cursor is not: keep inputs that place it
+//! outside of cached views.
+//!
+//! `AppContext::force_redraw` invalidates every cache and repaints the whole
+//! screen; the application does this on resize.
+
+use crate::element::key::{self, ElementKey};
+use crate::element::scroll::{self, ScrollHandler};
+use crate::element::click::{self, ClickHandler};
+use crate::element::{Element, IntoElement};
+use crate::state::Entity;
+use ratatui::buffer::Cell;
+use ratatui::prelude::*;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+thread_local! {
+    /// Bumped by `AppContext::force_redraw`; caches from an older generation are stale.
+    static GENERATION: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// Set the cache generation for the frame about to be drawn.
+pub(crate) fn set_generation(generation: u64) {
+    GENERATION.with(|g| g.set(generation));
+}
+
+fn generation() -> u64 {
+    GENERATION.with(|g| g.get())
+}
+
+/// Output of the last build, replayed while the cache is clean.
+struct Snapshot {
+    area: Rect,
+    generation: u64,
+    cells: Vec<Cell>,
+    width: Constraint,
+    height: Constraint,
+    keys: Vec<(ElementKey, Rect)>,
+    scrolls: Vec<(Rect, ScrollHandler)>,
+    clicks: Vec<(Rect, ClickHandler)>,
+}
+
+struct CacheInner {
+    /// Change notifications of the watched entities.
+    deps: Vec<watch::Receiver<u64>>,
+    invalidated: bool,
+    snapshot: Option<Snapshot>,
+    /// Number of frames drawn from the snapshot, for diagnostics.
+    hits: u64,
+}
+
+/// Render cache of one subtree, kept by the component across frames.
+///
+/// Clones share the same cache.
+#[derive(Clone)]
+pub struct RenderCache {
+    inner: Arc<Mutex<CacheInner>>,
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderCache {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(CacheInner {
+                deps: Vec::new(),
+                invalidated: true,
+                snapshot: None,
+                hits: 0,
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Rebuild the subtree whenever `entity` is updated.
+    pub fn watch<T: Send + Sync + 'static>(self, entity: &Entity<T>) -> Self {
+        self.lock().deps.push(entity.subscribe());
+        self
+    }
+
+    /// Rebuild the subtree on the next frame, e.g. after changing state that
+    /// is not held in a watched entity.
+    pub fn invalidate(&self) {
+        self.lock().invalidated = true;
+    }
+
+    /// Number of frames drawn from the cache instead of rebuilding.
+    pub fn hits(&self) -> u64 {
+        self.lock().hits
+    }
+
+    /// Whether the next `view` has to rebuild, ignoring area changes.
+    /// Marks watched changes as seen.
+    fn take_dirty(&self) -> bool {
+        let mut inner = self.lock();
+        let mut dirty = std::mem::take(&mut inner.invalidated);
+        for dep in &mut inner.deps {
+            if dep.has_changed().unwrap_or(false) {
+                dep.mark_unchanged();
+                dirty = true;
+            }
+        }
+        dirty || inner.snapshot.as_ref().is_none_or(|s| s.generation != generation())
+    }
+
+    /// The cached subtree. `build` runs now if the cache is dirty, during drawing
+    /// if the area changed, and not at all if the cached cells can be reused.
+    pub fn view<F, E>(&self, build: F) -> CachedView
+    where
+        F: FnOnce() -> E + Send + Sync + 'static,
+        E: IntoElement,
+        E::Element: 'static,
+    {
+        let build: Builder = Box::new(move || Box::new(build().into_element()));
+        if self.take_dirty() {
+            let element = build();
+            return CachedView {
+                cache: RenderCache::clone(self),
+                width: element.width(),
+                height: element.height(),
+                content: Content::Built(element),
+            };
+        }
+        let (width, height) = self.lock().snapshot.as_ref()
+            .map_or((Constraint::Min(0), Constraint::Min(0)), |s| (s.width, s.height));
+        CachedView {
+            cache: RenderCache::clone(self),
+            width,
+            height,
+            content: Content::Deferred(build),
+        }
+    }
+}
+
+type Builder = Box<dyn FnOnce() -> Box<dyn Element> + Send + Sync>;
+
+enum Content {
+    Built(Box<dyn Element>),
+    /// Only built if the cached cells don't fit the area.
+    Deferred(Builder),
+    Drawn,
+}
+
+/// Element returned by `RenderCache::view`.
+pub struct CachedView {
+    cache: RenderCache,
+    width: Constraint,
+    height: Constraint,
+    content: Content,
+}
+
+impl CachedView {
+    /// Draw `element` and keep its cells and registrations.
+    fn draw(&self, mut element: Box<dyn Element>, frame: &mut Frame, area: Rect) {
+        let (key_start, scroll_start, click_start) = (key::len(), scroll::len(), click::len());
+        element.render(frame, area);
+        let buffer = frame.buffer_mut();
+        let area = area.intersection(buffer.area);
+        let cells = area.positions().map(|p| buffer[p].clone()).collect();
+        self.cache.lock().snapshot = Some(Snapshot {
+            area,
+            generation: generation(),
+            cells,
+            width: element.width(),
+            height: element.height(),
+            keys: key::since(key_start),
+            scrolls: scroll::since(scroll_start),
+            clicks: click::since(click_start),
+        });
+    }
+}
+
+impl Element for CachedView {
+    fn width(&self) -> Constraint {
+        self.width
+    }
+
+    fn height(&self) -> Constraint {
+        self.height
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        match std::mem::replace(&mut self.content, Content::Drawn) {
+            Content::Built(element) => self.draw(element, frame, area),
+            Content::Deferred(build) => {
+                let mut inner = self.cache.lock();
+                let reusable = inner.snapshot.as_ref()
+                    .is_some_and(|s| s.area == area.intersection(frame.area()));
+                if !reusable {
+                    drop(inner);
+                    self.draw(build(), frame, area);
+                    return;
+                }
+                inner.hits += 1;
+                let snapshot = inner.snapshot.as_ref().expect("checked above");
+                let buffer = frame.buffer_mut();
+                for (position, cell) in snapshot.area.positions().zip(&snapshot.cells) {
+                    buffer[position] = cell.clone();
+                }
+                snapshot.keys.iter().for_each(|&(key, rect)| key::record(key, rect));
+                snapshot.scrolls.iter().for_each(|(rect, handler)| scroll::record(*rect, Arc::clone(handler)));
+                snapshot.clicks.iter().for_each(|(rect, handler)| click::record(*rect, Arc::clone(handler)));
+            }
+            Content::Drawn => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::text;
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn subtrees_are_rebuilt_only_when_watched_entities_change() {
+        let count = Entity::new(1_u32);
+        let builds = Arc::new(AtomicUsize::new(0));
+        let cache = RenderCache::new().watch(&count);
+        let mut terminal = Terminal::new(TestBackend::new(12, 1)).unwrap();
+        let draw = |terminal: &mut Terminal<TestBackend>, width: u16| {
+            let (count, builds) = (Entity::clone(&count), Arc::clone(&builds));
+            let mut view = cache.view(move || {
+                builds.fetch_add(1, Ordering::Relaxed);
+                text(format!("count {}", count.read(|n| *n).unwrap()))
+            });
+            terminal.draw(|frame| view.render(frame, Rect { width, ..frame.area() })).unwrap();
+            buffer_lines(terminal.backend().buffer())[0].clone()
+        };
+
+        assert_eq!(draw(&mut terminal, 12), "count 1");
+        assert_eq!(draw(&mut terminal, 12), "count 1");
+        assert_eq!((builds.load(Ordering::Relaxed), cache.hits()), (1, 1));
+
+        let _ = count.update(|n| *n = 2);
+        assert_eq!(draw(&mut terminal, 12), "count 2");
+        assert_eq!(builds.load(Ordering::Relaxed), 2);
+
+        // A different area rebuilds while drawing, as does invalidation
+        assert_eq!(draw(&mut terminal, 10), "count 2");
+        cache.invalidate();
+        draw(&mut terminal, 10);
+        assert_eq!(builds.load(Ordering::Relaxed), 4);
+    }
+}
@@ -1,10 +1,13 @@
 use ratatui::prelude::*;
 use crate::element::Element;
+use crate::state::Entity;
 
 pub struct Canvas {
     painter: Option<Box<dyn FnOnce(&mut Frame, Rect) + Send + Sync>>,
     width_constraint: Constraint,
     height_constraint: Constraint,
+    /// Receives the area the canvas is drawn into, see `canvas_measured`.
+    size: Option<Entity<Rect>>,
 }
 
 impl Canvas {
@@ -13,9 +16,17 @@ impl Canvas {
             painter: Some(Box::new(painter)),
             width_constraint: Constraint::Percentage(100),
             height_constraint: Constraint::Percentage(100),
+            size: None,
         }
     }
 
+    /// Store the area the canvas is drawn into in `size` on every frame.
+    /// The entity is only updated (and its observers notified) when the area changes.
+    pub fn measure(mut self, size: &Entity<Rect>) -> Self {
+        self.size = Some(Entity::clone(size));
+        self
+    }
+
     pub fn w_full(mut self) -> Self {
         self.width_constraint = Constraint::Percentage(100);
         self
@@ -57,6 +68,11 @@ impl Element for Canvas {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(size) = &self.size {
+            if size.read(|size| *size != area).unwrap_or(false) {
+                let _ = size.update(|size| *size = area);
+            }
+        }
         if let Some(painter) = self.painter.take() {
             painter(frame, area);
         }
@@ -66,3 +82,150 @@ impl Element for Canvas {
 pub fn canvas(painter: impl FnOnce(&mut Frame, Rect) + Send + Sync + 'static) -> Canvas {
     Canvas::new(painter)
 }
+
+/// A canvas that reports the area it is drawn into to `size`.
+///
+/// Use this when state outside the paint closure depends on the drawn size,
+/// e.g. a game loop that spawns obstacles at the right edge. Until the first
+/// frame has been drawn, `size` holds whatever it was created with (usually
+/// `Rect::default()`, i.e. empty), so check for an empty area before using it.
+///
+/// # Example
+/// ```ignore
+/// // In on_mount: self.size = cx.new_entity(Rect::default());
+/// canvas_measured(&self.size, move |frame, area| {
+///     frame.render_widget(world_widget, area);
+/// })
+/// ```
+pub fn canvas_measured(
+    size: &Entity<Rect>,
+    painter: impl FnOnce(&mut Frame, Rect) + Send + Sync + 'static,
+) -> Canvas {
+    Canvas::new(painter).measure(size)
+}
+
+/// Maps a fixed-size logical world onto the terminal for canvas-based games.
+///
+/// Game logic (movement, collisions, spawning) stays in world units; the world
+/// is fitted into whatever area is available, keeping its aspect ratio and
+/// leaving empty bars on the sides (letterboxing) when the shapes differ.
+/// Terminal cells are about twice as tall as wide, which is accounted for.
+/// The y axis points up, as in ratatui's canvas.
+///
+/// # Example
+/// ```ignore
+/// let world = CanvasWorld::new(100.0, 50.0);
+/// canvas(move |frame, area| {
+///     let viewport = world.viewport(area);
+///     frame.render_widget(
+///         RatatuiCanvas::default()
+///             .x_bounds(world.x_bounds())
+///             .y_bounds(world.y_bounds())
+///             .paint(|ctx| { /* draw in world units */ }),
+///         viewport,
+///     );
+/// })
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanvasWorld {
+    width: f64,
+    height: f64,
+}
+
+impl CanvasWorld {
+    /// Rows a terminal cell is tall, relative to its width.
+    const CELL_ASPECT: f64 = 2.0;
+
+    pub fn new(width: f64, height: f64) -> Self {
+        Self { width, height }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Bounds to pass to `Canvas::x_bounds`.
+    pub fn x_bounds(&self) -> [f64; 2] {
+        [0.0, self.width]
+    }
+
+    /// Bounds to pass to `Canvas::y_bounds`.
+    pub fn y_bounds(&self) -> [f64; 2] {
+        [0.0, self.height]
+    }
+
+    /// The largest part of `area` with the world's aspect ratio, centered.
+    pub fn viewport(&self, area: Rect) -> Rect {
+        if area.is_empty() || self.width <= 0.0 || self.height <= 0.0 {
+            return Rect { width: 0, height: 0, ..area };
+        }
+        let world_aspect = self.width / self.height;
+        let area_aspect = area.width as f64 / (area.height as f64 * Self::CELL_ASPECT);
+        if area_aspect > world_aspect {
+            // Too wide: bars left and right
+            let width = ((area.height as f64 * Self::CELL_ASPECT * world_aspect).round() as u16).clamp(1, area.width);
+            Rect { x: area.x + (area.width - width) / 2, width, ..area }
+        } else {
+            // Too tall: bars above and below
+            let height = ((area.width as f64 / world_aspect / Self::CELL_ASPECT).round() as u16).clamp(1, area.height);
+            Rect { y: area.y + (area.height - height) / 2, height, ..area }
+        }
+    }
+
+    /// The terminal cell showing world point `(x, y)` when drawn into `area`,
+    /// or `None` if the point is outside the world.
+    pub fn to_screen(&self, area: Rect, x: f64, y: f64) -> Option<(u16, u16)> {
+        let viewport = self.viewport(area);
+        if viewport.is_empty() || !(0.0..=self.width).contains(&x) || !(0.0..=self.height).contains(&y) {
+            return None;
+        }
+        let column = (x / self.width * viewport.width as f64).floor() as u16;
+        let row = ((self.height - y) / self.height * viewport.height as f64).floor() as u16;
+        Some((
+            viewport.x + column.min(viewport.width - 1),
+            viewport.y + row.min(viewport.height - 1),
+        ))
+    }
+
+    /// The world point at the center of terminal cell `(column, row)` when drawn
+    /// into `area`, or `None` if the cell is outside the viewport (e.g. in a bar).
+    pub fn from_screen(&self, area: Rect, column: u16, row: u16) -> Option<(f64, f64)> {
+        let viewport = self.viewport(area);
+        if !viewport.contains(Position::new(column, row)) {
+            return None;
+        }
+        let x = (column - viewport.x) as f64 + 0.5;
+        let y = (row - viewport.y) as f64 + 0.5;
+        Some((
+            x / viewport.width as f64 * self.width,
+            self.height - y / viewport.height as f64 * self.height,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_is_letterboxed_and_points_round_trip() {
+        let world = CanvasWorld::new(100.0, 50.0);
+        // 100x50 world units are 100 cells wide and 25 rows tall
+        assert_eq!(world.viewport(Rect::new(0, 0, 100, 25)), Rect::new(0, 0, 100, 25));
+        assert_eq!(world.viewport(Rect::new(0, 0, 200, 25)), Rect::new(50, 0, 100, 25));
+        assert_eq!(world.viewport(Rect::new(0, 0, 100, 40)), Rect::new(0, 7, 100, 25));
+
+        let area = Rect::new(0, 0, 200, 25);
+        assert_eq!(world.to_screen(area, 0.0, 50.0), Some((50, 0)));
+        assert_eq!(world.to_screen(area, 100.0, 0.0), Some((149, 24)));
+        assert_eq!(world.to_screen(area, 101.0, 0.0), None);
+
+        let (x, y) = world.from_screen(area, 75, 12).unwrap();
+        assert_eq!(world.to_screen(area, x, y), Some((75, 12)));
+        assert_eq!(world.from_screen(area, 10, 12), None);
+    }
+}
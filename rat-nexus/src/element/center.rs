@@ -0,0 +1,105 @@
+use ratatui::prelude::*;
+use ratatui::widgets::Clear;
+use crate::element::{Element, IntoElement};
+
+/// The `width` x `height` part in the middle of `area`, shrunk to fit.
+pub fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// An element drawn in a fixed-size box in the middle of its area.
+///
+/// The element itself fills the area it is given, so it can be drawn over a
+/// base element or returned from an overlay, which is drawn over the whole screen.
+///
+/// # Example
+/// ```ignore
+/// // A banner over the game view
+/// centered(text("GAME OVER").align_center(), 13, 1).clear().over(game_view)
+///
+/// // A dialog pushed with `cx.push_overlay`
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     centered(div().border_all().title(" Confirm ").child(text("Discard changes?")), 40, 5).clear()
+/// }
+/// ```
+pub struct Centered {
+    child: Box<dyn Element>,
+    width: u16,
+    height: u16,
+    clear: bool,
+    base: Option<Box<dyn Element>>,
+}
+
+pub fn centered(child: impl IntoElement + 'static, width: u16, height: u16) -> Centered {
+    Centered {
+        child: Box::new(child.into_element()),
+        width,
+        height,
+        clear: false,
+        base: None,
+    }
+}
+
+/// A `width` x `height` box in the middle of its area with the cells under it
+/// cleared first, e.g. a dialog in a `stack` over the page.
+pub fn centered_popup(width: u16, height: u16, child: impl IntoElement + 'static) -> Centered {
+    centered(child, width, height).clear()
+}
+
+impl Centered {
+    /// Reset the cells under the box before drawing the child, so nothing drawn
+    /// below (the base, or the page under an overlay) shows through.
+    pub fn clear(mut self) -> Self {
+        self.clear = true;
+        self
+    }
+
+    /// Render the box on top of the given element.
+    pub fn over(mut self, base: impl IntoElement + 'static) -> Self {
+        self.base = Some(Box::new(base.into_element()));
+        self
+    }
+}
+
+impl Element for Centered {
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(base) = self.base.as_mut() {
+            base.render(frame, area);
+        }
+        let inner = centered_rect(area, self.width, self.height);
+        if self.clear {
+            frame.render_widget(Clear, inner);
+        }
+        self.child.render(frame, inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{div, text};
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn draw(mut element: impl Element, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| element.render(frame, frame.area())).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    #[test]
+    fn children_are_centered_in_both_axes() {
+        let lines = draw(div().center().child(text("12:34")).child(text("RUNNING")), 11, 5);
+        assert_eq!(lines, ["", "", "   12:34", "  RUNNING", ""]);
+
+        let banner = centered(text("OVER"), 4, 1).clear().over(text("xxxxxxxxxx\nxxxxxxxxxx\nxxxxxxxxxx").h_auto());
+        assert_eq!(draw(banner, 10, 3), ["xxxxxxxxxx", "xxxOVERxxx", "xxxxxxxxxx"]);
+    }
+}
@@ -0,0 +1,513 @@
+use ratatui::prelude::*;
+use ratatui::symbols;
+use ratatui::widgets::{Axis, Bar, BarChart, BarGroup, Chart, Dataset, GraphType, Sparkline};
+use crate::element::Element;
+use crate::state::Entity;
+
+/// Where a chart gets its data from.
+enum Data<T> {
+    Fixed(T),
+    /// Read when the element is drawn.
+    Bound(Box<dyn Fn() -> T + Send + Sync>),
+}
+
+impl<T: Default + 'static> Data<T> {
+    fn bind<S, F>(entity: &Entity<S>, f: F) -> Self
+    where
+        S: Send + Sync + 'static,
+        F: Fn(&S) -> T + Send + Sync + 'static,
+    {
+        let entity = Entity::clone(entity);
+        Data::Bound(Box::new(move || entity.read(&f).unwrap_or_default()))
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        match self {
+            Data::Fixed(data) => f(data),
+            Data::Bound(read) => f(&read()),
+        }
+    }
+}
+
+/// Width and height setters shared by the chart elements.
+macro_rules! sizing {
+    ($view:ty) => {
+        impl $view {
+            pub fn w_full(mut self) -> Self {
+                self.width_constraint = Constraint::Percentage(100);
+                self
+            }
+
+            pub fn h_full(mut self) -> Self {
+                self.height_constraint = Constraint::Percentage(100);
+                self
+            }
+
+            pub fn w(mut self, length: u16) -> Self {
+                self.width_constraint = Constraint::Length(length);
+                self
+            }
+
+            pub fn h(mut self, length: u16) -> Self {
+                self.height_constraint = Constraint::Length(length);
+                self
+            }
+
+            pub fn w_percent(mut self, p: u16) -> Self {
+                self.width_constraint = Constraint::Percentage(p);
+                self
+            }
+
+            pub fn h_percent(mut self, p: u16) -> Self {
+                self.height_constraint = Constraint::Percentage(p);
+                self
+            }
+        }
+    };
+}
+
+/// A compact bar graph of recent values, e.g. network throughput.
+///
+/// When there are more values than columns, the newest ones are shown, so a
+/// history that grows at the end scrolls to the left. Bars are scaled to the
+/// largest visible value unless `max` is set.
+///
+/// # Example
+/// ```ignore
+/// sparkline([]).bind(&self.state, |s| s.network_in.clone()).fg(Color::Green).h(3)
+/// ```
+pub struct SparklineView {
+    data: Data<Vec<u64>>,
+    style: Style,
+    max: Option<u64>,
+    width_constraint: Constraint,
+    height_constraint: Constraint,
+}
+
+pub fn sparkline(data: impl IntoIterator<Item = u64>) -> SparklineView {
+    SparklineView {
+        data: Data::Fixed(data.into_iter().collect()),
+        style: Style::default(),
+        max: None,
+        width_constraint: Constraint::Min(0),
+        height_constraint: Constraint::Min(0),
+    }
+}
+
+impl SparklineView {
+    /// Read the values from an entity each time the element is drawn.
+    pub fn bind<T, F>(mut self, entity: &Entity<T>, f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&T) -> Vec<u64> + Send + Sync + 'static,
+    {
+        self.data = Data::bind(entity, f);
+        self
+    }
+
+    /// Value drawn as a full-height bar. Defaults to the largest visible value.
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Color of the bars. Defaults to the theme's primary color.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.style = self.style.fg(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.style = self.style.bg(color);
+        self
+    }
+}
+
+sizing!(SparklineView);
+
+/// The last `width` values, the ones a sparkline of that width shows.
+fn tail(data: &[u64], width: u16) -> &[u64] {
+    &data[data.len().saturating_sub(width as usize)..]
+}
+
+impl Element for SparklineView {
+    fn width(&self) -> Constraint {
+        self.width_constraint
+    }
+
+    fn height(&self) -> Constraint {
+        self.height_constraint
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let style = match self.style.fg {
+            Some(_) => self.style,
+            None => self.style.fg(crate::theme::current().primary),
+        };
+        self.data.with(|data| {
+            let mut sparkline = Sparkline::default().data(tail(data, area.width)).style(style);
+            if let Some(max) = self.max {
+                sparkline = sparkline.max(max);
+            }
+            frame.render_widget(sparkline, area);
+        });
+    }
+}
+
+/// Labeled vertical bars, e.g. requests per endpoint.
+///
+/// Bars share the available width unless `bar_width` is set, and are scaled to
+/// the largest value unless `max` is set.
+///
+/// # Example
+/// ```ignore
+/// bar_chart([]).bind(&self.state, |s| s.per_core.iter().enumerate()
+///     .map(|(i, load)| (format!("C{i}"), *load as u64)).collect())
+/// ```
+pub struct BarChartView {
+    data: Data<Vec<(String, u64)>>,
+    bar_width: Option<u16>,
+    bar_gap: u16,
+    style: Style,
+    max: Option<u64>,
+    width_constraint: Constraint,
+    height_constraint: Constraint,
+}
+
+pub fn bar_chart<I, L>(data: I) -> BarChartView
+where
+    I: IntoIterator<Item = (L, u64)>,
+    L: Into<String>,
+{
+    BarChartView {
+        data: Data::Fixed(data.into_iter().map(|(label, value)| (label.into(), value)).collect()),
+        bar_width: None,
+        bar_gap: 1,
+        style: Style::default(),
+        max: None,
+        width_constraint: Constraint::Min(0),
+        height_constraint: Constraint::Min(0),
+    }
+}
+
+impl BarChartView {
+    /// Read the labels and values from an entity each time the element is drawn.
+    pub fn bind<T, F>(mut self, entity: &Entity<T>, f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&T) -> Vec<(String, u64)> + Send + Sync + 'static,
+    {
+        self.data = Data::bind(entity, f);
+        self
+    }
+
+    /// Columns per bar. Defaults to filling the width.
+    pub fn bar_width(mut self, width: u16) -> Self {
+        self.bar_width = Some(width.max(1));
+        self
+    }
+
+    /// Columns between bars. Defaults to 1.
+    pub fn bar_gap(mut self, gap: u16) -> Self {
+        self.bar_gap = gap;
+        self
+    }
+
+    /// Value drawn as a full-height bar. Defaults to the largest value.
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Color of the bars. Defaults to the theme's primary color.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.style = self.style.fg(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.style = self.style.bg(color);
+        self
+    }
+}
+
+sizing!(BarChartView);
+
+impl Element for BarChartView {
+    fn width(&self) -> Constraint {
+        self.width_constraint
+    }
+
+    fn height(&self) -> Constraint {
+        self.height_constraint
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = crate::theme::current();
+        let style = match self.style.fg {
+            Some(_) => self.style,
+            None => self.style.fg(theme.primary),
+        };
+        self.data.with(|data| {
+            if data.is_empty() {
+                return;
+            }
+            let count = u16::try_from(data.len()).unwrap_or(u16::MAX);
+            // Wider bars or gaps than the area don't show, and overflow ratatui's layout
+            let bar_gap = self.bar_gap.min(area.width);
+            let bar_width = self
+                .bar_width
+                .unwrap_or_else(|| (area.width.saturating_add(bar_gap) / count).saturating_sub(bar_gap))
+                .clamp(1, area.width.max(1));
+            // Only the bars that fit are drawn, so don't hand ratatui the rest
+            let fit = (area.width.saturating_add(bar_gap) / bar_width.saturating_add(bar_gap)).max(1);
+            let bars: Vec<Bar> = data
+                .iter()
+                .take(fit as usize)
+                .map(|(label, value)| Bar::default().label(Line::from(label.as_str())).value(*value))
+                .collect();
+            let chart = BarChart::default()
+                .data(BarGroup::default().bars(&bars))
+                .bar_width(bar_width)
+                .bar_gap(bar_gap)
+                .bar_style(style)
+                .value_style(Style::default().fg(theme.background).bg(style.fg.unwrap_or(theme.primary)))
+                .label_style(Style::default().fg(theme.muted));
+            // Scale to every bar, as if the ones cut off were there
+            let max = self.max.unwrap_or_else(|| data.iter().map(|(_, value)| *value).max().unwrap_or(0));
+            frame.render_widget(chart.max(max), area);
+        });
+    }
+}
+
+/// One line of a `line_chart`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    name: String,
+    points: Vec<(f64, f64)>,
+    color: Option<Color>,
+}
+
+impl Series {
+    /// A series of `(x, y)` points, in order of `x`.
+    pub fn new(name: impl Into<String>, points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        Self {
+            name: name.into(),
+            points: points.into_iter().collect(),
+            color: None,
+        }
+    }
+
+    /// A series of values at x = 0, 1, 2, ..., e.g. samples taken once per tick.
+    pub fn from_values(name: impl Into<String>, values: impl IntoIterator<Item = f64>) -> Self {
+        Self::new(name, values.into_iter().enumerate().map(|(x, y)| (x as f64, y)))
+    }
+
+    /// Line color. Defaults to the theme's primary, accent, success, warning
+    /// and error colors, in order of the series.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Lines over x/y axes, e.g. CPU and memory usage over time.
+///
+/// The axes span the data's minimum to maximum unless bounds are set, so
+/// the lines use the full height whatever their range.
+///
+/// # Example
+/// ```ignore
+/// line_chart([])
+///     .bind(&self.state, |s| vec![
+///         Series::from_values("CPU", s.cpu_history.iter().map(|&v| v as f64)),
+///         Series::from_values("Memory", s.memory_history.iter().map(|&v| v as f64)),
+///     ])
+///     .y_bounds(0.0, 100.0)
+///     .y_title("Usage %")
+///     .axis_labels()
+/// ```
+pub struct LineChartView {
+    series: Data<Vec<Series>>,
+    x_bounds: Option<[f64; 2]>,
+    y_bounds: Option<[f64; 2]>,
+    x_title: Option<String>,
+    y_title: Option<String>,
+    axis_labels: bool,
+    width_constraint: Constraint,
+    height_constraint: Constraint,
+}
+
+pub fn line_chart(series: impl IntoIterator<Item = Series>) -> LineChartView {
+    LineChartView {
+        series: Data::Fixed(series.into_iter().collect()),
+        x_bounds: None,
+        y_bounds: None,
+        x_title: None,
+        y_title: None,
+        axis_labels: false,
+        width_constraint: Constraint::Min(0),
+        height_constraint: Constraint::Min(0),
+    }
+}
+
+impl LineChartView {
+    /// Read the series from an entity each time the element is drawn.
+    pub fn bind<T, F>(mut self, entity: &Entity<T>, f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&T) -> Vec<Series> + Send + Sync + 'static,
+    {
+        self.series = Data::bind(entity, f);
+        self
+    }
+
+    /// Fix the x axis range instead of fitting it to the data.
+    pub fn x_bounds(mut self, min: f64, max: f64) -> Self {
+        self.x_bounds = Some([min, max]);
+        self
+    }
+
+    /// Fix the y axis range instead of fitting it to the data, e.g. 0 to 100 for percentages.
+    pub fn y_bounds(mut self, min: f64, max: f64) -> Self {
+        self.y_bounds = Some([min, max]);
+        self
+    }
+
+    pub fn x_title(mut self, title: impl Into<String>) -> Self {
+        self.x_title = Some(title.into());
+        self
+    }
+
+    pub fn y_title(mut self, title: impl Into<String>) -> Self {
+        self.y_title = Some(title.into());
+        self
+    }
+
+    /// Label the ends of the x axis and the ends and middle of the y axis.
+    pub fn axis_labels(mut self) -> Self {
+        self.axis_labels = true;
+        self
+    }
+
+    /// The x and y ranges that will be drawn.
+    pub fn bounds(&self) -> ([f64; 2], [f64; 2]) {
+        self.series.with(|series| {
+            let points = || series.iter().flat_map(|s| s.points.iter());
+            let x = self.x_bounds.unwrap_or_else(|| fit(points().map(|p| p.0)));
+            let y = self.y_bounds.unwrap_or_else(|| fit(points().map(|p| p.1)));
+            (x, y)
+        })
+    }
+}
+
+sizing!(LineChartView);
+
+/// The range covering `values`, widened when they are all equal so the line
+/// isn't drawn on the edge.
+fn fit(values: impl Iterator<Item = f64>) -> [f64; 2] {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)));
+    if min > max {
+        [0.0, 1.0]
+    } else if min == max {
+        [min - 1.0, max + 1.0]
+    } else {
+        [min, max]
+    }
+}
+
+/// Axis label for a value: whole numbers without decimals, others with one.
+fn tick(value: f64) -> String {
+    if value.fract() == 0.0 || value.abs() >= 100.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+impl Element for LineChartView {
+    fn width(&self) -> Constraint {
+        self.width_constraint
+    }
+
+    fn height(&self) -> Constraint {
+        self.height_constraint
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = crate::theme::current();
+        let palette = [theme.primary, theme.accent, theme.success, theme.warning, theme.error];
+        let (x_bounds, y_bounds) = self.bounds();
+        let axis_style = Style::default().fg(theme.muted);
+
+        let mut x_axis = Axis::default().bounds(x_bounds).style(axis_style);
+        let mut y_axis = Axis::default().bounds(y_bounds).style(axis_style);
+        if let Some(title) = &self.x_title {
+            x_axis = x_axis.title(title.clone());
+        }
+        if let Some(title) = &self.y_title {
+            y_axis = y_axis.title(title.clone());
+        }
+        if self.axis_labels {
+            x_axis = x_axis.labels([tick(x_bounds[0]), tick(x_bounds[1])]);
+            let middle = (y_bounds[0] + y_bounds[1]) / 2.0;
+            y_axis = y_axis.labels([tick(y_bounds[0]), tick(middle), tick(y_bounds[1])]);
+        }
+
+        self.series.with(|series| {
+            let datasets = series
+                .iter()
+                .enumerate()
+                .map(|(i, s)| {
+                    Dataset::default()
+                        .name(s.name.clone())
+                        .marker(symbols::Marker::Braille)
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(s.color.unwrap_or(palette[i % palette.len()])))
+                        .data(&s.points)
+                })
+                .collect();
+            frame.render_widget(Chart::new(datasets).x_axis(x_axis).y_axis(y_axis), area);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn charts_fit_their_data_and_follow_bound_entities() {
+        let history = Entity::new(vec![1_u64, 8, 2, 8]);
+        let mut spark = sparkline([]).bind(&history, |h| h.clone());
+        let mut terminal = Terminal::new(TestBackend::new(3, 1)).unwrap();
+        terminal.draw(|frame| spark.render(frame, frame.area())).unwrap();
+        // Only the newest three fit, scaled to the largest of them
+        assert_eq!(buffer_lines(terminal.backend().buffer()), ["█▂█"]);
+        history.update(|h| h.push(0)).unwrap();
+        terminal.draw(|frame| spark.render(frame, frame.area())).unwrap();
+        assert_eq!(buffer_lines(terminal.backend().buffer()), ["▂█"]);
+
+        let mut bars = bar_chart([("a", 1), ("b", 2)]).max(2);
+        let mut terminal = Terminal::new(TestBackend::new(5, 3)).unwrap();
+        terminal.draw(|frame| bars.render(frame, frame.area())).unwrap();
+        assert_eq!(buffer_lines(terminal.backend().buffer()), ["   ██", "1█ 2█", "a  b"]);
+        // More bars than a u16 counts, or a huge gap, must not overflow
+        let mut many = bar_chart((0..70_000).map(|i| (i.to_string(), 1))).bar_gap(u16::MAX);
+        terminal.draw(|frame| many.render(frame, frame.area())).unwrap();
+
+        let chart = line_chart([
+            Series::new("a", [(0.0, 5.0), (2.0, -1.0)]),
+            Series::from_values("b", [3.0, f64::NAN, 7.5]),
+        ]);
+        assert_eq!(chart.bounds(), ([0.0, 2.0], [-1.0, 7.5]));
+        let flat = line_chart([Series::from_values("flat", [4.0, 4.0])]).y_bounds(0.0, 100.0);
+        assert_eq!(flat.bounds(), ([0.0, 1.0], [0.0, 100.0]));
+        assert_eq!(line_chart([]).bounds(), ([0.0, 1.0], [0.0, 1.0]));
+        assert_eq!((tick(2.0), tick(2.25), tick(150.4)), ("2".to_string(), "2.2".to_string(), "150".to_string()));
+    }
+}
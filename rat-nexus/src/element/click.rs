@@ -0,0 +1,109 @@
+//! Mouse click routing to the element under the pointer.
+//!
+//! Elements register a click handler for the area they were drawn into (see
+//! `Div::on_click`), so the application can hit-test a click against the last
+//! frame without the component doing coordinate math. When the left button
+//! goes down, the innermost handler whose area contains the pointer runs and
+//! the event is consumed. Clicks outside any handler are delivered to the root
+//! component like any other mouse event.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Called when an element is clicked.
+pub type ClickHandler = Arc<dyn Fn() + Send + Sync>;
+
+thread_local! {
+    /// Click handlers of the last drawn frame with their areas, in render order.
+    static HANDLERS: RefCell<Vec<(Rect, ClickHandler)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Register a handler for clicks inside `area` this frame.
+pub(crate) fn record(area: Rect, handler: ClickHandler) {
+    HANDLERS.with(|handlers| handlers.borrow_mut().push((area, handler)));
+}
+
+/// Forget the previous frame's handlers. Called before each frame is drawn.
+pub(crate) fn clear() {
+    HANDLERS.with(|handlers| handlers.borrow_mut().clear());
+}
+
+/// Number of handlers registered so far this frame.
+pub(crate) fn len() -> usize {
+    HANDLERS.with(|handlers| handlers.borrow().len())
+}
+
+/// Handlers registered this frame after the first `start`, e.g. by a cached subtree.
+pub(crate) fn since(start: usize) -> Vec<(Rect, ClickHandler)> {
+    HANDLERS.with(|handlers| handlers.borrow().get(start..).map(<[_]>::to_vec).unwrap_or_default())
+}
+
+/// Remove and return the handlers registered after the first `start`, e.g. to
+/// move those of an offscreen subtree to where it is shown.
+pub(crate) fn split_off(start: usize) -> Vec<(Rect, ClickHandler)> {
+    HANDLERS.with(|handlers| {
+        let mut handlers = handlers.borrow_mut();
+        let start = start.min(handlers.len());
+        handlers.split_off(start)
+    })
+}
+
+/// Run the innermost handler under the pointer. Returns false if the event is
+/// not a left-button press or no clickable element is under the pointer.
+pub(crate) fn dispatch(mouse: &MouseEvent) -> bool {
+    if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+        return false;
+    }
+    let position = Position::new(mouse.column, mouse.row);
+    // Elements are drawn before their children, so the last match is the innermost
+    let handler = HANDLERS.with(|handlers| {
+        handlers
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, handler)| Arc::clone(handler))
+    });
+    match handler {
+        Some(handler) => {
+            handler();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{div, text, Element};
+    use crossterm::event::KeyModifiers;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::sync::Mutex;
+
+    #[test]
+    fn clicks_reach_the_innermost_element_under_the_pointer() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (card, button) = (Arc::clone(&log), Arc::clone(&log));
+        let mut terminal = Terminal::new(TestBackend::new(20, 4)).unwrap();
+        terminal
+            .draw(|frame| {
+                clear();
+                let mut root = div()
+                    .on_click(move || card.lock().unwrap().push("card"))
+                    .child(text("title"))
+                    .child(div().h(1).on_click(move || button.lock().unwrap().push("button")));
+                root.render(frame, frame.area());
+            })
+            .unwrap();
+
+        let mouse = |kind, row| MouseEvent { kind, column: 3, row, modifiers: KeyModifiers::NONE };
+        assert!(dispatch(&mouse(MouseEventKind::Down(MouseButton::Left), 1)));
+        assert!(dispatch(&mouse(MouseEventKind::Down(MouseButton::Left), 0)));
+        assert!(!dispatch(&mouse(MouseEventKind::Down(MouseButton::Right), 1)));
+        assert!(!dispatch(&mouse(MouseEventKind::Up(MouseButton::Left), 1)));
+        assert_eq!(*log.lock().unwrap(), ["button", "card"]);
+    }
+}
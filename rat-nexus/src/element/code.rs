@@ -0,0 +1,167 @@
+use std::sync::OnceLock;
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, Style as SyntectStyle, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+use crate::element::Element;
+
+/// Loading the syntax and theme sets is expensive, so they are shared across frames.
+static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+
+fn syntaxes() -> &'static SyntaxSet {
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn themes() -> &'static ThemeSet {
+    THEMES.get_or_init(ThemeSet::load_defaults)
+}
+
+/// Syntax-highlighted source code.
+///
+/// The language is looked up by name or file extension (e.g. "rust", "rs", "json").
+/// Unknown languages are rendered as plain text.
+pub struct Code {
+    source: String,
+    language: String,
+    theme: String,
+    line_numbers: bool,
+    scroll: u16,
+    width_constraint: Constraint,
+    height_constraint: Constraint,
+}
+
+pub fn code(source: impl Into<String>, language: impl Into<String>) -> Code {
+    Code::new(source, language)
+}
+
+impl Code {
+    pub fn new(source: impl Into<String>, language: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            language: language.into(),
+            theme: "base16-ocean.dark".to_string(),
+            line_numbers: false,
+            scroll: 0,
+            width_constraint: Constraint::Min(0),
+            height_constraint: Constraint::Min(0),
+        }
+    }
+
+    /// Show a line number gutter.
+    pub fn line_numbers(mut self, show: bool) -> Self {
+        self.line_numbers = show;
+        self
+    }
+
+    /// Use one of syntect's bundled themes, e.g. "InspiredGitHub" or "Solarized (dark)".
+    pub fn theme(mut self, theme: impl Into<String>) -> Self {
+        self.theme = theme.into();
+        self
+    }
+
+    /// Scroll the content down by the given number of lines.
+    pub fn scroll(mut self, offset: u16) -> Self {
+        self.scroll = offset;
+        self
+    }
+
+    pub fn w_full(mut self) -> Self {
+        self.width_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn h_full(mut self) -> Self {
+        self.height_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn w(mut self, length: u16) -> Self {
+        self.width_constraint = Constraint::Length(length);
+        self
+    }
+
+    pub fn h(mut self, length: u16) -> Self {
+        self.height_constraint = Constraint::Length(length);
+        self
+    }
+}
+
+impl Element for Code {
+    fn width(&self) -> Constraint {
+        self.width_constraint
+    }
+
+    fn height(&self) -> Constraint {
+        self.height_constraint
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        // Highlighting is stateful, so every line up to the end of the viewport is processed,
+        // but nothing past it.
+        let visible_end = self.scroll as usize + area.height as usize;
+        let lines = highlight(&self.source, &self.language, &self.theme, visible_end);
+
+        let total = self.source.lines().count().max(1);
+        let gutter = total.to_string().len();
+        let lines: Vec<Line> = lines
+            .into_iter()
+            .enumerate()
+            .skip(self.scroll as usize)
+            .map(|(i, line)| {
+                if self.line_numbers {
+                    let mut spans = vec![Span::styled(format!("{:>gutter$} │ ", i + 1), Style::default().fg(Color::DarkGray))];
+                    spans.extend(line.spans);
+                    Line::from(spans)
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+/// Highlight up to `max_lines` lines of `source` into styled lines.
+pub fn highlight(source: &str, language: &str, theme: &str, max_lines: usize) -> Vec<Line<'static>> {
+    let syntaxes = syntaxes();
+    let syntax = syntaxes
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+    let themes = themes();
+    let Some(theme) = themes.themes.get(theme).or_else(|| themes.themes.values().next()) else {
+        return source.lines().take(max_lines).map(|l| Line::raw(l.to_string())).collect();
+    };
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    LinesWithEndings::from(source)
+        .take(max_lines)
+        .map(|line| match highlighter.highlight_line(line, syntaxes) {
+            Ok(ranges) => Line::from(
+                ranges
+                    .into_iter()
+                    .map(|(style, text)| Span::styled(text.trim_end_matches(['\n', '\r']).to_string(), convert_style(style)))
+                    .collect::<Vec<_>>(),
+            ),
+            Err(_) => Line::raw(line.trim_end_matches(['\n', '\r']).to_string()),
+        })
+        .collect()
+}
+
+fn convert_style(style: SyntectStyle) -> Style {
+    let fg = style.foreground;
+    let mut out = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+    if style.font_style.contains(FontStyle::BOLD) {
+        out = out.add_modifier(Modifier::BOLD);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        out = out.add_modifier(Modifier::ITALIC);
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        out = out.add_modifier(Modifier::UNDERLINED);
+    }
+    out
+}
@@ -1,20 +1,43 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, BorderType};
+use ratatui::layout::Flex;
+use ratatui::widgets::{Block, Borders, BorderType, Clear};
 use crate::element::{Element, IntoElement};
+use crate::element::key::{self, ElementKey};
+use crate::breakpoint::Breakpoint;
+use crate::element::scroll::{self, ScrollDirection, ScrollHandler};
+use crate::element::click::{self, ClickHandler};
+use std::sync::Arc;
 
 pub struct Div {
     children: Vec<Box<dyn Element>>,
+    /// Key of each child, `None` for children added without one.
+    keys: Vec<Option<ElementKey>>,
     style: Style,
     direction: Direction,
     width_constraint: Constraint,
     height_constraint: Constraint,
     // Block properties
     borders: Borders,
-    border_style: Style,
+    /// `None` until set explicitly; falls back to the theme's border color.
+    border_style: Option<Style>,
     border_type: BorderType,
     title: Option<String>,
     padding: ratatui::widgets::Padding,
     margin: u16,
+    /// Blank cells between children, see `gap`.
+    gap: u16,
+    /// Placement of the children along the layout direction, `None` to
+    /// stretch the last child over the leftover space; see `justify_center`.
+    justify: Option<Flex>,
+    /// Placement of each child across the layout direction, `None` to
+    /// stretch it across; see `items_center`.
+    items: Option<Flex>,
+    /// Whether the area is reset before drawing, see `clear`.
+    clear: bool,
+    /// Runs when the mouse wheel turns over this div.
+    on_scroll: Option<ScrollHandler>,
+    /// Runs when the div is clicked.
+    on_click: Option<ClickHandler>,
 }
 
 pub fn div() -> Div {
@@ -25,16 +48,23 @@ impl Default for Div {
     fn default() -> Self {
         Self {
             children: Vec::new(),
+            keys: Vec::new(),
             style: Style::default(),
             direction: Direction::Vertical,
             width_constraint: Constraint::Min(0),
             height_constraint: Constraint::Min(0),
             borders: Borders::NONE,
-            border_style: Style::default(),
+            border_style: None,
             border_type: BorderType::Plain,
             title: None,
             padding: ratatui::widgets::Padding::ZERO,
             margin: 0,
+            gap: 0,
+            justify: None,
+            items: None,
+            clear: false,
+            on_scroll: None,
+            on_click: None,
         }
     }
 }
@@ -56,6 +86,66 @@ impl Div {
         self
     }
 
+    /// Center the children in both axes. They keep their fixed or measured
+    /// sizes, e.g. a text is as wide as its longest line.
+    ///
+    /// ```ignore
+    /// div().center().child(text("GAME OVER").bold())
+    /// ```
+    pub fn center(self) -> Self {
+        self.justify_center().items_center()
+    }
+
+    // Justification sizes the children by their content (e.g. a text is as
+    // long as its longest line) and places them along the layout direction.
+
+    /// Center the children along the layout direction, e.g. a menu vertically.
+    pub fn justify_center(mut self) -> Self {
+        self.justify = Some(Flex::Center);
+        self
+    }
+
+    /// Pack the children at the end of the layout direction.
+    pub fn justify_end(mut self) -> Self {
+        self.justify = Some(Flex::End);
+        self
+    }
+
+    /// Put the first child at the start, the last at the end and share the
+    /// leftover space evenly between them.
+    pub fn justify_between(mut self) -> Self {
+        self.justify = Some(Flex::SpaceBetween);
+        self
+    }
+
+    /// Shrink each child to its size across the layout direction and center it,
+    /// e.g. horizontally in a column.
+    pub fn items_center(mut self) -> Self {
+        self.items = Some(Flex::Center);
+        self
+    }
+
+    /// Shrink each child to its size across the layout direction and put it at the end.
+    pub fn items_end(mut self) -> Self {
+        self.items = Some(Flex::End);
+        self
+    }
+
+    /// Adjust the div for the terminal's breakpoint, e.g. to stack panes on narrow terminals.
+    ///
+    /// ```ignore
+    /// div().responsive(|div, bp| match bp {
+    ///     Breakpoint::Small => div.flex_col(),
+    ///     _ => div.flex_row(),
+    /// })
+    /// ```
+    pub fn responsive<F>(self, f: F) -> Self
+    where
+        F: FnOnce(Self, Breakpoint) -> Self,
+    {
+        f(self, crate::breakpoint::current())
+    }
+
     // --- Sizing ---
 
     pub fn w_full(mut self) -> Self {
@@ -78,6 +168,49 @@ impl Div {
         self
     }
 
+    // Thirds and quarters are ratios rather than percentages, so that e.g.
+    // three `w_1_3` columns split the row exactly without a leftover cell.
+
+    pub fn w_1_3(mut self) -> Self {
+        self.width_constraint = Constraint::Ratio(1, 3);
+        self
+    }
+
+    pub fn w_2_3(mut self) -> Self {
+        self.width_constraint = Constraint::Ratio(2, 3);
+        self
+    }
+
+    pub fn w_1_4(mut self) -> Self {
+        self.width_constraint = Constraint::Ratio(1, 4);
+        self
+    }
+
+    pub fn w_3_4(mut self) -> Self {
+        self.width_constraint = Constraint::Ratio(3, 4);
+        self
+    }
+
+    pub fn h_1_3(mut self) -> Self {
+        self.height_constraint = Constraint::Ratio(1, 3);
+        self
+    }
+
+    pub fn h_2_3(mut self) -> Self {
+        self.height_constraint = Constraint::Ratio(2, 3);
+        self
+    }
+
+    pub fn h_1_4(mut self) -> Self {
+        self.height_constraint = Constraint::Ratio(1, 4);
+        self
+    }
+
+    pub fn h_3_4(mut self) -> Self {
+        self.height_constraint = Constraint::Ratio(3, 4);
+        self
+    }
+
     pub fn w(mut self, length: u16) -> Self {
         self.width_constraint = Constraint::Length(length);
         self
@@ -131,7 +264,7 @@ impl Div {
     }
 
     pub fn border_style(mut self, style: Style) -> Self {
-        self.border_style = style;
+        self.border_style = Some(style);
         self
     }
 
@@ -162,19 +295,117 @@ impl Div {
         self
     }
 
+    /// Leave `gap` blank rows (or columns, in a `flex_row`) between the
+    /// children, but not before the first or after the last. When the space
+    /// runs out the children shrink first, then the gaps.
+    ///
+    /// ```ignore
+    /// div().gap(1).child(header).child(body).child(footer)
+    /// ```
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Reset the cells under the div before drawing it, so glyphs drawn below
+    /// (e.g. the page under a panel or modal) don't show through cells the div
+    /// leaves empty. A background color alone only restyles those cells.
+    ///
+    /// ```ignore
+    /// div().clear().bg(theme.background).border_all().child(text("Saved"))
+    /// ```
+    pub fn clear(mut self) -> Self {
+        self.clear = true;
+        self
+    }
+
+    // --- Events ---
+
+    /// Handle mouse wheel events over this div, e.g. to scroll the panel under the
+    /// pointer. Wheel events over a div with a handler don't reach the component's
+    /// `handle_event`; nested handlers take precedence over outer ones.
+    pub fn on_scroll<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(ScrollDirection) + Send + Sync + 'static,
+    {
+        self.on_scroll = Some(Arc::new(handler));
+        self
+    }
+
+    /// Run `handler` when the left mouse button is pressed over this div, e.g.
+    /// to make a card or menu entry clickable. Clicks on a div with a handler
+    /// don't reach the component's `handle_event`; nested handlers take
+    /// precedence over outer ones.
+    ///
+    /// ```ignore
+    /// let selected = Entity::clone(&self.selected);
+    /// div().border_all().child(text(item.name.clone())).on_click(move || {
+    ///     let _ = selected.update(|s| *s = Some(id));
+    /// })
+    /// ```
+    pub fn on_click<F>(mut self, handler: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_click = Some(Arc::new(handler));
+        self
+    }
+
     // --- Children ---
 
     pub fn child(mut self, child: impl IntoElement + 'static) -> Self {
         self.children.push(Box::new(child.into_element()));
+        self.keys.push(None);
         self
     }
 
-    pub fn children(mut self, children: Vec<impl IntoElement + 'static>) -> Self {
+    /// Add several children, e.g. `list.map(|_, item| text(item.name.clone()))`.
+    pub fn children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoElement + 'static,
+    {
         for child in children {
             self.children.push(Box::new(child.into_element()));
+            self.keys.push(None);
+        }
+        self
+    }
+
+    /// Add one child per item, each tagged with a stable key.
+    ///
+    /// The key identifies the item independent of its position, so it survives
+    /// reordering and filtering. After the frame is drawn, `element::key_at`
+    /// maps a mouse position back to the key of the item under it.
+    ///
+    /// # Example
+    /// ```ignore
+    /// div().keyed_children(
+    ///     todos.iter(),
+    ///     |todo| todo.id,
+    ///     |todo| text(todo.title.clone()),
+    /// )
+    /// ```
+    pub fn keyed_children<I, K, E, KF, RF>(mut self, items: I, mut key_fn: KF, mut render_fn: RF) -> Self
+    where
+        I: IntoIterator,
+        K: std::hash::Hash,
+        E: IntoElement + 'static,
+        KF: FnMut(&I::Item) -> K,
+        RF: FnMut(I::Item) -> E,
+    {
+        for item in items {
+            let key = ElementKey::new(key_fn(&item));
+            self.children.push(Box::new(render_fn(item).into_element()));
+            self.keys.push(Some(key));
         }
         self
     }
+
+    /// Keys of the children, in order (`None` for unkeyed children).
+    pub fn keys(&self) -> &[Option<ElementKey>] {
+        &self.keys
+    }
 }
 
 impl Element for Div {
@@ -186,12 +417,53 @@ impl Element for Div {
         self.height_constraint
     }
 
+    fn content_height(&self, width: u16) -> Option<u16> {
+        if let Constraint::Length(height) = self.height_constraint {
+            return Some(height);
+        }
+        let block = Block::default().borders(self.borders).padding(self.padding);
+        let outer = Rect::new(0, 0, width, u16::MAX / 2);
+        let inner = block.inner(outer);
+        let chrome = outer.height - inner.height + self.margin * 2;
+        let width = inner.width.saturating_sub(self.margin * 2);
+        let gaps = self.gap.saturating_mul(self.children.len().saturating_sub(1) as u16);
+        let content = if self.direction == Direction::Vertical {
+            self.children.iter().try_fold(gaps, |total, child| Some(total.saturating_add(child.content_height(width)?)))?
+        } else {
+            // Each child is as tall as it needs at the width the row gives it
+            let constraints: Vec<Constraint> = self.children.iter().map(|c| c.width()).collect();
+            let chunks = Layout::horizontal(constraints).spacing(self.gap).split(Rect::new(0, 0, width, 1));
+            self.children.iter().zip(chunks.iter()).try_fold(0, |tallest: u16, (child, chunk)| {
+                Some(tallest.max(child.content_height(chunk.width)?))
+            })?
+        };
+        Some(content.saturating_add(chrome))
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(handler) = &self.on_scroll {
+            scroll::record(area, Arc::clone(handler));
+        }
+        if let Some(handler) = &self.on_click {
+            click::record(area, Arc::clone(handler));
+        }
+
+        if self.clear {
+            frame.render_widget(Clear, area);
+        }
+
         // 1. Render Block (background, borders)
+        let border_style = self.border_style.unwrap_or_else(|| {
+            // Reset would override a color inherited from `fg`, so only apply real theme colors
+            match crate::theme::current().border {
+                Color::Reset => Style::default(),
+                color => Style::default().fg(color),
+            }
+        });
         let block = Block::default()
             .style(self.style)
             .borders(self.borders)
-            .border_style(self.border_style)
+            .border_style(border_style)
             .border_type(self.border_type)
             .padding(self.padding);
         
@@ -214,26 +486,91 @@ impl Element for Div {
         // Ratatui Layout has margin() which shrinks the area BEFORE splitting.
         // So we should apply margin to the layout.
         
+        // Content-sized children are measured against the width they will get
+        let child_width = inner_area.width.saturating_sub(self.margin * 2);
         let constraints: Vec<Constraint> = self.children.iter().map(|c| {
             if self.direction == Direction::Vertical {
-                c.height()
+                c.measure_height(child_width).map_or_else(|| c.height(), Constraint::Length)
+            } else if self.justify.is_some() {
+                c.measure_width().map_or_else(|| c.width(), Constraint::Length)
             } else {
                 c.width()
             }
         }).collect();
 
-        let layout = Layout::default()
+        let mut layout = Layout::default()
             .direction(self.direction)
             .margin(self.margin)
+            .spacing(self.gap)
             .constraints(constraints);
+        if let Some(justify) = self.justify {
+            layout = layout.flex(justify);
+        }
 
-        let chunks = layout.split(inner_area);
+        let mut chunks = layout.split(inner_area).to_vec();
+        if let Some(items) = self.items {
+            for (chunk, child) in chunks.iter_mut().zip(&self.children) {
+                *chunk = align_across(child.as_ref(), *chunk, self.direction, items);
+            }
+        }
 
         // 3. Render Children
         for (i, child) in self.children.iter_mut().enumerate() {
             if i < chunks.len() {
+                if let Some(key) = self.keys[i] {
+                    key::record(key, chunks[i]);
+                }
                 child.render(frame, chunks[i]);
             }
         }
     }
 }
+
+/// Shrink a child's `chunk` to the child's size across the layout direction,
+/// placed as `items` says.
+fn align_across(child: &dyn Element, chunk: Rect, direction: Direction, items: Flex) -> Rect {
+    if direction == Direction::Vertical {
+        let width = child.measure_width().map_or_else(|| child.width(), Constraint::Length);
+        Layout::horizontal([width]).flex(items).split(chunk)[0]
+    } else {
+        let height = child.measure_height(chunk.width).map_or_else(|| child.height(), Constraint::Length);
+        Layout::vertical([height]).flex(items).split(chunk)[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::text;
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn draw(mut page: Div, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| page.render(frame, frame.area())).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    #[test]
+    fn gaps_go_between_children_only() {
+        let column = div().gap(1).child(text("a")).child(text("b")).child(text("c"));
+        assert_eq!(draw(column, 3, 6), ["a", "", "b", "", "c", ""]);
+        let row = div().flex_row().gap(2).child(text("a").w(1)).child(text("b").w(1));
+        assert_eq!(draw(row, 5, 1), ["a  b"]);
+
+        assert_eq!(draw(div().gap(3).child(text("x")), 2, 2), draw(div().child(text("x")), 2, 2));
+        // More gap than space draws what fits instead of panicking
+        draw(div().gap(u16::MAX).child(text("a")).child(text("b")), 3, 2);
+    }
+
+    #[test]
+    fn children_are_justified_along_and_aligned_across_the_direction() {
+        let menu = || div().child(text("Play")).child(text("Quit"));
+        assert_eq!(draw(menu().justify_center().items_center(), 6, 4), ["", " Play", " Quit", ""]);
+        assert_eq!(draw(menu().justify_end().items_end(), 6, 3), ["", "  Play", "  Quit"]);
+        assert_eq!(draw(menu().justify_between(), 6, 4), ["Play", "", "", "Quit"]);
+
+        let toolbar = div().flex_row().justify_between().child(text("<")).child(text("title")).child(text(">"));
+        assert_eq!(draw(toolbar, 11, 1), ["<  title  >"]);
+    }
+}
@@ -0,0 +1,72 @@
+use ratatui::prelude::*;
+use crate::element::{Element, IntoElement};
+
+/// An element that draws nothing and takes no space.
+///
+/// It is what `None` turns into, so optional children can be passed directly:
+///
+/// ```ignore
+/// div()
+///     .child(header)
+///     .child(self.error.as_ref().map(|error| text(error.clone()).fg(theme.error)))
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Empty;
+
+pub fn empty() -> Empty {
+    Empty
+}
+
+impl Element for Empty {
+    fn width(&self) -> Constraint {
+        Constraint::Length(0)
+    }
+
+    fn height(&self) -> Constraint {
+        Constraint::Length(0)
+    }
+
+    fn measure_height(&self, _width: u16) -> Option<u16> {
+        Some(0)
+    }
+
+    fn measure_width(&self) -> Option<u16> {
+        Some(0)
+    }
+
+    fn render(&mut self, _frame: &mut Frame, _area: Rect) {}
+}
+
+impl<E> IntoElement for Option<E>
+where
+    E: IntoElement + 'static,
+    E::Element: 'static,
+{
+    type Element = Box<dyn Element>;
+    fn into_element(self) -> Self::Element {
+        match self {
+            Some(element) => Box::new(element.into_element()),
+            None => Box::new(Empty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::{div, text, Element};
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn none_children_take_no_space() {
+        let banner: Option<&str> = None;
+        let mut element = div()
+            .child(banner)
+            .child(text("first").h(1))
+            .child(Some("second").map(|s| text(s).h(1)))
+            .child(None::<crate::element::Div>);
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        terminal.draw(|frame| element.render(frame, frame.area())).unwrap();
+        assert_eq!(buffer_lines(terminal.backend().buffer()), ["first", "second", ""]);
+    }
+}
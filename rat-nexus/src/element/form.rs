@@ -0,0 +1,507 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, BorderType, Paragraph};
+use crate::element::Element;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+/// A single input of a `Form`.
+pub trait FormField: Send + Sync {
+    /// Key of the field in the submitted values.
+    fn name(&self) -> &str;
+
+    /// Label shown above the input.
+    fn label(&self) -> &str;
+
+    /// The current value.
+    fn value(&self) -> String;
+
+    /// How the value is shown, e.g. masked for passwords.
+    fn display(&self) -> String {
+        self.value()
+    }
+
+    /// Handle a key while the field has focus. Returns true if the key was consumed.
+    fn handle_key(&mut self, key: &KeyEvent) -> bool;
+
+    /// Handle pasted text while the field has focus. Returns true if it was inserted.
+    fn handle_paste(&mut self, text: &str) -> bool {
+        let _ = text;
+        false
+    }
+
+    /// Position of the cursor in `display`, in characters.
+    fn cursor(&self) -> usize {
+        self.display().chars().count()
+    }
+
+    /// Check the current value, returning a message describing the problem.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Clean up pasted text before inserting it into an input.
+///
+/// Line endings are normalized to `\n` and control characters other than tabs
+/// and line breaks are dropped. With `single_line`, trailing line breaks are
+/// removed and the remaining ones become spaces, as do tabs.
+pub fn sanitize_paste(text: &str, single_line: bool) -> String {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let text = if single_line { text.trim_end_matches('\n') } else { text.as_str() };
+    text.chars()
+        .filter_map(|c| match c {
+            '\n' | '\t' if single_line => Some(' '),
+            '\n' | '\t' => Some(c),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// Validator of a `TextField`'s value.
+type Validator = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// A single-line text input.
+///
+/// # Example
+/// ```ignore
+/// TextField::new("port", "Port")
+///     .value("8080")
+///     .required()
+///     .validate(|v| v.parse::<u16>().map(|_| ()).map_err(|_| "Not a port number".into()))
+/// ```
+pub struct TextField {
+    name: String,
+    label: String,
+    value: String,
+    /// Cursor position in characters, `0..=value.chars().count()`.
+    cursor: usize,
+    masked: bool,
+    validators: Vec<Validator>,
+}
+
+impl TextField {
+    pub fn new(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            value: String::new(),
+            cursor: 0,
+            masked: false,
+            validators: Vec::new(),
+        }
+    }
+
+    /// Initial value. The cursor is placed at its end.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = value.into();
+        self.cursor = self.value.chars().count();
+        self
+    }
+
+    /// Show the value as `*`s, for passwords.
+    pub fn masked(mut self) -> Self {
+        self.masked = true;
+        self
+    }
+
+    /// Reject an empty (or whitespace-only) value.
+    pub fn required(self) -> Self {
+        self.validate(|value| {
+            if value.trim().is_empty() {
+                Err("Required".to_string())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Add a validator. Validators run in the order they were added; the first error wins.
+    pub fn validate<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Insert text at the cursor and move the cursor past it.
+    ///
+    /// The text is inserted as is; run pasted text through `sanitize_paste` first.
+    pub fn insert_str(&mut self, text: &str) {
+        let at = self.byte_offset(self.cursor);
+        self.value.insert_str(at, text);
+        self.cursor += text.chars().count();
+    }
+
+    /// Byte index of the character at `cursor`, so edits never split a character.
+    fn byte_offset(&self, cursor: usize) -> usize {
+        self.value.char_indices().nth(cursor).map_or(self.value.len(), |(i, _)| i)
+    }
+}
+
+impl FormField for TextField {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn value(&self) -> String {
+        self.value.clone()
+    }
+
+    fn display(&self) -> String {
+        if self.masked {
+            "*".repeat(self.value.chars().count())
+        } else {
+            self.value.clone()
+        }
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.insert_str(c.encode_utf8(&mut [0; 4]));
+                true
+            }
+            KeyCode::Backspace => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.value.remove(self.byte_offset(self.cursor));
+                }
+                true
+            }
+            KeyCode::Delete => {
+                if self.cursor < self.value.chars().count() {
+                    self.value.remove(self.byte_offset(self.cursor));
+                }
+                true
+            }
+            KeyCode::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                true
+            }
+            KeyCode::Right => {
+                self.cursor = (self.cursor + 1).min(self.value.chars().count());
+                true
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                true
+            }
+            KeyCode::End => {
+                self.cursor = self.value.chars().count();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> bool {
+        self.insert_str(&sanitize_paste(text, true));
+        true
+    }
+
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        self.validators.iter().try_for_each(|validator| validator(&self.value))
+    }
+}
+
+/// A validation failure of one field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Name of the field.
+    pub field: String,
+    pub message: String,
+}
+
+/// Outcome of submitting a form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormEvent {
+    /// Every field was valid. Values are keyed by field name.
+    Submitted(HashMap<String, String>),
+    /// At least one field failed validation. Errors are in field order.
+    Invalid(Vec<FieldError>),
+}
+
+/// An ordered set of fields with focus traversal and validation, kept by the component.
+///
+/// Tab and BackTab (Shift+Tab) move the focus, Enter submits, other keys go to
+/// the focused field. Errors are shown under their field until it is edited.
+///
+/// # Example
+/// ```ignore
+/// let form = Form::new()
+///     .field(TextField::new("user", "Username").required())
+///     .field(TextField::new("password", "Password").masked().required());
+///
+/// fn captures_key(&self, key: &KeyEvent) -> bool {
+///     self.form.captures_key(key)
+/// }
+///
+/// fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+///     if let Event::Key(key) = event {
+///         if let Some(FormEvent::Submitted(values)) = self.form.handle_key(&key) {
+///             return Some(Action::custom(Login(values)));
+///         }
+///     }
+///     None
+/// }
+///
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     self.form.view()
+/// }
+/// ```
+#[derive(Default)]
+pub struct Form {
+    fields: Vec<Box<dyn FormField>>,
+    focus: usize,
+    /// Current error message of each field, by index.
+    errors: HashMap<usize, String>,
+}
+
+impl Form {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a field.
+    pub fn field(mut self, field: impl FormField + 'static) -> Self {
+        self.fields.push(Box::new(field));
+        self
+    }
+
+    /// Index of the focused field.
+    pub fn focus(&self) -> usize {
+        self.focus
+    }
+
+    /// Focus the field at `index` (clamped to the last field).
+    pub fn set_focus(&mut self, index: usize) {
+        self.focus = index.min(self.fields.len().saturating_sub(1));
+    }
+
+    pub fn focus_next(&mut self) {
+        if !self.fields.is_empty() {
+            self.focus = (self.focus + 1) % self.fields.len();
+        }
+    }
+
+    pub fn focus_prev(&mut self) {
+        if !self.fields.is_empty() {
+            self.focus = (self.focus + self.fields.len() - 1) % self.fields.len();
+        }
+    }
+
+    /// Current values, keyed by field name.
+    pub fn values(&self) -> HashMap<String, String> {
+        self.fields
+            .iter()
+            .map(|field| (field.name().to_string(), field.value()))
+            .collect()
+    }
+
+    /// Validate every field and return the outcome. Errors are kept for display
+    /// and the first invalid field receives focus.
+    pub fn submit(&mut self) -> FormEvent {
+        self.errors = self.fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, field)| field.validate().err().map(|message| (i, message)))
+            .collect();
+
+        if self.errors.is_empty() {
+            return FormEvent::Submitted(self.values());
+        }
+
+        let mut invalid: Vec<usize> = self.errors.keys().copied().collect();
+        invalid.sort_unstable();
+        self.focus = invalid[0];
+        FormEvent::Invalid(
+            invalid
+                .into_iter()
+                .map(|i| FieldError {
+                    field: self.fields[i].name().to_string(),
+                    message: self.errors[&i].clone(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether the form wants `key` even if it is bound globally, i.e. it is text
+    /// for the focused field. Forward this from `Component::captures_key`.
+    pub fn captures_key(&self, key: &KeyEvent) -> bool {
+        !self.fields.is_empty()
+            && matches!(key.code, KeyCode::Char(_))
+            && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+
+    /// Handle a key press. Returns the outcome when the key submitted the form.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<FormEvent> {
+        match key.code {
+            KeyCode::Tab => self.focus_next(),
+            KeyCode::BackTab => self.focus_prev(),
+            KeyCode::Enter => return Some(self.submit()),
+            _ => {
+                if let Some(field) = self.fields.get_mut(self.focus) {
+                    if field.handle_key(key) {
+                        self.errors.remove(&self.focus);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Insert pasted text into the focused field. Forward `Event::Paste` here.
+    pub fn handle_paste(&mut self, text: &str) {
+        if let Some(field) = self.fields.get_mut(self.focus) {
+            if field.handle_paste(text) {
+                self.errors.remove(&self.focus);
+            }
+        }
+    }
+
+    /// Build the element showing the fields, the focus and any errors.
+    pub fn view(&self) -> FormView {
+        FormView {
+            fields: self.fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| FieldView {
+                    label: field.label().to_string(),
+                    display: field.display(),
+                    cursor: field.cursor(),
+                    error: self.errors.get(&i).cloned(),
+                    focused: i == self.focus,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Snapshot of one field for rendering.
+struct FieldView {
+    label: String,
+    display: String,
+    /// Cursor position in `display`, in characters.
+    cursor: usize,
+    error: Option<String>,
+    focused: bool,
+}
+
+/// Element rendering a `Form`: one bordered input per field, stacked.
+pub struct FormView {
+    fields: Vec<FieldView>,
+}
+
+impl FormView {
+    /// Rows each field takes: the bordered input line.
+    const FIELD_HEIGHT: u16 = 3;
+}
+
+impl Element for FormView {
+    fn height(&self) -> Constraint {
+        Constraint::Length(self.fields.len() as u16 * Self::FIELD_HEIGHT)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = crate::theme::current();
+        let rows = Layout::vertical(vec![Constraint::Length(Self::FIELD_HEIGHT); self.fields.len()]).split(area);
+
+        for (field, &row) in self.fields.iter().zip(rows.iter()) {
+            let border = match (&field.error, field.focused) {
+                (Some(_), _) => theme.error,
+                (None, true) => theme.primary,
+                (None, false) => theme.border,
+            };
+            let mut block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(if field.focused { BorderType::Thick } else { BorderType::Rounded })
+                .border_style(Style::default().fg(border))
+                .title(format!(" {} ", field.label));
+            if let Some(error) = &field.error {
+                block = block.title_bottom(Line::styled(format!(" {error} "), Style::default().fg(theme.error)));
+            }
+
+            let inner = block.inner(row);
+            frame.render_widget(Paragraph::new(field.display.clone()).style(Style::default().fg(theme.text)).block(block), row);
+            if field.focused && inner.width > 0 && inner.height > 0 {
+                let x = inner.x + (field.cursor as u16).min(inner.width - 1);
+                frame.set_cursor_position((x, inner.y));
+                crate::terminal::request_cursor_shape(crate::terminal::CursorShape::Bar);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(form: &mut Form, text: &str) {
+        for c in text.chars() {
+            form.handle_key(&key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn submit_validates_fields_and_focuses_the_first_error() {
+        let mut form = Form::new()
+            .field(TextField::new("user", "Username").required())
+            .field(TextField::new("port", "Port").validate(|v| {
+                v.parse::<u16>().map(|_| ()).map_err(|_| "Not a port".to_string())
+            }));
+
+        type_text(&mut form, "ann");
+        form.handle_key(&key(KeyCode::Tab));
+        type_text(&mut form, "http");
+        form.handle_key(&key(KeyCode::BackTab));
+        form.handle_key(&key(KeyCode::Backspace));
+        assert_eq!(form.focus(), 0);
+
+        let errors = match form.handle_key(&key(KeyCode::Enter)) {
+            Some(FormEvent::Invalid(errors)) => errors,
+            other => panic!("expected errors, got {other:?}"),
+        };
+        assert_eq!(errors, [FieldError { field: "port".into(), message: "Not a port".into() }]);
+        assert_eq!(form.focus(), 1);
+
+        for _ in 0..4 {
+            form.handle_key(&key(KeyCode::Backspace));
+        }
+        type_text(&mut form, "80");
+        let Some(FormEvent::Submitted(values)) = form.handle_key(&key(KeyCode::Enter)) else {
+            panic!("expected the form to submit");
+        };
+        assert_eq!(values["user"], "an");
+        assert_eq!(values["port"], "80");
+    }
+
+    #[test]
+    fn pastes_are_sanitized_and_inserted_at_the_cursor() {
+        assert_eq!(sanitize_paste("error\r\ntimeout\n", true), "error timeout");
+        assert_eq!(sanitize_paste("a\tb\x1b[0m\r\n", false), "a\tb[0m\n");
+
+        let mut field = TextField::new("q", "Query").value("héé");
+        field.handle_key(&key(KeyCode::Left));
+        assert!(field.handle_paste("日本\n"));
+        assert_eq!(FormField::value(&field), "hé日本é");
+        assert_eq!(FormField::cursor(&field), 4);
+        field.handle_key(&key(KeyCode::Backspace));
+        field.handle_key(&key(KeyCode::Home));
+        field.handle_key(&key(KeyCode::Delete));
+        assert_eq!(FormField::value(&field), "é日é");
+    }
+}
@@ -0,0 +1,181 @@
+use ratatui::prelude::*;
+use crate::element::{Element, IntoElement};
+
+/// Children laid out in columns, filled row by row.
+///
+/// Each column gets one of the constraints passed to `grid`. A row is as tall
+/// as its tallest child if all of them have a fixed height (`h`), otherwise
+/// the rows share the leftover space. Wrap a child in `cell` to let it span
+/// several columns; a cell that doesn't fit in the rest of a row starts the
+/// next one, and columns left over at the end stay blank.
+///
+/// # Example
+/// ```ignore
+/// grid([Constraint::Fill(1), Constraint::Fill(1), Constraint::Length(30)])
+///     .row_gap(1)
+///     .col_gap(2)
+///     .child(cell(cpu_chart).col_span(2))
+///     .child(sidebar)
+///     .child(network_in)
+///     .child(network_out)
+/// ```
+pub struct Grid {
+    columns: Vec<Constraint>,
+    cells: Vec<GridCell>,
+    row_gap: u16,
+    col_gap: u16,
+}
+
+/// A grid child with placement options, see `cell`.
+pub struct GridCell {
+    child: Box<dyn Element>,
+    col_span: u16,
+}
+
+pub fn grid(columns: impl IntoIterator<Item = Constraint>) -> Grid {
+    Grid {
+        columns: columns.into_iter().collect(),
+        cells: Vec::new(),
+        row_gap: 0,
+        col_gap: 0,
+    }
+}
+
+/// Wrap a grid child to set its placement, e.g. `cell(chart).col_span(2)`.
+pub fn cell(child: impl IntoElement + 'static) -> GridCell {
+    GridCell { child: Box::new(child.into_element()), col_span: 1 }
+}
+
+impl GridCell {
+    /// Cover `span` columns instead of one, at most the whole row.
+    pub fn col_span(mut self, span: u16) -> Self {
+        self.col_span = span.max(1);
+        self
+    }
+}
+
+impl<T: IntoElement + 'static> From<T> for GridCell {
+    fn from(child: T) -> Self {
+        cell(child)
+    }
+}
+
+impl Grid {
+    /// Blank rows between the rows of the grid.
+    pub fn row_gap(mut self, gap: u16) -> Self {
+        self.row_gap = gap;
+        self
+    }
+
+    /// Blank columns between the columns of the grid.
+    pub fn col_gap(mut self, gap: u16) -> Self {
+        self.col_gap = gap;
+        self
+    }
+
+    /// Add a child in the next free cell; pass a `cell` to span columns.
+    pub fn child(mut self, child: impl Into<GridCell>) -> Self {
+        self.cells.push(child.into());
+        self
+    }
+
+    /// Add several children, e.g. `panels.iter().map(|p| panel(p))`.
+    pub fn children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<GridCell>,
+    {
+        self.cells.extend(children.into_iter().map(Into::into));
+        self
+    }
+
+    /// The column and span of each cell, grouped by row.
+    fn placements(&self) -> Vec<Vec<(usize, usize)>> {
+        let columns = self.columns.len();
+        let mut rows: Vec<Vec<(usize, usize)>> = Vec::new();
+        let mut column = columns;
+        for cell in &self.cells {
+            let span = (cell.col_span as usize).min(columns);
+            if column + span > columns {
+                rows.push(Vec::new());
+                column = 0;
+            }
+            rows.last_mut().unwrap().push((column, span));
+            column += span;
+        }
+        rows
+    }
+
+    /// Height of each row: the tallest child if all have a fixed height.
+    fn row_constraints(&self, rows: &[Vec<(usize, usize)>]) -> Vec<Constraint> {
+        let mut cells = self.cells.iter();
+        rows.iter()
+            .map(|row| {
+                let tallest = cells.by_ref().take(row.len()).try_fold(0, |tallest, cell| match cell.child.height() {
+                    Constraint::Length(height) => Some(tallest.max(height)),
+                    _ => None,
+                });
+                tallest.map_or(Constraint::Fill(1), Constraint::Length)
+            })
+            .collect()
+    }
+}
+
+impl Element for Grid {
+    fn height(&self) -> Constraint {
+        let rows = self.placements();
+        let gaps = self.row_gap.saturating_mul(rows.len().saturating_sub(1) as u16);
+        self.row_constraints(&rows)
+            .into_iter()
+            .try_fold(gaps, |total, row| match row {
+                Constraint::Length(height) => Some(total.saturating_add(height)),
+                _ => None,
+            })
+            .map_or(Constraint::Min(0), Constraint::Length)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if self.columns.is_empty() {
+            return;
+        }
+        let rows = self.placements();
+        let row_areas = Layout::vertical(self.row_constraints(&rows)).spacing(self.row_gap).split(area);
+        let mut cells = self.cells.iter_mut();
+        for (row, row_area) in rows.iter().zip(row_areas.iter()) {
+            let columns = Layout::horizontal(self.columns.iter().copied()).spacing(self.col_gap).split(*row_area);
+            for (&(column, span), cell) in row.iter().zip(cells.by_ref()) {
+                // A spanning cell also covers the gaps between its columns
+                let cell_area = columns[column].union(columns[column + span - 1]);
+                cell.child.render(frame, cell_area);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::text;
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn children_fill_rows_with_spans_and_a_blank_remainder() {
+        let mut root = grid([Constraint::Length(3), Constraint::Length(3), Constraint::Length(3)])
+            .row_gap(1)
+            .col_gap(1)
+            .child(cell(text("wide-cell").h(1)).col_span(2))
+            .child(text("c").h(1))
+            .child(text("d").h(1))
+            .child(cell(text("too-wide-to-fit").h(1)).col_span(3))
+            .child(text("f").h(1))
+            .child(cell(text("gg").h(1)).col_span(5));
+        assert_eq!(root.height(), Constraint::Length(9));
+        let mut terminal = Terminal::new(TestBackend::new(11, 9)).unwrap();
+        terminal.draw(|frame| root.render(frame, frame.area())).unwrap();
+        assert_eq!(
+            buffer_lines(terminal.backend().buffer()),
+            ["wide-ce c", "", "d", "", "too-wide-to", "", "f", "", "gg"]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, BorderType, Clear, Paragraph};
+use crate::element::{centered_rect, Element, IntoElement};
+use crate::keymap::KeyBindings;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::{Arc, Mutex};
+
+/// A centered modal listing key bindings, optionally drawn over a base element.
+///
+/// The list is split into pages when it doesn't fit the screen; use
+/// `page_count` to find out how many pages a given area yields.
+pub struct HelpOverlay {
+    bindings: KeyBindings,
+    page: usize,
+    title: String,
+    base: Option<Box<dyn Element>>,
+    /// Where to record the area drawn into, see `HelpState::overlay`.
+    drawn: Option<Arc<Mutex<Rect>>>,
+}
+
+pub fn help_overlay(bindings: KeyBindings) -> HelpOverlay {
+    HelpOverlay::new(bindings)
+}
+
+impl HelpOverlay {
+    pub fn new(bindings: KeyBindings) -> Self {
+        Self {
+            bindings,
+            page: 0,
+            title: " Help ".to_string(),
+            base: None,
+            drawn: None,
+        }
+    }
+
+    /// Select the page to show. Out of range pages are clamped to the last page.
+    pub fn page(mut self, page: usize) -> Self {
+        self.page = page;
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Render the overlay on top of the given element.
+    pub fn over(mut self, base: impl IntoElement + 'static) -> Self {
+        self.base = Some(Box::new(base.into_element()));
+        self
+    }
+
+    /// Number of pages needed to show all bindings inside `area`.
+    pub fn page_count(&self, area: Rect) -> usize {
+        let per_page = Self::per_page(Self::modal_area(area, self.bindings.len()));
+        self.bindings.len().div_ceil(per_page).max(1)
+    }
+
+    fn modal_area(area: Rect, len: usize) -> Rect {
+        // The page indicator lives in the bottom border, so only the borders add height.
+        let width = area.width.saturating_sub(4).min(60);
+        let height = area.height.saturating_sub(2).min(len.max(1) as u16 + 2);
+        centered_rect(area, width, height)
+    }
+
+    fn per_page(modal: Rect) -> usize {
+        (modal.height.saturating_sub(2) as usize).max(1)
+    }
+}
+
+impl Element for HelpOverlay {
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(base) = self.base.as_mut() {
+            base.render(frame, area);
+        }
+        if let Some(drawn) = &self.drawn {
+            *drawn.lock().unwrap_or_else(|e| e.into_inner()) = area;
+        }
+
+        let modal = Self::modal_area(area, self.bindings.len());
+        let per_page = Self::per_page(modal);
+        let pages = self.page_count(area);
+        let page = self.page.min(pages - 1);
+
+        let theme = crate::theme::current();
+        let label_width = self.bindings.iter()
+            .map(|b| b.label().chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines: Vec<Line> = self.bindings.iter()
+            .skip(page * per_page)
+            .take(per_page)
+            .map(|b| Line::from(vec![
+                Span::styled(format!(" {:>width$} ", b.label(), width = label_width), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                Span::raw(format!(" {}", b.description)),
+            ]))
+            .collect();
+
+        if self.bindings.is_empty() {
+            lines.push(Line::styled(" No key bindings", Style::default().fg(theme.muted)));
+        }
+
+        let block = Block::default()
+            .title(self.title.clone())
+            .title_bottom(Line::from(format!(" {}/{} │ ←/→ Page │ ? Close ", page + 1, pages)).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.primary));
+
+        frame.render_widget(Clear, modal);
+        frame.render_widget(Paragraph::new(lines).block(block), modal);
+    }
+}
+
+/// Open/closed and page state for the help overlay, kept by the root component.
+///
+/// Paging follows the area the overlay was last drawn in, so it matches what
+/// is on screen even when the overlay isn't full-screen.
+#[derive(Debug, Clone, Default)]
+pub struct HelpState {
+    open: bool,
+    page: usize,
+    /// Shared with the overlays built by `overlay`, which record it when drawn.
+    area: Arc<Mutex<Rect>>,
+}
+
+impl HelpState {
+    /// The key that toggles the overlay.
+    pub const TOGGLE_KEY: KeyCode = KeyCode::Char('?');
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.page = 0;
+    }
+
+    /// Handle a key press. Returns true if the key was consumed by the overlay.
+    /// While open, all keys are consumed so the page underneath doesn't react.
+    pub fn handle_key(&mut self, key: &KeyEvent, bindings: &KeyBindings) -> bool {
+        if !self.open {
+            if key.code == Self::TOGGLE_KEY {
+                self.toggle();
+                return true;
+            }
+            return false;
+        }
+
+        match key.code {
+            Self::TOGGLE_KEY | KeyCode::Esc => self.toggle(),
+            KeyCode::Right | KeyCode::PageDown => {
+                let area = *self.area.lock().unwrap_or_else(|e| e.into_inner());
+                let pages = help_overlay(bindings.clone()).page_count(area);
+                self.page = (self.page + 1).min(pages - 1);
+            }
+            KeyCode::Left | KeyCode::PageUp => self.page = self.page.saturating_sub(1),
+            _ => {}
+        }
+        true
+    }
+
+    /// Build the overlay element for the current page. Pass the same bindings
+    /// to `handle_key`, so it pages through what is shown.
+    pub fn overlay(&self, bindings: KeyBindings) -> HelpOverlay {
+        HelpOverlay {
+            drawn: Some(Arc::clone(&self.area)),
+            ..help_overlay(bindings).page(self.page)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn pages_follow_the_area_the_overlay_was_drawn_in() {
+        let bindings = (0..6).fold(KeyBindings::new(), |b, i| b.bind(KeyCode::F(i), format!("Action {i}")));
+        let mut help = HelpState::new();
+        let right = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+        assert!(help.handle_key(&KeyEvent::new(HelpState::TOGGLE_KEY, KeyModifiers::NONE), &bindings));
+
+        // Three bindings fit per page in a 7 row screen, so there are two pages
+        let mut terminal = Terminal::new(TestBackend::new(40, 7)).unwrap();
+        terminal.draw(|frame| help.overlay(bindings.clone()).render(frame, frame.area())).unwrap();
+        for _ in 0..3 {
+            assert!(help.handle_key(&right, &bindings));
+        }
+        terminal.draw(|frame| help.overlay(bindings.clone()).render(frame, frame.area())).unwrap();
+        let bottom = crate::testing::buffer_lines(terminal.backend().buffer())[5].clone();
+        assert!(bottom.contains(" 2/2 "), "{bottom}");
+    }
+}
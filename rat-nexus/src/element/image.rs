@@ -0,0 +1,544 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, Cursor};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use ::image::{imageops::FilterType, DynamicImage, ImageFormat, RgbaImage};
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use crate::element::Element;
+
+/// Decoded images loaded from disk, so a path isn't decoded again every frame.
+static PATH_CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<DynamicImage>>>> = OnceLock::new();
+
+/// Images recently prepared for an area, most recently drawn last, so an image
+/// redrawn at the same size isn't resized and encoded again every frame.
+static PREPARED: OnceLock<Mutex<Vec<PreparedEntry>>> = OnceLock::new();
+
+/// Number of prepared images kept in `PREPARED`.
+const PREPARED_CAPACITY: usize = 16;
+
+/// Graphics protocol used to draw an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// The kitty graphics protocol (kitty, WezTerm, Ghostty).
+    Kitty,
+    /// DEC sixel graphics (foot, mlterm, xterm with sixel enabled).
+    Sixel,
+    /// Unicode half blocks with true-color foreground/background. Works everywhere.
+    HalfBlocks,
+}
+
+impl ImageProtocol {
+    /// Guess the best protocol from the environment.
+    /// Falls back to `HalfBlocks` when no graphics protocol is advertised.
+    pub fn detect() -> Self {
+        let var = |name: &str| std::env::var(name).unwrap_or_default().to_lowercase();
+        let term = var("TERM");
+        let program = var("TERM_PROGRAM");
+
+        if std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || program == "wezterm"
+            || program == "ghostty"
+        {
+            ImageProtocol::Kitty
+        } else if term.contains("sixel") || term.starts_with("foot") || term.contains("mlterm") || program == "mlterm" {
+            ImageProtocol::Sixel
+        } else {
+            ImageProtocol::HalfBlocks
+        }
+    }
+}
+
+/// Where an image comes from.
+#[derive(Clone)]
+pub enum ImageSource {
+    Path(PathBuf),
+    Bytes(Arc<[u8]>),
+    Decoded(Arc<DynamicImage>),
+}
+
+impl From<&str> for ImageSource {
+    fn from(path: &str) -> Self {
+        ImageSource::Path(PathBuf::from(path))
+    }
+}
+
+impl From<&Path> for ImageSource {
+    fn from(path: &Path) -> Self {
+        ImageSource::Path(path.to_path_buf())
+    }
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(path: PathBuf) -> Self {
+        ImageSource::Path(path)
+    }
+}
+
+impl From<Vec<u8>> for ImageSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        ImageSource::Bytes(bytes.into())
+    }
+}
+
+impl From<&[u8]> for ImageSource {
+    fn from(bytes: &[u8]) -> Self {
+        ImageSource::Bytes(bytes.into())
+    }
+}
+
+impl From<DynamicImage> for ImageSource {
+    fn from(image: DynamicImage) -> Self {
+        ImageSource::Decoded(Arc::new(image))
+    }
+}
+
+impl From<Arc<DynamicImage>> for ImageSource {
+    fn from(image: Arc<DynamicImage>) -> Self {
+        ImageSource::Decoded(image)
+    }
+}
+
+impl ImageSource {
+    fn load(&self) -> Result<Arc<DynamicImage>, String> {
+        match self {
+            ImageSource::Decoded(image) => Ok(Arc::clone(image)),
+            ImageSource::Bytes(bytes) => ::image::load_from_memory(bytes)
+                .map(Arc::new)
+                .map_err(|e| e.to_string()),
+            ImageSource::Path(path) => {
+                let cache = PATH_CACHE.get_or_init(Default::default);
+                if let Some(image) = cache.lock().ok().and_then(|c| c.get(path).cloned()) {
+                    return Ok(image);
+                }
+                let image = Arc::new(::image::open(path).map_err(|e| e.to_string())?);
+                if let Ok(mut cache) = cache.lock() {
+                    cache.insert(path.clone(), Arc::clone(&image));
+                }
+                Ok(image)
+            }
+        }
+    }
+
+    fn id(&self) -> u32 {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            ImageSource::Path(path) => path.hash(&mut hasher),
+            ImageSource::Bytes(bytes) => bytes.hash(&mut hasher),
+            ImageSource::Decoded(image) => (Arc::as_ptr(image) as usize).hash(&mut hasher),
+        }
+        // Kitty image ids must be non-zero
+        (hasher.finish() as u32).max(1)
+    }
+
+    /// Whether both sources are the same image, without decoding them.
+    fn same(&self, other: &ImageSource) -> bool {
+        match (self, other) {
+            (ImageSource::Path(a), ImageSource::Path(b)) => a == b,
+            (ImageSource::Bytes(a), ImageSource::Bytes(b)) => Arc::ptr_eq(a, b) || a == b,
+            (ImageSource::Decoded(a), ImageSource::Decoded(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// An image resized and encoded for one area.
+enum Prepared {
+    /// A kitty or sixel escape sequence covering `cols` x `rows` cells.
+    Sequence { sequence: String, cols: u16, rows: u16 },
+    /// Pixels for `render_half_blocks`, two per cell vertically.
+    HalfBlocks(RgbaImage),
+}
+
+struct PreparedEntry {
+    /// Kept so a `Decoded` image's address can't be reused by another image while cached.
+    source: ImageSource,
+    protocol: ImageProtocol,
+    size: (u16, u16),
+    cell: (u32, u32),
+    prepared: Arc<Prepared>,
+}
+
+/// An image scaled to fit its area while keeping its aspect ratio.
+pub struct Image {
+    source: ImageSource,
+    protocol: Option<ImageProtocol>,
+    width_constraint: Constraint,
+    height_constraint: Constraint,
+}
+
+pub fn image(source: impl Into<ImageSource>) -> Image {
+    Image::new(source)
+}
+
+impl Image {
+    pub fn new(source: impl Into<ImageSource>) -> Self {
+        Self {
+            source: source.into(),
+            protocol: None,
+            width_constraint: Constraint::Min(0),
+            height_constraint: Constraint::Min(0),
+        }
+    }
+
+    /// Force a protocol instead of detecting it from the environment.
+    pub fn protocol(mut self, protocol: ImageProtocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    pub fn w_full(mut self) -> Self {
+        self.width_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn h_full(mut self) -> Self {
+        self.height_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn w(mut self, length: u16) -> Self {
+        self.width_constraint = Constraint::Length(length);
+        self
+    }
+
+    pub fn h(mut self, length: u16) -> Self {
+        self.height_constraint = Constraint::Length(length);
+        self
+    }
+}
+
+impl Element for Image {
+    fn width(&self) -> Constraint {
+        self.width_constraint
+    }
+
+    fn height(&self) -> Constraint {
+        self.height_constraint
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+        let prepared = match self.prepare(area) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                frame.render_widget(Paragraph::new(format!("image error: {}", e)).style(Style::default().fg(Color::Red)), area);
+                return;
+            }
+        };
+
+        match &*prepared {
+            Prepared::HalfBlocks(pixels) => render_half_blocks(pixels, frame.buffer_mut(), area),
+            Prepared::Sequence { sequence, cols, rows } => {
+                place_sequence(frame.buffer_mut(), centered(area, *cols, *rows), sequence);
+            }
+        }
+    }
+}
+
+impl Image {
+    /// The image resized and encoded for `area`, from `PREPARED` when it was drawn
+    /// at this size before.
+    fn prepare(&self, area: Rect) -> Result<Arc<Prepared>, String> {
+        let protocol = self.protocol.unwrap_or_else(ImageProtocol::detect);
+        let cell = match protocol {
+            ImageProtocol::HalfBlocks => (1, 2),
+            _ => cell_pixel_size(),
+        };
+        let size = (area.width, area.height);
+        let cache = PREPARED.get_or_init(Default::default);
+        if let Ok(mut cache) = cache.lock() {
+            let hit = cache.iter().position(|entry| {
+                entry.protocol == protocol && entry.size == size && entry.cell == cell && entry.source.same(&self.source)
+            });
+            if let Some(index) = hit {
+                let entry = cache.remove(index);
+                let prepared = Arc::clone(&entry.prepared);
+                cache.push(entry);
+                return Ok(prepared);
+            }
+        }
+
+        let image = self.source.load()?;
+        let prepared = Arc::new(prepare(&image, self.source.id(), protocol, area, cell));
+        if let Ok(mut cache) = cache.lock() {
+            // Only the latest size of each image is worth keeping
+            cache.retain(|entry| !entry.source.same(&self.source));
+            if cache.len() >= PREPARED_CAPACITY {
+                cache.remove(0);
+            }
+            cache.push(PreparedEntry {
+                source: self.source.clone(),
+                protocol,
+                size,
+                cell,
+                prepared: Arc::clone(&prepared),
+            });
+        }
+        Ok(prepared)
+    }
+}
+
+/// Resize `image` to fit `area` and encode it for `protocol`, falling back to half
+/// blocks when encoding fails.
+fn prepare(image: &DynamicImage, id: u32, protocol: ImageProtocol, area: Rect, (cell_w, cell_h): (u32, u32)) -> Prepared {
+    if protocol != ImageProtocol::HalfBlocks {
+        let target = fit(image.width(), image.height(), area.width as u32 * cell_w, area.height as u32 * cell_h);
+        let cols = (target.0 / cell_w).clamp(1, area.width as u32) as u16;
+        let rows = (target.1 / cell_h).clamp(1, area.height as u32) as u16;
+        let resized = image.resize_exact(target.0.max(1), target.1.max(1), FilterType::Triangle).to_rgba8();
+        let sequence = match protocol {
+            ImageProtocol::Kitty => kitty_sequence(&resized, id, cols, rows),
+            _ => sixel_sequence(&resized),
+        };
+        if let Some(sequence) = sequence {
+            return Prepared::Sequence { sequence, cols, rows };
+        }
+    }
+    let (w, h) = fit(image.width(), image.height(), area.width as u32, area.height as u32 * 2);
+    if w == 0 || h == 0 {
+        return Prepared::HalfBlocks(RgbaImage::new(0, 0));
+    }
+    Prepared::HalfBlocks(image.resize_exact(w, h, FilterType::Triangle).to_rgba8())
+}
+
+/// Scale `(w, h)` to fit inside `(max_w, max_h)` keeping the aspect ratio.
+fn fit(w: u32, h: u32, max_w: u32, max_h: u32) -> (u32, u32) {
+    if w == 0 || h == 0 {
+        return (0, 0);
+    }
+    let scale = (max_w as f64 / w as f64).min(max_h as f64 / h as f64);
+    ((w as f64 * scale) as u32, (h as f64 * scale) as u32)
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Size of a terminal cell in pixels, falling back to a common 8x16 when unknown.
+fn cell_pixel_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns).max(1) as u32,
+            (size.height / size.rows).max(1) as u32,
+        ),
+        _ => (8, 16),
+    }
+}
+
+/// Draw using "▀" cells: the foreground is the top pixel, the background the bottom one.
+fn render_half_blocks(pixels: &RgbaImage, buf: &mut Buffer, area: Rect) {
+    let cols = pixels.width() as u16;
+    let rows = pixels.height().div_ceil(2) as u16;
+    let target = centered(area, cols, rows);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let top = pixels.get_pixel(col as u32, row as u32 * 2);
+            let bottom = pixels.get_pixel_checked(col as u32, row as u32 * 2 + 1).copied();
+            let cell = &mut buf[(target.x + col, target.y + row)];
+            cell.set_symbol("▀").set_fg(Color::Rgb(top[0], top[1], top[2]));
+            if let Some(bottom) = bottom {
+                cell.set_bg(Color::Rgb(bottom[0], bottom[1], bottom[2]));
+            }
+        }
+    }
+}
+
+/// Write the escape sequence into the top-left cell and mark the rest of the area as
+/// skipped so the backend doesn't draw over the image.
+fn place_sequence(buf: &mut Buffer, area: Rect, sequence: &str) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            buf[(x, y)].reset();
+            buf[(x, y)].set_skip(true);
+        }
+    }
+    let cell = &mut buf[(area.x, area.y)];
+    cell.set_skip(false);
+    cell.set_symbol(sequence);
+}
+
+/// Kitty images drawn by the last frame.
+///
+/// Kitty keeps an image on screen until it is deleted, even after the cells under it
+/// are drawn over, so the application deletes the images a new frame no longer draws,
+/// e.g. after navigating away from the page showing them.
+#[derive(Debug, Default)]
+pub(crate) struct KittyImages {
+    shown: HashSet<u32>,
+    stale: Vec<u32>,
+}
+
+impl KittyImages {
+    /// Note the images drawn by `buffer`, a completed frame.
+    pub(crate) fn track(&mut self, buffer: &Buffer) {
+        let drawn: HashSet<u32> = buffer.content.iter().filter_map(|cell| kitty_id(cell.symbol())).collect();
+        self.stale.extend(self.shown.difference(&drawn));
+        self.shown = drawn;
+    }
+
+    /// Delete the images the last tracked frame no longer draws.
+    pub(crate) fn delete_stale(&mut self, out: &mut impl io::Write) -> io::Result<()> {
+        if self.stale.is_empty() {
+            return Ok(());
+        }
+        for id in self.stale.drain(..) {
+            write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", id)?;
+        }
+        out.flush()
+    }
+}
+
+/// The image id of a kitty sequence written by `kitty_sequence`.
+fn kitty_id(symbol: &str) -> Option<u32> {
+    let params = symbol.strip_prefix("\x1b_G")?.split(';').next()?;
+    params.split(',').find_map(|param| param.strip_prefix("i="))?.parse().ok()
+}
+
+fn kitty_sequence(image: &RgbaImage, id: u32, cols: u16, rows: u16) -> Option<String> {
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png).ok()?;
+    let data = base64(&png);
+
+    // Payloads are sent in chunks of at most 4096 bytes; `m=1` marks more to come.
+    let chunks: Vec<&str> = data.as_bytes().chunks(4096).map(|c| std::str::from_utf8(c).unwrap_or_default()).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            let _ = write!(out, "\x1b_Gf=100,a=T,i={},c={},r={},q=2,C=1,m={};{}\x1b\\", id, cols, rows, more, chunk);
+        } else {
+            let _ = write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk);
+        }
+    }
+    Some(out)
+}
+
+/// Encode as sixel using a fixed 6x6x6 color cube palette.
+fn sixel_sequence(image: &RgbaImage) -> Option<String> {
+    let (w, h) = image.dimensions();
+    if w == 0 || h == 0 {
+        return None;
+    }
+    let level = |c: u8| (c as u16 * 5 / 255) as usize;
+    let index = |p: &::image::Rgba<u8>| level(p[0]) * 36 + level(p[1]) * 6 + level(p[2]);
+
+    let mut out = String::from("\x1bPq");
+    let _ = write!(out, "\"1;1;{};{}", w, h);
+    for i in 0..216 {
+        let (r, g, b) = (i / 36, (i / 6) % 6, i % 6);
+        let _ = write!(out, "#{};2;{};{};{}", i, r * 20, g * 20, b * 20);
+    }
+
+    for band in (0..h).step_by(6) {
+        let band_h = (h - band).min(6);
+        let mut used = [false; 216];
+        for y in band..band + band_h {
+            for x in 0..w {
+                let p = image.get_pixel(x, y);
+                if p[3] > 0 {
+                    used[index(p)] = true;
+                }
+            }
+        }
+        for (color, _) in used.iter().enumerate().filter(|(_, u)| **u) {
+            let _ = write!(out, "#{}", color);
+            let mut x = 0;
+            while x < w {
+                let bits = |x: u32| {
+                    (0..band_h).fold(0u8, |acc, dy| {
+                        let p = image.get_pixel(x, band + dy);
+                        if p[3] > 0 && index(p) == color { acc | (1 << dy) } else { acc }
+                    })
+                };
+                let value = bits(x);
+                let mut run = 1;
+                while x + run < w && bits(x + run) == value {
+                    run += 1;
+                }
+                let ch = (63 + value) as char;
+                if run > 3 {
+                    let _ = write!(out, "!{}{}", run, ch);
+                } else {
+                    (0..run).for_each(|_| out.push(ch));
+                }
+                x += run;
+            }
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    Some(out)
+}
+
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(TABLE[(n >> 18) as usize & 63] as char);
+        out.push(TABLE[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { TABLE[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { TABLE[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn half_blocks_keep_the_aspect_ratio_inside_the_area() {
+        // 4x2 pixels scaled into a 10x10 area: 10 columns by 5 pixels, so 3 rows, centered
+        let pixels = RgbaImage::from_fn(4, 2, |_, y| if y == 0 { [255, 0, 0, 255].into() } else { [0, 0, 255, 255].into() });
+        let mut element = image(DynamicImage::ImageRgba8(pixels)).protocol(ImageProtocol::HalfBlocks);
+        let area = Rect::new(1, 1, 10, 10);
+        let mut terminal = Terminal::new(TestBackend::new(12, 12)).unwrap();
+        terminal.draw(|frame| element.render(frame, area)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let drawn: Vec<Position> = buffer.area.positions().filter(|&p| buffer[p].symbol() == "▀").collect();
+        let expected: Vec<Position> = Rect::new(1, 4, 10, 3).positions().collect();
+        assert_eq!(drawn, expected);
+        assert_eq!(buffer[(1, 4)].fg, Color::Rgb(255, 0, 0));
+
+        // Drawing the same image at the same size reuses the resized pixels
+        let first = element.prepare(area).unwrap();
+        assert!(Arc::ptr_eq(&first, &element.prepare(area).unwrap()));
+        assert!(!Arc::ptr_eq(&first, &element.prepare(Rect::new(0, 0, 6, 6)).unwrap()));
+    }
+
+    #[test]
+    fn kitty_images_are_deleted_once_no_longer_drawn() {
+        let pixels = RgbaImage::from_pixel(2, 2, [0, 255, 0, 255].into());
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        let sequence = kitty_sequence(&pixels, 7, 2, 1).unwrap();
+        place_sequence(&mut buffer, Rect::new(1, 0, 2, 1), &sequence);
+
+        let mut images = KittyImages::default();
+        let mut out = Vec::new();
+        images.track(&buffer);
+        images.delete_stale(&mut out).unwrap();
+        assert!(out.is_empty());
+
+        images.track(&Buffer::empty(buffer.area));
+        images.delete_stale(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b_Ga=d,d=I,i=7,q=2\x1b\\");
+    }
+}
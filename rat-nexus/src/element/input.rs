@@ -0,0 +1,279 @@
+use ratatui::prelude::*;
+use crate::component::traits::Event;
+use crate::element::{sanitize_paste, Element};
+use crate::terminal::{request_cursor_shape, CursorShape};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Called with the new value whenever the user edits an `Input`.
+type ChangeCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// A single-line text input, kept by the component and drawn with `input`.
+///
+/// The cursor moves by grapheme, so editing never splits a character or an
+/// emoji sequence, and is drawn at the right cell for wide characters.
+///
+/// # Example
+/// ```ignore
+/// let search = Input::new()
+///     .placeholder("Search...")
+///     .on_change(move |query| { let _ = query_entity.update(|q| *q = query.to_string()); });
+///
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     div().border_all().title(" Search ").child(input(&self.search).focused(self.typing))
+/// }
+///
+/// fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+///     self.search.handle_event(&event);
+///     None
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Input {
+    value: String,
+    /// Cursor position in bytes, always on a grapheme boundary.
+    cursor: usize,
+    placeholder: String,
+    on_change: Option<ChangeCallback>,
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Text shown, dimmed, while the value is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Call `f` with the new value whenever the user edits it.
+    pub fn on_change<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(f));
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Cursor position in bytes into `value`.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replace the value and put the cursor at its end. Doesn't call `on_change`,
+    /// which reports the user's edits only.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    /// Insert text at the cursor and move the cursor past it.
+    ///
+    /// The text is inserted as is; run pasted text through `sanitize_paste` first.
+    pub fn insert_str(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.value.insert_str(self.cursor, text);
+        self.cursor += text.len();
+        self.changed();
+    }
+
+    /// Handle editing keys: characters, Backspace/Delete, Left/Right and
+    /// Home/End. Returns true if the key was consumed.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.insert_str(c.encode_utf8(&mut [0; 4]));
+            }
+            KeyCode::Backspace => {
+                let start = self.previous_boundary();
+                if start < self.cursor {
+                    self.value.replace_range(start..self.cursor, "");
+                    self.cursor = start;
+                    self.changed();
+                }
+            }
+            KeyCode::Delete => {
+                let end = self.next_boundary();
+                if end > self.cursor {
+                    self.value.replace_range(self.cursor..end, "");
+                    self.changed();
+                }
+            }
+            KeyCode::Left => self.cursor = self.previous_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Insert pasted text at the cursor, as a single line.
+    pub fn handle_paste(&mut self, text: &str) -> bool {
+        self.insert_str(&sanitize_paste(text, true));
+        true
+    }
+
+    /// Handle a key or paste event. Returns true if the event was consumed.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Paste(text) => self.handle_paste(text),
+            _ => false,
+        }
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    fn changed(&self) {
+        if let Some(on_change) = &self.on_change {
+            on_change(&self.value);
+        }
+    }
+}
+
+/// The element drawing an `Input`, see `input`.
+pub struct InputView {
+    value: String,
+    cursor: usize,
+    placeholder: String,
+    focused: bool,
+    style: Style,
+}
+
+/// Draw `state` on one line. The cursor is only shown while `focused`.
+pub fn input(state: &Input) -> InputView {
+    InputView {
+        value: state.value.clone(),
+        cursor: state.cursor,
+        placeholder: state.placeholder.clone(),
+        focused: false,
+        style: Style::default(),
+    }
+}
+
+impl InputView {
+    /// Whether keys go to the input, so the cursor is drawn.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.style = self.style.fg(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.style = self.style.bg(color);
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.style = self.style.add_modifier(Modifier::BOLD);
+        self
+    }
+}
+
+impl Element for InputView {
+    fn height(&self) -> Constraint {
+        Constraint::Length(1)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+        let buffer = frame.buffer_mut();
+        buffer.set_style(area, self.style);
+        if self.value.is_empty() {
+            let muted = Style::default().fg(crate::theme::current().muted);
+            buffer.set_stringn(area.x, area.y, &self.placeholder, area.width as usize, muted);
+        }
+
+        // Scroll horizontally so the cursor stays in view, with room for it after the text
+        let cursor_column = self.value[..self.cursor].width();
+        let skip = cursor_column.saturating_sub(area.width as usize - 1);
+        let mut column = 0;
+        let mut x = area.x;
+        for grapheme in self.value.graphemes(true) {
+            let width = grapheme.width();
+            if column >= skip {
+                if x as usize + width > area.right() as usize {
+                    break;
+                }
+                buffer.set_stringn(x, area.y, grapheme, width.max(1), Style::default());
+                x += width as u16;
+            }
+            column += width;
+        }
+
+        if self.focused {
+            frame.set_cursor_position((area.x + (cursor_column - skip) as u16, area.y));
+            request_cursor_shape(CursorShape::Bar);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Entity;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn press(input: &mut Input, code: KeyCode) -> bool {
+        input.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn editing_moves_by_grapheme_and_the_cursor_lands_on_the_right_cell() {
+        let changes = Entity::new(Vec::new());
+        let log = Entity::clone(&changes);
+        let mut search = Input::new().on_change(move |value| {
+            let _ = log.update(|log| log.push(value.to_string()));
+        });
+        for c in "日本e\u{301}x".chars() {
+            assert!(press(&mut search, KeyCode::Char(c)));
+        }
+        assert!(press(&mut search, KeyCode::Left));
+        assert!(press(&mut search, KeyCode::Left));
+        assert!(press(&mut search, KeyCode::Backspace));
+        assert_eq!((search.value(), search.cursor()), ("日e\u{301}x", 3));
+        assert!(press(&mut search, KeyCode::Delete));
+        assert_eq!(search.value(), "日x");
+        assert!(!press(&mut search, KeyCode::Up));
+        assert_eq!(changes.read(|c| c.len()).unwrap(), 7);
+
+        let mut terminal = Terminal::new(TestBackend::new(6, 1)).unwrap();
+        terminal.draw(|frame| input(&search).focused(true).render(frame, frame.area())).unwrap();
+        // The wide character covers two cells, the second left blank
+        assert_eq!(crate::testing::buffer_lines(terminal.backend().buffer()), ["日 x"]);
+        terminal.backend_mut().assert_cursor_position((2, 0));
+    }
+}
@@ -0,0 +1,112 @@
+//! Stable identities for rendered children.
+//!
+//! `Div::keyed_children` tags each child with an `ElementKey` derived from the
+//! item it was built from. Element trees are rebuilt every frame, so the key is
+//! what ties a child to "the same item" across frames, independent of its
+//! position: use it to keep per-item view state, and to map mouse positions back
+//! to items with `key_at` after the frame has been drawn.
+
+use ratatui::layout::{Position, Rect};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::cell::RefCell;
+
+/// Identity of a keyed child element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElementKey(u64);
+
+impl ElementKey {
+    /// Derive a key from any hashable value, e.g. a database id or an `EntityId`.
+    pub fn new(key: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<crate::state::EntityId> for ElementKey {
+    fn from(id: crate::state::EntityId) -> Self {
+        Self::new(id)
+    }
+}
+
+thread_local! {
+    /// Areas of keyed children in the last drawn frame, in render order.
+    /// Frames are drawn and events handled on the same thread, so this is per thread.
+    static LAYOUT: RefCell<Vec<(ElementKey, Rect)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Record where a keyed child was drawn this frame.
+pub(crate) fn record(key: ElementKey, area: Rect) {
+    LAYOUT.with(|layout| layout.borrow_mut().push((key, area)));
+}
+
+/// Forget the previous frame's layout. Called before each frame is drawn.
+pub(crate) fn clear() {
+    LAYOUT.with(|layout| layout.borrow_mut().clear());
+}
+
+/// Number of keyed children recorded so far this frame.
+pub(crate) fn len() -> usize {
+    LAYOUT.with(|layout| layout.borrow().len())
+}
+
+/// Keyed children recorded this frame after the first `start`, e.g. by a cached subtree.
+pub(crate) fn since(start: usize) -> Vec<(ElementKey, Rect)> {
+    LAYOUT.with(|layout| layout.borrow().get(start..).map(<[_]>::to_vec).unwrap_or_default())
+}
+
+/// Remove and return the keyed children recorded after the first `start`,
+/// e.g. to move those of an offscreen subtree to where it is shown.
+pub(crate) fn split_off(start: usize) -> Vec<(ElementKey, Rect)> {
+    LAYOUT.with(|layout| {
+        let mut layout = layout.borrow_mut();
+        let start = start.min(layout.len());
+        layout.split_off(start)
+    })
+}
+
+/// The key of the innermost keyed child drawn at the given cell in the last frame.
+pub fn key_at(column: u16, row: u16) -> Option<ElementKey> {
+    LAYOUT.with(|layout| {
+        layout
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(_, area)| area.contains(Position::new(column, row)))
+            .map(|(key, _)| *key)
+    })
+}
+
+/// Where the child with the given key was drawn in the last frame.
+pub fn key_area(key: ElementKey) -> Option<Rect> {
+    LAYOUT.with(|layout| layout.borrow().iter().rev().find(|(k, _)| *k == key).map(|(_, area)| *area))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{div, text, Element};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn keyed_children_map_positions_back_to_items() {
+        let items = [(7, "seven"), (3, "three"), (9, "nine")];
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        terminal
+            .draw(|frame| {
+                clear();
+                let mut root = div().keyed_children(items.iter(), |(id, _)| *id, |(_, name)| text(*name));
+                assert_eq!(root.keys().len(), 3);
+                root.render(frame, frame.area());
+            })
+            .unwrap();
+
+        assert_eq!(key_at(2, 1), Some(ElementKey::new(3)));
+        assert_eq!(key_area(ElementKey::new(9)), Some(Rect::new(0, 2, 10, 1)));
+        assert_eq!(key_at(2, 5), None);
+    }
+}
@@ -0,0 +1,403 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{List, ListItem, ListState};
+use crate::component::traits::Event;
+use crate::element::Element;
+use crate::element::scroll::{self, ScrollDirection};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use std::sync::{Arc, Mutex};
+
+/// Called with the new index when the user changes the selection.
+type SelectCallback = Arc<dyn Fn(usize) + Send + Sync>;
+
+/// A scrollable list with a highlighted selection.
+///
+/// The list keeps the selection visible while scrolling. To keep the scroll
+/// position across frames and react to keys and the mouse, keep a
+/// `ListSelection` in the component and pass it with `state`.
+///
+/// # Example
+/// ```ignore
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     list(self.files.iter().map(|f| f.name.clone())).state(&self.selection)
+/// }
+///
+/// fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+///     self.selection.handle_event(&event);
+///     None
+/// }
+/// ```
+///
+/// For streaming content such as logs, `follow_tail` keeps the newest item
+/// selected until the user moves away from it.
+pub struct ListView {
+    items: Vec<ListItem<'static>>,
+    selection: ListSelection,
+    /// Whether the selection is kept by the component, see `state`.
+    stateful: bool,
+    highlight_style: Option<Style>,
+    highlight_symbol: Option<String>,
+    follow_tail: bool,
+}
+
+pub fn list<I>(items: I) -> ListView
+where
+    I: IntoIterator,
+    I::Item: Into<ListItem<'static>>,
+{
+    ListView {
+        items: items.into_iter().map(Into::into).collect(),
+        selection: ListSelection::new(),
+        stateful: false,
+        highlight_style: None,
+        highlight_symbol: None,
+        follow_tail: false,
+    }
+}
+
+impl ListView {
+    /// Share selection and scroll position with a `ListSelection` kept by the component.
+    /// The mouse wheel then moves the selection while the pointer is over the list.
+    pub fn state(mut self, selection: &ListSelection) -> Self {
+        self.selection = ListSelection::clone(selection);
+        self.stateful = true;
+        self.pin_to_tail();
+        self
+    }
+
+    /// Keep the last item selected as items are added, unless the user has moved
+    /// the selection away from it. Following resumes once the user selects the
+    /// last item again; see `ListSelection::is_following`.
+    pub fn follow_tail(mut self, follow: bool) -> Self {
+        self.follow_tail = follow;
+        self.pin_to_tail();
+        self
+    }
+
+    /// Select the item at `index` (clamped to the last item when drawn).
+    pub fn selected(self, index: usize) -> Self {
+        self.selection.select(Some(index));
+        self
+    }
+
+    /// Style of the selected row. Defaults to the theme's primary color, reversed.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = Some(style);
+        self
+    }
+
+    /// Symbol drawn in front of the selected row.
+    pub fn highlight_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.highlight_symbol = Some(symbol.into());
+        self
+    }
+
+    /// Call `f` with the new index whenever the user changes the selection.
+    /// Registered on the underlying `ListSelection`, so it persists across frames.
+    pub fn on_select<F>(self, f: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        self.selection.set_on_select(Arc::new(f));
+        self
+    }
+
+    /// Select the last item if following the tail. Done as soon as the list is
+    /// built, so the component can read the selection in the same render.
+    fn pin_to_tail(&self) {
+        if !self.follow_tail {
+            return;
+        }
+        let mut inner = self.selection.lock();
+        if inner.following && !self.items.is_empty() {
+            inner.state.select(Some(self.items.len() - 1));
+        }
+    }
+}
+
+impl Element for ListView {
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let highlight_style = self.highlight_style.unwrap_or_else(|| {
+            match crate::theme::current().primary {
+                Color::Reset => Style::default().add_modifier(Modifier::REVERSED),
+                color => Style::default().fg(color).add_modifier(Modifier::REVERSED),
+            }
+        });
+        self.pin_to_tail();
+        let mut widget = List::new(std::mem::take(&mut self.items)).highlight_style(highlight_style);
+        if let Some(symbol) = &self.highlight_symbol {
+            widget = widget.highlight_symbol(symbol);
+        }
+
+        let len = widget.len();
+        if self.stateful {
+            let selection = ListSelection::clone(&self.selection);
+            scroll::record(area, Arc::new(move |direction| selection.scroll(direction)));
+        }
+        let mut inner = self.selection.lock();
+        inner.len = len;
+        inner.area = area;
+        let selected = inner.state.selected().map(|i| i.min(len.saturating_sub(1)));
+        inner.state.select(if len == 0 { None } else { selected });
+        frame.render_stateful_widget(widget, area, &mut inner.state);
+    }
+}
+
+struct SelectionInner {
+    state: ListState,
+    /// Number of items and area of the last frame, for keyboard and mouse handling.
+    len: usize,
+    area: Rect,
+    on_select: Option<SelectCallback>,
+    /// Whether a `follow_tail` list keeps the last item selected.
+    following: bool,
+}
+
+/// Selection and scroll state of a `ListView`, kept by the component across frames.
+///
+/// Clones share the same state, so the component can hand a clone to the list
+/// each frame and still see the selection the user made.
+#[derive(Clone)]
+pub struct ListSelection {
+    inner: Arc<Mutex<SelectionInner>>,
+}
+
+impl Default for ListSelection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ListSelection {
+    /// A selection starting at the first item.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SelectionInner {
+                state: ListState::default().with_selected(Some(0)),
+                len: 0,
+                area: Rect::default(),
+                on_select: None,
+                following: true,
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SelectionInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set_on_select(&self, callback: SelectCallback) {
+        self.lock().on_select = Some(callback);
+    }
+
+    /// Index of the selected item, if any.
+    pub fn selected(&self) -> Option<usize> {
+        self.lock().state.selected()
+    }
+
+    /// Select an item, or clear the selection with `None`.
+    /// Selecting anything but the last item stops following the tail.
+    pub fn select(&self, index: Option<usize>) {
+        let mut inner = self.lock();
+        inner.following = index.is_some_and(|i| i + 1 >= inner.len);
+        inner.state.select(index);
+    }
+
+    /// Whether a `follow_tail` list keeps the newest item selected. True until the
+    /// user moves the selection off the last item, and again once they return to it.
+    pub fn is_following(&self) -> bool {
+        self.lock().following
+    }
+
+    /// Start or stop following the tail, e.g. from an "auto-scroll" toggle.
+    pub fn set_following(&self, following: bool) {
+        self.lock().following = following;
+    }
+
+    /// Index of the first visible item.
+    pub fn offset(&self) -> usize {
+        self.lock().state.offset()
+    }
+
+    /// Handle navigation keys: Up/Down, Home/End and PageUp/PageDown.
+    /// Returns true if the key was consumed.
+    pub fn handle_key(&self, key: &KeyEvent) -> bool {
+        let (len, page) = {
+            let inner = self.lock();
+            (inner.len, inner.area.height.max(1) as usize)
+        };
+        if len == 0 {
+            return false;
+        }
+        let current = self.selected().unwrap_or(0);
+        let next = match key.code {
+            KeyCode::Up => current.saturating_sub(1),
+            KeyCode::Down => current + 1,
+            KeyCode::Home => 0,
+            KeyCode::End => len - 1,
+            KeyCode::PageUp => current.saturating_sub(page),
+            KeyCode::PageDown => current + page,
+            _ => return false,
+        };
+        self.change_to(next.min(len - 1));
+        true
+    }
+
+    /// Handle the mouse wheel and clicks inside the list's last drawn area.
+    /// Returns true if the event was consumed.
+    pub fn handle_mouse(&self, mouse: &MouseEvent) -> bool {
+        let (len, area, offset) = {
+            let inner = self.lock();
+            (inner.len, inner.area, inner.state.offset())
+        };
+        if len == 0 || !area.contains(Position::new(mouse.column, mouse.row)) {
+            return false;
+        }
+        let current = self.selected().unwrap_or(0);
+        let next = match mouse.kind {
+            MouseEventKind::ScrollUp => current.saturating_sub(1),
+            MouseEventKind::ScrollDown => current + 1,
+            MouseEventKind::Down(MouseButton::Left) => {
+                let row = offset + (mouse.row - area.y) as usize;
+                if row >= len {
+                    return false;
+                }
+                row
+            }
+            _ => return false,
+        };
+        self.change_to(next.min(len - 1));
+        true
+    }
+
+    /// Move the selection one item up or down.
+    pub fn scroll(&self, direction: ScrollDirection) {
+        let len = self.lock().len;
+        if len == 0 {
+            return;
+        }
+        let current = self.selected().unwrap_or(0);
+        match direction {
+            ScrollDirection::Up => self.change_to(current.saturating_sub(1)),
+            ScrollDirection::Down => self.change_to((current + 1).min(len - 1)),
+            ScrollDirection::Left | ScrollDirection::Right => {}
+        }
+    }
+
+    /// Handle a key or mouse event. Returns true if the event was consumed.
+    pub fn handle_event(&self, event: &Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            _ => false,
+        }
+    }
+
+    /// Select `index` and run the `on_select` callback if the selection changed.
+    fn change_to(&self, index: usize) {
+        let callback = {
+            let mut inner = self.lock();
+            inner.following = index + 1 >= inner.len;
+            if inner.state.selected() == Some(index) {
+                return;
+            }
+            inner.state.select(Some(index));
+            inner.on_select.clone()
+        };
+        if let Some(callback) = callback {
+            callback(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn press(selection: &ListSelection, code: KeyCode) -> bool {
+        selection.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn selection_follows_keys_and_stays_visible() {
+        let selection = ListSelection::new();
+        let last = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&last);
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        let draw = |terminal: &mut Terminal<TestBackend>| {
+            let seen = Arc::clone(&seen);
+            let mut element = list((0..10).map(|i| format!("item {i}")))
+                .state(&selection)
+                .on_select(move |i| seen.store(i, Ordering::Relaxed));
+            terminal.draw(|frame| element.render(frame, frame.area())).unwrap();
+        };
+
+        draw(&mut terminal);
+        assert!(!press(&selection, KeyCode::Char('x')));
+        assert!(press(&selection, KeyCode::End));
+        assert_eq!(selection.selected(), Some(9));
+        assert_eq!(last.load(Ordering::Relaxed), 9);
+
+        draw(&mut terminal);
+        assert_eq!(selection.offset(), 7);
+        assert!(press(&selection, KeyCode::PageUp));
+        assert!(press(&selection, KeyCode::Down));
+        assert_eq!(selection.selected(), Some(7));
+        assert!(press(&selection, KeyCode::Home));
+        draw(&mut terminal);
+        assert_eq!(selection.offset(), 0);
+    }
+
+    #[test]
+    fn the_viewport_keeps_its_offset_across_frames() {
+        let selection = ListSelection::new();
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        let mut draw = || {
+            let mut element = list((0..10).map(|i| format!("item {i}"))).state(&selection);
+            terminal.draw(|frame| element.render(frame, frame.area())).unwrap();
+            buffer_lines(terminal.backend().buffer())
+        };
+
+        assert_eq!(draw(), ["item 0", "item 1", "item 2"]);
+        assert!(press(&selection, KeyCode::End));
+        assert_eq!(draw(), ["item 7", "item 8", "item 9"]);
+        // Moving within the viewport doesn't scroll it
+        assert!(press(&selection, KeyCode::Up));
+        assert!(press(&selection, KeyCode::Up));
+        assert_eq!(draw(), ["item 7", "item 8", "item 9"]);
+        // Leaving it scrolls by one row rather than jumping to the selection
+        assert!(press(&selection, KeyCode::Up));
+        assert_eq!(draw(), ["item 6", "item 7", "item 8"]);
+        assert_eq!(selection.offset(), 6);
+    }
+
+    #[test]
+    fn follow_tail_pins_the_newest_item_until_the_user_scrolls_up() {
+        let selection = ListSelection::new();
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        let mut draw = |len: usize| {
+            let mut element = list((0..len).map(|i| format!("log {i}"))).state(&selection).follow_tail(true);
+            terminal.draw(|frame| element.render(frame, frame.area())).unwrap();
+        };
+
+        draw(5);
+        assert_eq!(selection.selected(), Some(4));
+        draw(8);
+        assert_eq!(selection.selected(), Some(7));
+        assert_eq!(selection.offset(), 5);
+
+        selection.scroll(ScrollDirection::Up);
+        assert!(!selection.is_following());
+        draw(10);
+        assert_eq!(selection.selected(), Some(6));
+
+        // Back at the bottom, following resumes
+        assert!(press(&selection, KeyCode::End));
+        assert!(selection.is_following());
+        draw(12);
+        assert_eq!(selection.selected(), Some(11));
+    }
+}
@@ -0,0 +1,307 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Paragraph, Wrap};
+use pulldown_cmark::{CodeBlockKind, Event as MdEvent, HeadingLevel, Options, Parser, Tag, TagEnd};
+use crate::element::Element;
+
+/// Renders a markdown string as styled text.
+///
+/// Supports headings, bold/italic/strikethrough, inline code, code blocks,
+/// ordered and unordered lists, block quotes, links and horizontal rules.
+/// Anything else is rendered as plain text.
+pub struct Markdown {
+    source: String,
+    style: Style,
+    scroll: u16,
+    width_constraint: Constraint,
+    height_constraint: Constraint,
+}
+
+pub fn markdown(source: impl Into<String>) -> Markdown {
+    Markdown::new(source)
+}
+
+impl Markdown {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            style: Style::default(),
+            scroll: 0,
+            width_constraint: Constraint::Min(0),
+            height_constraint: Constraint::Min(0),
+        }
+    }
+
+    /// Scroll the content down by the given number of lines.
+    pub fn scroll(mut self, offset: u16) -> Self {
+        self.scroll = offset;
+        self
+    }
+
+    pub fn w_full(mut self) -> Self {
+        self.width_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn h_full(mut self) -> Self {
+        self.height_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn w(mut self, length: u16) -> Self {
+        self.width_constraint = Constraint::Length(length);
+        self
+    }
+
+    pub fn h(mut self, length: u16) -> Self {
+        self.height_constraint = Constraint::Length(length);
+        self
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.style = self.style.fg(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.style = self.style.bg(color);
+        self
+    }
+}
+
+impl Element for Markdown {
+    fn width(&self) -> Constraint {
+        self.width_constraint
+    }
+
+    fn height(&self) -> Constraint {
+        self.height_constraint
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let lines = markdown_to_lines(&self.source, area.width);
+        let p = Paragraph::new(lines)
+            .style(self.style)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(p, area);
+    }
+}
+
+/// Convert markdown into styled lines. `width` is used for horizontal rules.
+pub fn markdown_to_lines(source: &str, width: u16) -> Vec<Line<'static>> {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS;
+    let mut writer = Writer::new(width);
+    for event in Parser::new_ext(source, options) {
+        writer.handle(event);
+    }
+    writer.finish()
+}
+
+struct Writer {
+    width: u16,
+    lines: Vec<Line<'static>>,
+    spans: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    /// One entry per nested list: `Some(n)` for ordered lists (next number), `None` for bullets.
+    lists: Vec<Option<u64>>,
+    quote_depth: usize,
+    in_code_block: bool,
+    link: Option<String>,
+}
+
+impl Writer {
+    fn new(width: u16) -> Self {
+        Self {
+            width,
+            lines: Vec::new(),
+            spans: Vec::new(),
+            styles: vec![Style::default()],
+            lists: Vec::new(),
+            quote_depth: 0,
+            in_code_block: false,
+            link: None,
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: Style) {
+        let patched = self.style().patch(style);
+        self.styles.push(patched);
+    }
+
+    fn pop_style(&mut self) {
+        if self.styles.len() > 1 {
+            self.styles.pop();
+        }
+    }
+
+    fn prefix(&self) -> Vec<Span<'static>> {
+        let mut prefix = Vec::new();
+        if self.quote_depth > 0 {
+            prefix.push(Span::styled("│ ".repeat(self.quote_depth), Style::default().fg(Color::DarkGray)));
+        }
+        prefix
+    }
+
+    fn flush(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+        let mut spans = self.prefix();
+        spans.append(&mut self.spans);
+        self.lines.push(Line::from(spans));
+    }
+
+    fn blank_line(&mut self) {
+        // Avoid stacking blank lines between blocks
+        if self.lines.last().is_some_and(|l| l.width() > 0) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.in_code_block {
+            let style = Style::default().fg(Color::Yellow);
+            for line in text.lines() {
+                self.spans.push(Span::styled(format!("  {}", line), style));
+                self.flush();
+            }
+            return;
+        }
+        self.spans.push(Span::styled(text.to_string(), self.style()));
+    }
+
+    fn handle(&mut self, event: MdEvent) {
+        match event {
+            MdEvent::Start(tag) => self.start(tag),
+            MdEvent::End(tag) => self.end(tag),
+            MdEvent::Text(text) => self.text(&text),
+            MdEvent::Code(code) => {
+                self.spans.push(Span::styled(code.to_string(), self.style().fg(Color::Yellow)));
+            }
+            MdEvent::SoftBreak => self.spans.push(Span::raw(" ")),
+            MdEvent::HardBreak => self.flush(),
+            MdEvent::Rule => {
+                self.flush();
+                self.lines.push(Line::styled("─".repeat(self.width as usize), Style::default().fg(Color::DarkGray)));
+                self.blank_line();
+            }
+            MdEvent::TaskListMarker(done) => {
+                self.spans.push(Span::raw(if done { "[x] " } else { "[ ] " }));
+            }
+            // Unsupported constructs degrade to their raw text
+            MdEvent::Html(raw) | MdEvent::InlineHtml(raw) => self.text(&raw),
+            MdEvent::InlineMath(raw) | MdEvent::DisplayMath(raw) => self.text(&raw),
+            MdEvent::FootnoteReference(name) => self.text(&format!("[{}]", name)),
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                let style = match level {
+                    HeadingLevel::H1 => Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    HeadingLevel::H2 => Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                    _ => Style::default().add_modifier(Modifier::BOLD),
+                };
+                self.push_style(style);
+            }
+            Tag::Emphasis => self.push_style(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.push_style(Style::default().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } => {
+                self.link = Some(dest_url.to_string());
+                self.push_style(Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED));
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth += 1;
+                self.push_style(Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC));
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush();
+                self.in_code_block = true;
+                if let CodeBlockKind::Fenced(lang) = kind {
+                    if !lang.is_empty() {
+                        self.lines.push(Line::styled(format!("  {}", lang), Style::default().fg(Color::DarkGray)));
+                    }
+                }
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        let marker = format!("{}. ", n);
+                        *n += 1;
+                        marker
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.spans.push(Span::raw("  ".repeat(depth)));
+                self.spans.push(Span::styled(marker, Style::default().fg(Color::Cyan)));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(_) => {
+                self.flush();
+                self.pop_style();
+                self.blank_line();
+            }
+            TagEnd::Paragraph => {
+                self.flush();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_style(),
+            TagEnd::Link => {
+                self.pop_style();
+                if let Some(url) = self.link.take() {
+                    self.spans.push(Span::styled(format!(" ({})", url), Style::default().fg(Color::DarkGray)));
+                }
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.pop_style();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.blank_line();
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.blank_line();
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            TagEnd::Item => self.flush(),
+            TagEnd::TableCell => self.spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray))),
+            TagEnd::TableHead | TagEnd::TableRow => self.flush(),
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush();
+        while self.lines.last().is_some_and(|l| l.width() == 0) {
+            self.lines.pop();
+        }
+        self.lines
+    }
+}
@@ -2,13 +2,61 @@ use ratatui::prelude::*;
 
 pub mod div;
 pub mod text;
+pub mod center;
+pub mod stack;
+pub mod grid;
+pub mod empty;
+pub mod breadcrumb;
 pub mod widget;
 pub mod canvas;
+pub mod help;
+pub mod list;
+pub mod table;
+pub mod form;
+pub mod input;
+pub mod progress;
+pub mod chart;
+pub mod viewer;
+pub mod scroll_view;
+pub mod key;
+pub mod scroll;
+pub mod click;
+pub mod cache;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "code")]
+pub mod code;
+#[cfg(feature = "image")]
+pub mod image;
 
 pub use div::{div, Div};
 pub use text::{text, Text};
+pub use center::{centered, centered_popup, centered_rect, Centered};
+pub use stack::{stack, Stack};
+pub use grid::{grid, cell, Grid, GridCell};
+pub use empty::{empty, Empty};
+pub use breadcrumb::{breadcrumb, Breadcrumb};
 pub use widget::{widget, WidgetElement};
-pub use canvas::{canvas, Canvas};
+pub use canvas::{canvas, canvas_measured, Canvas, CanvasWorld};
+pub use help::{help_overlay, HelpOverlay, HelpState};
+pub use list::{list, ListView, ListSelection};
+pub use table::{table, TableView, TableSelection, SortDirection};
+pub use progress::{progress, line_gauge, Progress};
+pub use chart::{sparkline, bar_chart, line_chart, SparklineView, BarChartView, LineChartView, Series};
+pub use viewer::{text_viewer, TextViewer};
+pub use scroll_view::{scroll, Scroll, ScrollState};
+pub use input::{input, Input, InputView};
+pub use form::{Form, FormField, FormEvent, FormView, FieldError, TextField, sanitize_paste};
+pub use key::{key_at, key_area, ElementKey};
+pub use scroll::{ScrollDirection, ScrollHandler};
+pub use click::ClickHandler;
+pub use cache::{RenderCache, CachedView};
+#[cfg(feature = "markdown")]
+pub use markdown::{markdown, Markdown};
+#[cfg(feature = "code")]
+pub use code::{code, Code};
+#[cfg(feature = "image")]
+pub use image::{image, Image, ImageProtocol, ImageSource};
 
 /// The core trait for any UI element.
 pub trait Element: Send + Sync {
@@ -22,6 +70,30 @@ pub trait Element: Send + Sync {
         Constraint::Min(0)
     }
 
+    /// The height this element needs when laid out `width` columns wide, for
+    /// content-sized elements such as wrapped text. `None` lays it out with
+    /// `height()` instead.
+    fn measure_height(&self, width: u16) -> Option<u16> {
+        let _ = width;
+        None
+    }
+
+    /// The width this element needs for its content, used when centering it.
+    /// `None` lays it out with `width()` instead.
+    fn measure_width(&self) -> Option<u16> {
+        None
+    }
+
+    /// The height of all of the content laid out `width` columns wide, for
+    /// containers that scroll it (see `scroll`). Defaults to `measure_height`,
+    /// then a fixed `height()`; `None` for elements that fill whatever they get.
+    fn content_height(&self, width: u16) -> Option<u16> {
+        self.measure_height(width).or(match self.height() {
+            Constraint::Length(height) => Some(height),
+            _ => None,
+        })
+    }
+
     /// Render the element into the given area.
     fn render(&mut self, frame: &mut Frame, area: Rect);
 }
@@ -46,6 +118,15 @@ impl Element for Box<dyn Element> {
     fn height(&self) -> Constraint {
         self.as_ref().height()
     }
+    fn measure_height(&self, width: u16) -> Option<u16> {
+        self.as_ref().measure_height(width)
+    }
+    fn measure_width(&self) -> Option<u16> {
+        self.as_ref().measure_width()
+    }
+    fn content_height(&self, width: u16) -> Option<u16> {
+        self.as_ref().content_height(width)
+    }
     fn render(&mut self, frame: &mut Frame, area: Rect) {
         self.as_mut().render(frame, area)
     }
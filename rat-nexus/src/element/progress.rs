@@ -0,0 +1,196 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Gauge, LineGauge};
+use crate::element::Element;
+use crate::state::Entity;
+
+/// Where a progress element gets its ratio from.
+enum Ratio {
+    Fixed(f64),
+    /// Read when the element is drawn.
+    Bound(Box<dyn Fn() -> f64 + Send + Sync>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Gauge,
+    Line,
+}
+
+/// A progress indicator: a filled bar (`progress`) or a thin line (`line_gauge`).
+///
+/// Ratios outside `0.0..=1.0` are clamped, and NaN is shown as empty.
+///
+/// # Example
+/// ```ignore
+/// div()
+///     .child(progress(0.42).label("Downloading").h(1))
+///     .child(line_gauge(0.0).bind(&self.state, |s| s.cpu / 100.0).fg(Color::Green))
+/// ```
+pub struct Progress {
+    ratio: Ratio,
+    kind: Kind,
+    label: Option<String>,
+    style: Style,
+    filled_style: Option<Style>,
+    width_constraint: Constraint,
+    height_constraint: Constraint,
+}
+
+/// A filled progress bar.
+pub fn progress(ratio: f64) -> Progress {
+    Progress::new(ratio, Kind::Gauge)
+}
+
+/// A one-line progress indicator drawn with line characters.
+pub fn line_gauge(ratio: f64) -> Progress {
+    Progress::new(ratio, Kind::Line)
+}
+
+impl Progress {
+    fn new(ratio: f64, kind: Kind) -> Self {
+        Self {
+            ratio: Ratio::Fixed(ratio),
+            kind,
+            label: None,
+            style: Style::default(),
+            filled_style: None,
+            width_constraint: Constraint::Min(0),
+            height_constraint: Constraint::Length(1),
+        }
+    }
+
+    /// Read the ratio from an entity each time the element is drawn.
+    pub fn bind<T, F>(mut self, entity: &Entity<T>, f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&T) -> f64 + Send + Sync + 'static,
+    {
+        let entity = Entity::clone(entity);
+        self.ratio = Ratio::Bound(Box::new(move || entity.read(&f).unwrap_or(0.0)));
+        self
+    }
+
+    /// Text shown on the bar. Defaults to the percentage.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Color of the filled part. Defaults to the theme's primary color.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.filled_style = Some(self.filled_style.unwrap_or_default().fg(color));
+        self
+    }
+
+    /// Color of the unfilled part.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.style = self.style.bg(color);
+        self
+    }
+
+    /// Style of the filled part.
+    pub fn filled_style(mut self, style: Style) -> Self {
+        self.filled_style = Some(style);
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.style = self.style.add_modifier(Modifier::BOLD);
+        self
+    }
+
+    pub fn w_full(mut self) -> Self {
+        self.width_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn h_full(mut self) -> Self {
+        self.height_constraint = Constraint::Percentage(100);
+        self
+    }
+
+    pub fn w(mut self, length: u16) -> Self {
+        self.width_constraint = Constraint::Length(length);
+        self
+    }
+
+    pub fn h(mut self, length: u16) -> Self {
+        self.height_constraint = Constraint::Length(length);
+        self
+    }
+
+    pub fn w_percent(mut self, p: u16) -> Self {
+        self.width_constraint = Constraint::Percentage(p);
+        self
+    }
+
+    /// The ratio that will be drawn, clamped to `0.0..=1.0`.
+    pub fn ratio(&self) -> f64 {
+        let ratio = match &self.ratio {
+            Ratio::Fixed(ratio) => *ratio,
+            Ratio::Bound(f) => f(),
+        };
+        if ratio.is_nan() {
+            0.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl Element for Progress {
+    fn width(&self) -> Constraint {
+        self.width_constraint
+    }
+
+    fn height(&self) -> Constraint {
+        self.height_constraint
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let ratio = self.ratio();
+        let filled_style = self.filled_style.unwrap_or_else(|| {
+            Style::default().fg(crate::theme::current().primary)
+        });
+        let label = self.label.clone().unwrap_or_else(|| format!("{:.0}%", ratio * 100.0));
+
+        match self.kind {
+            Kind::Gauge => {
+                let gauge = Gauge::default()
+                    .ratio(ratio)
+                    .label(label)
+                    .style(self.style)
+                    .gauge_style(filled_style)
+                    .use_unicode(true);
+                frame.render_widget(gauge, area);
+            }
+            Kind::Line => {
+                let gauge = LineGauge::default()
+                    .ratio(ratio)
+                    .label(label)
+                    .style(self.style)
+                    .filled_style(filled_style)
+                    .unfilled_style(Style::default().fg(crate::theme::current().muted));
+                frame.render_widget(gauge, area);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratios_are_clamped_and_bound_values_are_read_on_draw() {
+        assert_eq!(progress(1.5).ratio(), 1.0);
+        assert_eq!(line_gauge(-0.2).ratio(), 0.0);
+        assert_eq!(progress(f64::NAN).ratio(), 0.0);
+
+        let done = Entity::new(3_u32);
+        let bar = progress(0.0).bind(&done, |n| *n as f64 / 4.0);
+        assert_eq!(bar.ratio(), 0.75);
+        let _ = done.update(|n| *n = 4);
+        assert_eq!(bar.ratio(), 1.0);
+    }
+}
@@ -0,0 +1,130 @@
+//! Mouse wheel routing to the element under the pointer.
+//!
+//! Elements register a scroll handler for the area they were drawn into (see
+//! `Div::on_scroll`). When a wheel event arrives, the innermost handler whose
+//! area contains the pointer runs and the event is consumed. If no scrollable
+//! element is under the pointer, the event is delivered to the root component
+//! like any other mouse event.
+
+use crossterm::event::{MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// Direction of a mouse wheel event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl ScrollDirection {
+    /// The direction of a wheel event, `None` for other mouse events.
+    pub fn from_mouse(mouse: &MouseEvent) -> Option<Self> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => Some(ScrollDirection::Up),
+            MouseEventKind::ScrollDown => Some(ScrollDirection::Down),
+            MouseEventKind::ScrollLeft => Some(ScrollDirection::Left),
+            MouseEventKind::ScrollRight => Some(ScrollDirection::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Called when the wheel turns over an element.
+pub type ScrollHandler = Arc<dyn Fn(ScrollDirection) + Send + Sync>;
+
+thread_local! {
+    /// Scroll handlers of the last drawn frame with their areas, in render order.
+    static HANDLERS: RefCell<Vec<(Rect, ScrollHandler)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Register a handler for wheel events over `area` this frame.
+pub(crate) fn record(area: Rect, handler: ScrollHandler) {
+    HANDLERS.with(|handlers| handlers.borrow_mut().push((area, handler)));
+}
+
+/// Forget the previous frame's handlers. Called before each frame is drawn.
+pub(crate) fn clear() {
+    HANDLERS.with(|handlers| handlers.borrow_mut().clear());
+}
+
+/// Number of handlers registered so far this frame.
+pub(crate) fn len() -> usize {
+    HANDLERS.with(|handlers| handlers.borrow().len())
+}
+
+/// Handlers registered this frame after the first `start`, e.g. by a cached subtree.
+pub(crate) fn since(start: usize) -> Vec<(Rect, ScrollHandler)> {
+    HANDLERS.with(|handlers| handlers.borrow().get(start..).map(<[_]>::to_vec).unwrap_or_default())
+}
+
+/// Remove and return the handlers registered after the first `start`, e.g. to
+/// move those of an offscreen subtree to where it is shown.
+pub(crate) fn split_off(start: usize) -> Vec<(Rect, ScrollHandler)> {
+    HANDLERS.with(|handlers| {
+        let mut handlers = handlers.borrow_mut();
+        let start = start.min(handlers.len());
+        handlers.split_off(start)
+    })
+}
+
+/// Run the innermost handler under the pointer. Returns false if the event is
+/// not a wheel event or no scrollable element is under the pointer.
+pub(crate) fn dispatch(mouse: &MouseEvent) -> bool {
+    let Some(direction) = ScrollDirection::from_mouse(mouse) else {
+        return false;
+    };
+    let position = Position::new(mouse.column, mouse.row);
+    // Elements are drawn before their children, so the last match is the innermost
+    let handler = HANDLERS.with(|handlers| {
+        handlers
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(position))
+            .map(|(_, handler)| Arc::clone(handler))
+    });
+    match handler {
+        Some(handler) => {
+            handler(direction);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{div, Element};
+    use crossterm::event::KeyModifiers;
+    use ratatui::{backend::TestBackend, Terminal};
+    use std::sync::Mutex;
+
+    #[test]
+    fn wheel_events_reach_the_innermost_panel_under_the_pointer() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (left, right) = (Arc::clone(&log), Arc::clone(&log));
+        let mut terminal = Terminal::new(TestBackend::new(20, 4)).unwrap();
+        terminal
+            .draw(|frame| {
+                clear();
+                let mut root = div()
+                    .flex_row()
+                    .child(div().on_scroll(move |dir| left.lock().unwrap().push(("list", dir))))
+                    .child(div().on_scroll(move |dir| right.lock().unwrap().push(("detail", dir))));
+                root.render(frame, frame.area());
+            })
+            .unwrap();
+
+        let wheel = |kind, column| MouseEvent { kind, column, row: 1, modifiers: KeyModifiers::NONE };
+        assert!(dispatch(&wheel(MouseEventKind::ScrollDown, 2)));
+        assert!(dispatch(&wheel(MouseEventKind::ScrollUp, 15)));
+        assert!(!dispatch(&wheel(MouseEventKind::Moved, 15)));
+        assert!(!dispatch(&MouseEvent { row: 10, ..wheel(MouseEventKind::ScrollUp, 2) }));
+        assert_eq!(*log.lock().unwrap(), [("list", ScrollDirection::Down), ("detail", ScrollDirection::Up)]);
+    }
+}
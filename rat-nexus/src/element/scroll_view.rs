@@ -0,0 +1,453 @@
+use ratatui::prelude::*;
+use ratatui::backend::{Backend, ClearType, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
+use crate::component::traits::Event;
+use crate::element::{click, key, Element, IntoElement};
+use crate::element::scroll::{self, ScrollDirection};
+use crate::terminal;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use std::cell::RefCell;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    /// Off-screen terminal `Scroll` draws its child into, kept across frames so its
+    /// buffers are only reallocated when the content outgrows them.
+    static OFFSCREEN: RefCell<Option<Terminal<Offscreen>>> = const { RefCell::new(None) };
+}
+
+/// A viewport onto a child taller than the space it gets, e.g. a long form or
+/// a settings page.
+///
+/// The child is drawn at its full height off screen and the rows at the
+/// scroll offset are copied into the area. Its height comes from
+/// `Element::content_height`, e.g. the rows of a div of texts; a child that
+/// takes whatever it is given just fills the viewport. For long lists of rows or
+/// text, `list` and `text_viewer` only draw what is visible and scale better.
+///
+/// # Example
+/// ```ignore
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     scroll(div().children(self.settings.iter().map(setting_row))).scrollbar().state(&self.scroll)
+/// }
+///
+/// fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+///     self.scroll.handle_event(&event);
+///     None
+/// }
+/// ```
+pub struct Scroll {
+    child: Box<dyn Element>,
+    state: ScrollState,
+    /// Whether the offset is kept by the component, see `state`.
+    stateful: bool,
+    scrollbar: bool,
+}
+
+pub fn scroll(child: impl IntoElement + 'static) -> Scroll {
+    Scroll {
+        child: Box::new(child.into_element()),
+        state: ScrollState::new(),
+        stateful: false,
+        scrollbar: false,
+    }
+}
+
+impl Scroll {
+    /// Share the offset with a `ScrollState` kept by the component. The mouse
+    /// wheel then scrolls while the pointer is over the viewport.
+    pub fn state(mut self, state: &ScrollState) -> Self {
+        self.state = ScrollState::clone(state);
+        self.stateful = true;
+        self
+    }
+
+    /// Scroll to `row` of the child (clamped when drawn).
+    pub fn offset(self, row: u16) -> Self {
+        self.state.scroll_to(row as usize);
+        self
+    }
+
+    /// Draw a scrollbar on the right edge while the child doesn't fit.
+    pub fn scrollbar(mut self) -> Self {
+        self.scrollbar = true;
+        self
+    }
+
+    /// Height of the child drawn `width` columns wide.
+    fn content_height(&self, width: u16, viewport: u16) -> u16 {
+        self.child.content_height(width).unwrap_or(viewport)
+    }
+}
+
+impl Element for Scroll {
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+        if self.stateful {
+            let state = ScrollState::clone(&self.state);
+            scroll::record(area, Arc::new(move |direction| state.scroll(direction)));
+        }
+
+        let mut width = area.width;
+        let mut height = self.content_height(width, area.height);
+        if self.scrollbar && height > area.height {
+            width -= 1;
+            height = self.content_height(width, area.height);
+        }
+        let offset = self.state.layout(height.saturating_sub(area.height) as usize, area) as u16;
+        if height == 0 || width == 0 {
+            return;
+        }
+
+        // A scroll nested in the child finds the terminal taken and makes its own
+        let offscreen = OFFSCREEN.with(|offscreen| offscreen.borrow_mut().take())
+            .map_or_else(|| Terminal::new(Offscreen::default()), Ok);
+        let Ok(mut offscreen) = offscreen else {
+            return;
+        };
+        offscreen.backend_mut().size = Size::new(width, height);
+        let (key_start, scroll_start, click_start) = (key::len(), scroll::len(), click::len());
+        // Only keep the child's cursor shape if its cursor ends up in view
+        let outer_shape = terminal::take_requested_cursor_shape();
+        let child = &mut self.child;
+        let Ok(content) = offscreen.draw(|virtual_frame| child.render(virtual_frame, virtual_frame.area())) else {
+            if let Some(shape) = outer_shape {
+                terminal::request_cursor_shape(shape);
+            }
+            return;
+        };
+
+        // Copy the visible rows, and move what the child registered along with them
+        let viewport = Rect::new(area.x, area.y, width, area.height.min(height - offset));
+        let buffer = frame.buffer_mut();
+        for y in 0..viewport.height {
+            for x in 0..viewport.width {
+                buffer[(viewport.x + x, viewport.y + y)] = content.buffer[(x, offset + y)].clone();
+            }
+        }
+        let to_screen = |rect: Rect| {
+            let y = rect.y as i32 - offset as i32 + area.y as i32;
+            let top = y.max(area.y as i32);
+            let bottom = (y + rect.height as i32).min(viewport.bottom() as i32);
+            (bottom > top).then(|| Rect::new(area.x + rect.x, top as u16, rect.width, (bottom - top) as u16))
+        };
+        let mut child_shape = terminal::take_requested_cursor_shape();
+        if let Some(cursor) = offscreen.backend().cursor() {
+            match to_screen(Rect::new(cursor.x, cursor.y, 1, 1)) {
+                Some(rect) => frame.set_cursor_position(rect.as_position()),
+                // Scrolled out of view: leave the cursor hidden
+                None => child_shape = None,
+            }
+        }
+        if let Some(shape) = child_shape.or(outer_shape) {
+            terminal::request_cursor_shape(shape);
+        }
+        OFFSCREEN.with(|cached| *cached.borrow_mut() = Some(offscreen));
+
+        let keys = key::split_off(key_start);
+        let scrolls = scroll::split_off(scroll_start);
+        let clicks = click::split_off(click_start);
+        for (element_key, rect) in keys {
+            if let Some(rect) = to_screen(rect) {
+                key::record(element_key, rect);
+            }
+        }
+        for (rect, handler) in scrolls {
+            if let Some(rect) = to_screen(rect) {
+                scroll::record(rect, handler);
+            }
+        }
+        for (rect, handler) in clicks {
+            if let Some(rect) = to_screen(rect) {
+                click::record(rect, handler);
+            }
+        }
+
+        if self.scrollbar && height > area.height {
+            let mut state = ScrollbarState::new(height.saturating_sub(area.height) as usize + 1)
+                .position(offset as usize)
+                .viewport_content_length(area.height as usize);
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .thumb_style(Style::default().fg(crate::theme::current().muted));
+            frame.render_stateful_widget(scrollbar, area, &mut state);
+        }
+    }
+}
+
+/// Backend of the off-screen terminal: it writes nothing and only remembers where
+/// the child placed the cursor, which `Frame` doesn't expose.
+#[derive(Default)]
+struct Offscreen {
+    size: Size,
+    cursor: Position,
+    cursor_visible: bool,
+}
+
+impl Offscreen {
+    /// Where the child placed the cursor in the last frame, if it did.
+    fn cursor(&self) -> Option<Position> {
+        self.cursor_visible.then_some(self.cursor)
+    }
+}
+
+impl Backend for Offscreen {
+    fn draw<'a, I>(&mut self, _content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.cursor_visible = false;
+        Ok(())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.cursor_visible = true;
+        Ok(())
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        Ok(self.cursor)
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.cursor = position.into();
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn clear_region(&mut self, _clear_type: ClearType) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        Ok(self.size)
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        Ok(WindowSize { columns_rows: self.size, pixels: Size::default() })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct ScrollInner {
+    /// Index of the first visible line (or row).
+    offset: usize,
+    /// Limits of the last frame, for keyboard and mouse handling.
+    max_offset: usize,
+    page: usize,
+    area: Rect,
+}
+
+/// Scroll position of a `Scroll` or `TextViewer`, kept by the component across frames.
+///
+/// Clones share the same position, so the component can hand a clone to the
+/// element each frame and still see where the user scrolled to.
+#[derive(Clone)]
+pub struct ScrollState {
+    inner: Arc<Mutex<ScrollInner>>,
+}
+
+impl Default for ScrollState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScrollState {
+    /// A position at the top of the content.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ScrollInner {
+                offset: 0,
+                max_offset: 0,
+                page: 0,
+                area: Rect::default(),
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ScrollInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Index of the first visible line.
+    pub fn offset(&self) -> usize {
+        self.lock().offset
+    }
+
+    /// The largest offset in the last frame: 0 if the content fit.
+    pub fn max_offset(&self) -> usize {
+        self.lock().max_offset
+    }
+
+    /// Scroll by `delta` lines, negative for up.
+    pub fn scroll_by(&self, delta: i32) {
+        let offset = self.offset();
+        let target = if delta < 0 {
+            offset.saturating_sub(delta.unsigned_abs() as usize)
+        } else {
+            offset.saturating_add(delta as usize)
+        };
+        self.scroll_to(target);
+    }
+
+    /// Record the limits of the frame being drawn and return the offset to
+    /// draw at, clamped to `max_offset`.
+    pub(crate) fn layout(&self, max_offset: usize, area: Rect) -> usize {
+        let mut inner = self.lock();
+        inner.offset = inner.offset.min(max_offset);
+        inner.max_offset = max_offset;
+        inner.page = area.height as usize;
+        inner.area = area;
+        inner.offset
+    }
+
+    /// Scroll so that `line` is the first visible line, as far as the content
+    /// allows. Clamped again when drawn, in case the content changed.
+    pub fn scroll_to(&self, line: usize) {
+        let mut inner = self.lock();
+        // Before the first frame the limit isn't known yet
+        inner.offset = if inner.page == 0 { line } else { line.min(inner.max_offset) };
+    }
+
+    pub fn scroll_to_top(&self) {
+        self.scroll_to(0);
+    }
+
+    pub fn scroll_to_bottom(&self) {
+        self.scroll_to(usize::MAX);
+    }
+
+    /// Whether the end of the content was visible in the last frame.
+    pub fn is_at_bottom(&self) -> bool {
+        let inner = self.lock();
+        inner.offset >= inner.max_offset
+    }
+
+    /// Handle navigation keys: Up/Down, Home/End and PageUp/PageDown.
+    /// Returns true if the key was consumed.
+    pub fn handle_key(&self, key: &KeyEvent) -> bool {
+        let (offset, page) = {
+            let inner = self.lock();
+            (inner.offset, inner.page.max(1))
+        };
+        let next = match key.code {
+            KeyCode::Up => offset.saturating_sub(1),
+            KeyCode::Down => offset + 1,
+            KeyCode::Home => 0,
+            KeyCode::End => usize::MAX,
+            KeyCode::PageUp => offset.saturating_sub(page),
+            KeyCode::PageDown => offset.saturating_add(page),
+            _ => return false,
+        };
+        self.scroll_to(next);
+        true
+    }
+
+    /// Handle the mouse wheel inside the viewer's last drawn area.
+    /// Returns true if the event was consumed.
+    pub fn handle_mouse(&self, mouse: &MouseEvent) -> bool {
+        let area = self.lock().area;
+        if !area.contains(Position::new(mouse.column, mouse.row)) {
+            return false;
+        }
+        match ScrollDirection::from_mouse(mouse) {
+            Some(direction @ (ScrollDirection::Up | ScrollDirection::Down)) => {
+                self.scroll(direction);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Move one line up or down.
+    pub fn scroll(&self, direction: ScrollDirection) {
+        let offset = self.offset();
+        match direction {
+            ScrollDirection::Up => self.scroll_to(offset.saturating_sub(1)),
+            ScrollDirection::Down => self.scroll_to(offset + 1),
+            ScrollDirection::Left | ScrollDirection::Right => {}
+        }
+    }
+
+    /// Handle a key or mouse event. Returns true if the event was consumed.
+    pub fn handle_event(&self, event: &Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{div, input, text, Input};
+    use crate::terminal::CursorShape;
+    use crate::testing::buffer_lines;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn the_child_is_drawn_at_the_offset_and_the_offset_is_clamped() {
+        let state = ScrollState::new();
+        let mut terminal = Terminal::new(TestBackend::new(6, 3)).unwrap();
+        let mut draw = |rows: usize| {
+            let child = div().children((0..rows).map(|i| text(format!("row {i}"))));
+            let mut view = scroll(child).scrollbar().state(&state);
+            terminal.draw(|frame| view.render(frame, frame.area())).unwrap();
+            buffer_lines(terminal.backend().buffer())
+        };
+
+        assert_eq!(draw(8), ["row 0█", "row 1║", "row 2║"]);
+        assert_eq!(state.max_offset(), 5);
+        state.scroll_by(4);
+        assert_eq!(draw(8), ["row 4║", "row 5║", "row 6█"]);
+        state.scroll_by(10);
+        assert_eq!(draw(8)[2], "row 7█");
+        state.scroll_by(-1);
+        assert_eq!(state.offset(), 4);
+
+        // Content shorter than the viewport can't be scrolled
+        assert_eq!(draw(2), ["row 0", "row 1", ""]);
+        assert_eq!((state.offset(), state.max_offset()), (0, 0));
+    }
+    #[test]
+    fn a_focused_input_keeps_its_cursor_only_while_scrolled_into_view() {
+        let state = ScrollState::new();
+        let name = Input::new();
+        let mut terminal = Terminal::new(TestBackend::new(8, 3)).unwrap();
+        let mut draw = |offset: usize| {
+            state.scroll_to(offset);
+            let child = div()
+                .children((0..4).map(|i| text(format!("row {i}"))))
+                .child(input(&name).focused(true))
+                .child(text("last"));
+            let mut view = scroll(child).state(&state);
+            terminal.draw(|frame| view.render(frame, frame.area())).unwrap();
+            let cursor = terminal.get_cursor_position().unwrap();
+            (cursor, terminal::take_requested_cursor_shape())
+        };
+
+        // The input is row 4 of the content: at the bottom of the viewport at offset 2
+        assert_eq!(draw(2), (Position::new(0, 2), Some(CursorShape::Bar)));
+        // Scrolled out of view, it gets neither the cursor nor the bar shape
+        assert_eq!(draw(1).1, None);
+        assert_eq!(draw(3), (Position::new(0, 1), Some(CursorShape::Bar)));
+    }
+}
@@ -0,0 +1,78 @@
+use ratatui::prelude::*;
+use crate::element::{Element, IntoElement};
+
+/// Children drawn into the same area, in order, so later ones cover earlier ones.
+///
+/// The stack takes the size of its first child, the base, so it lays out in a
+/// `Div` like that child would on its own. Use `centered_popup` or `Div::clear`
+/// for layers that shouldn't let the ones below show through.
+///
+/// # Example
+/// ```ignore
+/// stack()
+///     .child(page)
+///     .child(centered_popup(40, 5, div().border_all().title(" Quit? ").child(text("y / n"))))
+/// ```
+#[derive(Default)]
+pub struct Stack {
+    children: Vec<Box<dyn Element>>,
+}
+
+pub fn stack() -> Stack {
+    Stack::default()
+}
+
+impl Stack {
+    /// Add a layer on top of the ones added so far.
+    pub fn child(mut self, child: impl IntoElement + 'static) -> Self {
+        self.children.push(Box::new(child.into_element()));
+        self
+    }
+}
+
+impl Element for Stack {
+    fn width(&self) -> Constraint {
+        self.children.first().map_or(Constraint::Min(0), |base| base.width())
+    }
+
+    fn height(&self) -> Constraint {
+        self.children.first().map_or(Constraint::Min(0), |base| base.height())
+    }
+
+    fn measure_height(&self, width: u16) -> Option<u16> {
+        self.children.first()?.measure_height(width)
+    }
+
+    fn measure_width(&self) -> Option<u16> {
+        self.children.first()?.measure_width()
+    }
+
+    fn content_height(&self, width: u16) -> Option<u16> {
+        self.children.first()?.content_height(width)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        for child in &mut self.children {
+            child.render(frame, area);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{centered_popup, div, text};
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn later_children_cover_earlier_ones_inside_a_div() {
+        let page = text("abcdefghij\nabcdefghij\nabcdefghij").h_auto();
+        let mut root = div()
+            .child(stack().child(page).child(centered_popup(6, 1, text("ok"))))
+            .child(text("footer"));
+        let mut terminal = Terminal::new(TestBackend::new(10, 4)).unwrap();
+        terminal.draw(|frame| root.render(frame, frame.area())).unwrap();
+        assert_eq!(buffer_lines(terminal.backend().buffer()), ["abcdefghij", "abok    ij", "abcdefghij", "footer"]);
+    }
+}
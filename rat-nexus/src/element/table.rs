@@ -0,0 +1,438 @@
+use ratatui::prelude::*;
+use ratatui::layout::Flex;
+use ratatui::widgets::{Cell, Row, Table, TableState};
+use crate::component::traits::Event;
+use crate::element::Element;
+use crate::element::scroll::{self, ScrollDirection};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use std::cmp::Ordering;
+use std::sync::{Arc, Mutex};
+
+/// Direction of a sorted column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    fn indicator(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => " ▲",
+            SortDirection::Descending => " ▼",
+        }
+    }
+}
+
+/// A table of text cells with a selectable row and optional click-to-sort headers.
+///
+/// Rows keep their index in the `rows` passed to `table` while sorted, so
+/// `TableSelection::selected` always refers to the caller's data. Cells that
+/// parse as numbers are compared numerically.
+///
+/// # Example
+/// ```ignore
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     let rows = self.processes.iter().map(|p| vec![p.name.clone(), p.cpu.to_string()]);
+///     table(["Name", "CPU %"], rows)
+///         .widths([Constraint::Fill(1), Constraint::Length(8)])
+///         .sortable()
+///         .state(&self.selection)
+/// }
+///
+/// fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+///     self.selection.handle_event(&event);
+///     None
+/// }
+/// ```
+pub struct TableView {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    widths: Vec<Constraint>,
+    sortable: bool,
+    selection: TableSelection,
+    /// Whether the selection is kept by the component, see `state`.
+    stateful: bool,
+    header_style: Option<Style>,
+    highlight_style: Option<Style>,
+    highlight_symbol: Option<String>,
+}
+
+pub fn table<H, R, C>(headers: H, rows: R) -> TableView
+where
+    H: IntoIterator,
+    H::Item: Into<String>,
+    R: IntoIterator<Item = C>,
+    C: IntoIterator,
+    C::Item: Into<String>,
+{
+    TableView {
+        headers: headers.into_iter().map(Into::into).collect(),
+        rows: rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect())
+            .collect(),
+        widths: Vec::new(),
+        sortable: false,
+        selection: TableSelection::new(),
+        stateful: false,
+        header_style: None,
+        highlight_style: None,
+        highlight_symbol: None,
+    }
+}
+
+impl TableView {
+    /// Column widths. Columns share the width equally by default.
+    pub fn widths<I>(mut self, widths: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint>,
+    {
+        self.widths = widths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Let the user sort by clicking a column header. Clicking the sorted
+    /// column again reverses the direction.
+    pub fn sortable(mut self) -> Self {
+        self.sortable = true;
+        self
+    }
+
+    /// Share selection, sorting and scroll position with a `TableSelection` kept by the component.
+    /// The mouse wheel then moves the selection while the pointer is over the table.
+    pub fn state(mut self, selection: &TableSelection) -> Self {
+        self.selection = TableSelection::clone(selection);
+        self.stateful = true;
+        self
+    }
+
+    /// Select the row at `index` in the unsorted `rows`.
+    pub fn selected(self, index: usize) -> Self {
+        self.selection.select(Some(index));
+        self
+    }
+
+    /// Style of the header row. Defaults to bold in the theme's accent color.
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = Some(style);
+        self
+    }
+
+    /// Style of the selected row. Defaults to the theme's primary color, reversed.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = Some(style);
+        self
+    }
+
+    /// Symbol drawn in front of the selected row.
+    pub fn highlight_symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.highlight_symbol = Some(symbol.into());
+        self
+    }
+}
+
+/// Compare two cells, numerically if both are numbers.
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+impl Element for TableView {
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = crate::theme::current();
+        let column_count = self.rows.iter().map(Vec::len).chain([self.headers.len()]).max().unwrap_or(0);
+        let widths = if self.widths.is_empty() {
+            vec![Constraint::Fill(1); column_count]
+        } else {
+            self.widths.clone()
+        };
+
+        if self.stateful {
+            let selection = TableSelection::clone(&self.selection);
+            scroll::record(area, Arc::new(move |direction| selection.scroll(direction)));
+        }
+        let mut inner = self.selection.lock();
+
+        // Display order of the rows, as indices into `self.rows`
+        let mut order: Vec<usize> = (0..self.rows.len()).collect();
+        if let Some((column, direction)) = inner.sort {
+            let empty = String::new();
+            order.sort_by(|&a, &b| {
+                let a = self.rows[a].get(column).unwrap_or(&empty);
+                let b = self.rows[b].get(column).unwrap_or(&empty);
+                match direction {
+                    SortDirection::Ascending => compare_cells(a, b),
+                    SortDirection::Descending => compare_cells(b, a),
+                }
+            });
+        }
+
+        let selected_row = inner.selected.map(|row| row.min(self.rows.len().saturating_sub(1)));
+        inner.selected = if self.rows.is_empty() { None } else { selected_row };
+        let display_selected = inner.selected.and_then(|row| order.iter().position(|&r| r == row));
+        inner.state.select(display_selected);
+
+        // Column bounds, computed the way `Table` lays them out, for header clicks
+        let symbol_width = match (&self.highlight_symbol, display_selected) {
+            (Some(symbol), Some(_)) => Line::from(symbol.as_str()).width() as u16,
+            _ => 0,
+        };
+        let columns_area = Rect {
+            x: area.x + symbol_width.min(area.width),
+            width: area.width.saturating_sub(symbol_width),
+            ..area
+        };
+        inner.columns = Layout::horizontal(widths.clone())
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(columns_area)
+            .iter()
+            .map(|rect| (rect.x, rect.width))
+            .collect();
+        inner.sortable = self.sortable;
+        inner.area = area;
+        inner.order = order;
+
+        let headers = self.headers.iter().enumerate().map(|(i, header)| {
+            match inner.sort {
+                Some((column, direction)) if column == i => Cell::from(format!("{header}{}", direction.indicator())),
+                _ => Cell::from(header.clone()),
+            }
+        });
+        let header_style = self.header_style.unwrap_or_else(|| {
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+        });
+        let highlight_style = self.highlight_style.unwrap_or_else(|| match theme.primary {
+            Color::Reset => Style::default().add_modifier(Modifier::REVERSED),
+            color => Style::default().fg(color).add_modifier(Modifier::REVERSED),
+        });
+        let rows = inner.order.iter().map(|&i| Row::new(self.rows[i].clone()));
+
+        let mut widget = Table::new(rows, widths)
+            .header(Row::new(headers).style(header_style))
+            .row_highlight_style(highlight_style);
+        if let Some(symbol) = &self.highlight_symbol {
+            widget = widget.highlight_symbol(symbol.as_str());
+        }
+        frame.render_stateful_widget(widget, area, &mut inner.state);
+    }
+}
+
+struct SelectionInner {
+    state: TableState,
+    /// Selected row as an index into the unsorted rows.
+    selected: Option<usize>,
+    /// Sorted column and direction.
+    sort: Option<(usize, SortDirection)>,
+    /// Layout of the last frame, for keyboard and mouse handling.
+    order: Vec<usize>,
+    columns: Vec<(u16, u16)>,
+    area: Rect,
+    sortable: bool,
+}
+
+/// Selection, sorting and scroll state of a `TableView`, kept by the component across frames.
+///
+/// Clones share the same state, so the component can hand a clone to the table
+/// each frame and still see what the user selected.
+#[derive(Clone)]
+pub struct TableSelection {
+    inner: Arc<Mutex<SelectionInner>>,
+}
+
+impl Default for TableSelection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TableSelection {
+    /// A selection starting at the first row, unsorted.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SelectionInner {
+                state: TableState::default(),
+                selected: Some(0),
+                sort: None,
+                order: Vec::new(),
+                columns: Vec::new(),
+                area: Rect::default(),
+                sortable: false,
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SelectionInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Index of the selected row in the unsorted rows, if any.
+    pub fn selected(&self) -> Option<usize> {
+        self.lock().selected
+    }
+
+    /// Select a row by its index in the unsorted rows, or clear the selection with `None`.
+    pub fn select(&self, index: Option<usize>) {
+        self.lock().selected = index;
+    }
+
+    /// The sorted column and direction, if sorted.
+    pub fn sort(&self) -> Option<(usize, SortDirection)> {
+        self.lock().sort
+    }
+
+    /// Sort by a column, or restore the original order with `None`.
+    pub fn sort_by(&self, sort: Option<(usize, SortDirection)>) {
+        self.lock().sort = sort;
+    }
+
+    /// Handle navigation keys: Up/Down, Home/End and PageUp/PageDown.
+    /// Returns true if the key was consumed.
+    pub fn handle_key(&self, key: &KeyEvent) -> bool {
+        let mut inner = self.lock();
+        let len = inner.order.len();
+        if len == 0 {
+            return false;
+        }
+        // The header takes one line
+        let page = inner.area.height.saturating_sub(1).max(1) as usize;
+        let current = inner.selected
+            .and_then(|row| inner.order.iter().position(|&r| r == row))
+            .unwrap_or(0);
+        let next = match key.code {
+            KeyCode::Up => current.saturating_sub(1),
+            KeyCode::Down => current + 1,
+            KeyCode::Home => 0,
+            KeyCode::End => len - 1,
+            KeyCode::PageUp => current.saturating_sub(page),
+            KeyCode::PageDown => current + page,
+            _ => return false,
+        };
+        inner.selected = Some(inner.order[next.min(len - 1)]);
+        true
+    }
+
+    /// Handle the mouse wheel, row clicks and (for sortable tables) header clicks
+    /// inside the table's last drawn area. Returns true if the event was consumed.
+    pub fn handle_mouse(&self, mouse: &MouseEvent) -> bool {
+        let mut inner = self.lock();
+        let area = inner.area;
+        if !area.contains(Position::new(mouse.column, mouse.row)) {
+            return false;
+        }
+        let len = inner.order.len();
+        let current = inner.selected
+            .and_then(|row| inner.order.iter().position(|&r| r == row))
+            .unwrap_or(0);
+        let next = match mouse.kind {
+            MouseEventKind::ScrollUp => current.saturating_sub(1),
+            MouseEventKind::ScrollDown => current + 1,
+            MouseEventKind::Down(MouseButton::Left) if mouse.row == area.y => {
+                if !inner.sortable {
+                    return false;
+                }
+                let Some(column) = inner.columns.iter().position(|&(x, width)| {
+                    mouse.column >= x && mouse.column < x + width
+                }) else {
+                    return false;
+                };
+                inner.sort = match inner.sort {
+                    Some((c, SortDirection::Ascending)) if c == column => Some((column, SortDirection::Descending)),
+                    _ => Some((column, SortDirection::Ascending)),
+                };
+                return true;
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let row = inner.state.offset() + (mouse.row - area.y - 1) as usize;
+                if row >= len {
+                    return false;
+                }
+                row
+            }
+            _ => return false,
+        };
+        if len == 0 {
+            return false;
+        }
+        inner.selected = Some(inner.order[next.min(len - 1)]);
+        true
+    }
+
+    /// Move the selection one row up or down in display order.
+    pub fn scroll(&self, direction: ScrollDirection) {
+        let mut inner = self.lock();
+        let len = inner.order.len();
+        if len == 0 {
+            return;
+        }
+        let current = inner.selected
+            .and_then(|row| inner.order.iter().position(|&r| r == row))
+            .unwrap_or(0);
+        let next = match direction {
+            ScrollDirection::Up => current.saturating_sub(1),
+            ScrollDirection::Down => (current + 1).min(len - 1),
+            ScrollDirection::Left | ScrollDirection::Right => return,
+        };
+        inner.selected = Some(inner.order[next]);
+    }
+
+    /// Handle a key or mouse event. Returns true if the event was consumed.
+    pub fn handle_event(&self, event: &Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Mouse(mouse) => self.handle_mouse(mouse),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn header_clicks_sort_and_selection_tracks_the_row() {
+        let selection = TableSelection::new();
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let mut draw = || {
+            let rows = [["b", "10"], ["a", "9"], ["c", "100"]];
+            let mut element = table(["Name", "Size"], rows).sortable().state(&selection);
+            terminal.draw(|frame| element.render(frame, frame.area())).unwrap();
+            terminal.backend().buffer().clone()
+        };
+
+        draw();
+        // Select "c", then sort by size: it stays selected and moves to the bottom
+        assert!(selection.handle_key(&KeyEvent::new(KeyCode::End, KeyModifiers::NONE)));
+        assert_eq!(selection.selected(), Some(2));
+        assert!(selection.handle_mouse(&click(12, 0)));
+        assert_eq!(selection.sort(), Some((1, SortDirection::Ascending)));
+
+        let buffer = draw();
+        let line = |y: u16| (0..20).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert!(line(0).contains("Size ▲"));
+        assert!(line(1).starts_with('a'));
+        assert!(line(3).starts_with('c'));
+
+        // Clicking a row selects it by its original index
+        assert!(selection.handle_mouse(&click(0, 1)));
+        assert_eq!(selection.selected(), Some(1));
+        assert!(selection.handle_mouse(&click(12, 0)));
+        assert_eq!(selection.sort(), Some((1, SortDirection::Descending)));
+    }
+}
@@ -3,11 +3,15 @@ use ratatui::widgets::{Paragraph, Wrap};
 use crate::element::{Element, IntoElement};
 
 pub struct Text {
-    pub content: String,
+    /// The lines to draw. Spans keep their own styles; `style` applies under them.
+    pub content: ratatui::text::Text<'static>,
     pub style: Style,
     pub style_fn: Option<Box<dyn Fn(Style) -> Style + Send + Sync>>,
     pub alignment: Alignment,
     pub wrap: bool,
+    /// Whether the height is measured from the wrapped content: `None` measures
+    /// wrapped text only, `Some(false)` is set by a fixed `h`/`h_full`.
+    pub auto_height: Option<bool>,
     pub width_constraint: Constraint,
     pub height_constraint: Constraint,
 }
@@ -19,16 +23,52 @@ pub fn text(content: impl Into<String>) -> Text {
 impl Text {
     pub fn new(content: impl Into<String>) -> Self {
         Self {
-            content: content.into(),
+            content: ratatui::text::Text::raw(content.into()),
             style: Style::default(),
             style_fn: None,
             alignment: Alignment::Left,
             wrap: false,
+            auto_height: None,
             width_constraint: Constraint::Min(0), // Default to flex
             height_constraint: Constraint::Length(1),
         }
     }
 
+    /// A single line of styled fragments.
+    ///
+    /// ```ignore
+    /// Text::from_spans([Span::raw("CPU "), Span::styled("42%", Style::new().green())])
+    /// ```
+    pub fn from_spans(spans: impl IntoIterator<Item = Span<'static>>) -> Self {
+        Self::from_lines([Line::from_iter(spans)])
+    }
+
+    /// Several lines, each with its own spans and alignment.
+    pub fn from_lines(lines: impl IntoIterator<Item = Line<'static>>) -> Self {
+        let mut text = Self::new(String::new());
+        text.content = ratatui::text::Text::from_iter(lines);
+        text
+    }
+
+    /// Append a fragment with its own style (or color) to the last line. A
+    /// line break in `content` starts a new line.
+    ///
+    /// ```ignore
+    /// text("Status: ").span("OK", theme.success).span(" / 3 warnings", theme.warning).bold()
+    /// ```
+    pub fn span(mut self, content: impl Into<String>, style: impl Into<Style>) -> Self {
+        let style = style.into();
+        for (i, part) in content.into().split('\n').enumerate() {
+            if i > 0 {
+                self.content.push_line(Line::default());
+            }
+            if !part.is_empty() {
+                self.content.push_span(Span::styled(part.to_string(), style));
+            }
+        }
+        self
+    }
+
     pub fn w_full(mut self) -> Self {
         self.width_constraint = Constraint::Percentage(100);
         self
@@ -36,6 +76,7 @@ impl Text {
 
     pub fn h_full(mut self) -> Self {
         self.height_constraint = Constraint::Percentage(100);
+        self.auto_height = Some(false);
         self
     }
 
@@ -44,8 +85,30 @@ impl Text {
         self
     }
 
+    /// A fixed height. Wrapped text longer than this is clipped.
     pub fn h(mut self, length: u16) -> Self {
         self.height_constraint = Constraint::Length(length);
+        self.auto_height = Some(false);
+        self
+    }
+
+    /// Take as many lines as the content needs at the width it is laid out
+    /// with, also when not wrapping (one per line of the content).
+    pub fn h_auto(mut self) -> Self {
+        self.auto_height = Some(true);
+        self
+    }
+
+    /// Wrap long lines at word boundaries. Unless the height was fixed with
+    /// `h`/`h_full`, a wrapped text is as tall as its wrapped lines.
+    pub fn wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    /// Cut long lines at the edge of the area (the default).
+    pub fn no_wrap(mut self) -> Self {
+        self.wrap = false;
         self
     }
 
@@ -77,6 +140,19 @@ impl Text {
     }
 }
 
+impl Text {
+    fn paragraph(&self) -> Paragraph<'_> {
+        let p = Paragraph::new(self.content.clone())
+            .style(self.style)
+            .alignment(self.alignment);
+        if self.wrap {
+            p.wrap(Wrap { trim: true })
+        } else {
+            p
+        }
+    }
+}
+
 impl Element for Text {
     fn width(&self) -> Constraint {
         self.width_constraint
@@ -86,16 +162,24 @@ impl Element for Text {
         self.height_constraint
     }
 
-    fn render(&mut self, frame: &mut Frame, area: Rect) {
-        let mut p = Paragraph::new(self.content.clone())
-            .style(self.style)
-            .alignment(self.alignment);
-        
-        if self.wrap {
-             p = p.wrap(Wrap { trim: true });
+    fn measure_height(&self, width: u16) -> Option<u16> {
+        if !self.auto_height.unwrap_or(self.wrap) {
+            return None;
+        }
+        let lines = self.paragraph().line_count(width.max(1));
+        Some(lines.min(u16::MAX as usize) as u16)
+    }
+
+    fn measure_width(&self) -> Option<u16> {
+        // Texts with an explicit width keep it
+        if self.width_constraint != Constraint::Min(0) {
+            return None;
         }
+        Some(self.paragraph().line_width().min(u16::MAX as usize) as u16)
+    }
 
-        frame.render_widget(p, area);
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(self.paragraph(), area);
     }
 }
 
@@ -112,3 +196,55 @@ impl IntoElement for &str {
         Text::new(self.to_string())
     }
 }
+
+impl IntoElement for Line<'static> {
+    type Element = Text;
+    fn into_element(self) -> Self::Element {
+        Text::from_lines([self])
+    }
+}
+
+impl IntoElement for Span<'static> {
+    type Element = Text;
+    fn into_element(self) -> Self::Element {
+        Text::from_spans([self])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::div;
+    use crate::testing::buffer_lines;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn wrapped_text_takes_the_height_it_needs() {
+        let draw = |help: Text| {
+            let mut terminal = Terminal::new(TestBackend::new(12, 5)).unwrap();
+            let mut page = div().child(help).child(text("footer").h(1));
+            terminal.draw(|frame| page.render(frame, frame.area())).unwrap();
+            buffer_lines(terminal.backend().buffer())
+        };
+
+        let lines = draw(text("press q to quit the app").wrap());
+        assert_eq!(lines[..4], ["press q to", "quit the app", "footer", ""]);
+
+        // A fixed height wins, in either order
+        assert_eq!(draw(text("press q to quit the app").h(1).wrap())[..2], ["press q to", "footer"]);
+        assert_eq!(draw(text("a\nb").h_auto())[..3], ["a", "b", "footer"]);
+    }
+
+    #[test]
+    fn spans_keep_their_colors_under_the_text_style() {
+        let mut label = text("ok: ").span("3", Color::Green).span(" / 1\nnext", Color::Red).bold().align_center();
+        let mut terminal = Terminal::new(TestBackend::new(11, 2)).unwrap();
+        terminal.draw(|frame| label.render(frame, frame.area())).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer_lines(buffer), [" ok: 3 / 1", "   next"]);
+        assert_eq!(buffer[(5, 0)].fg, Color::Green);
+        assert_eq!(buffer[(7, 0)].fg, Color::Red);
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::BOLD) && buffer[(5, 0)].modifier.contains(Modifier::BOLD));
+        assert_eq!(Span::raw("ab").into_element().measure_width(), Some(2));
+    }
+}
@@ -0,0 +1,202 @@
+use ratatui::prelude::*;
+use ratatui::widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap};
+use crate::element::Element;
+use crate::element::scroll;
+use crate::element::scroll_view::ScrollState;
+use std::sync::Arc;
+
+/// A read-only, scrollable view of a long string such as a file, command
+/// output or a JSON payload.
+///
+/// Only the lines in the viewport are formatted, so content many times larger
+/// than the terminal draws as quickly as a screenful. To keep the scroll
+/// position across frames and react to keys and the mouse, keep a
+/// `ScrollState` in the component and pass it with `state`.
+///
+/// # Example
+/// ```ignore
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     text_viewer(Arc::clone(&self.output)).line_numbers().wrap().state(&self.scroll)
+/// }
+///
+/// fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+///     self.scroll.handle_event(&event);
+///     None
+/// }
+/// ```
+pub struct TextViewer {
+    content: Arc<str>,
+    scroll: ScrollState,
+    /// Whether the scroll position is kept by the component, see `state`.
+    stateful: bool,
+    wrap: bool,
+    line_numbers: bool,
+    style: Style,
+}
+
+/// Create a viewer for `content`. Passing an `Arc<str>` avoids copying large
+/// content every frame.
+pub fn text_viewer(content: impl Into<Arc<str>>) -> TextViewer {
+    TextViewer {
+        content: content.into(),
+        scroll: ScrollState::new(),
+        stateful: false,
+        wrap: false,
+        line_numbers: false,
+        style: Style::default(),
+    }
+}
+
+impl TextViewer {
+    /// Share the scroll position with a `ScrollState` kept by the component.
+    /// The mouse wheel then scrolls while the pointer is over the viewer.
+    pub fn state(mut self, scroll: &ScrollState) -> Self {
+        self.scroll = ScrollState::clone(scroll);
+        self.stateful = true;
+        self
+    }
+
+    /// Wrap long lines instead of cutting them off at the right edge. The
+    /// scroll position still moves by whole lines of the content.
+    pub fn wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    /// Show line numbers in a gutter on the left.
+    pub fn line_numbers(mut self) -> Self {
+        self.line_numbers = true;
+        self
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.style = self.style.fg(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.style = self.style.bg(color);
+        self
+    }
+
+    /// Rows `line` takes when drawn `width` columns wide.
+    fn rows(&self, line: &str, width: u16) -> usize {
+        if self.wrap && !line.is_empty() {
+            Paragraph::new(line).wrap(Wrap { trim: false }).line_count(width.max(1)).max(1)
+        } else {
+            1
+        }
+    }
+
+    /// The first line to show so that the end of the content fills the last
+    /// `height` rows. Only measures lines from the end, as far as they fit.
+    fn max_offset(&self, len: usize, width: u16, height: u16) -> usize {
+        if !self.wrap {
+            return len.saturating_sub(height as usize);
+        }
+        let mut rows = 0;
+        let mut first = len;
+        for line in self.content.lines().rev() {
+            rows += self.rows(line, width);
+            if rows > height as usize {
+                break;
+            }
+            first -= 1;
+        }
+        first.min(len.saturating_sub(1))
+    }
+}
+
+impl Element for TextViewer {
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        if area.is_empty() {
+            return;
+        }
+        frame.buffer_mut().set_style(area, self.style);
+        if self.stateful {
+            let scroll = ScrollState::clone(&self.scroll);
+            scroll::record(area, Arc::new(move |direction| scroll.scroll(direction)));
+        }
+
+        let len = self.content.lines().count();
+        let gutter = if self.line_numbers { len.max(1).to_string().len() as u16 + 1 } else { 0 };
+        // Assume a scrollbar while measuring; without one there is nothing to scroll
+        let text_width = area.width.saturating_sub(gutter + 1);
+        let max_offset = self.max_offset(len, text_width, area.height);
+        let text_width = if max_offset == 0 { text_width + 1 } else { text_width };
+
+        let offset = self.scroll.layout(max_offset, area);
+
+        let muted = Style::default().fg(crate::theme::current().muted);
+        let mut y = area.y;
+        for (index, line) in self.content.lines().enumerate().skip(offset) {
+            if y >= area.bottom() {
+                break;
+            }
+            let rows = (self.rows(line, text_width) as u16).min(area.bottom() - y);
+            if self.line_numbers {
+                let number = format!("{:>width$}", index + 1, width = gutter as usize - 1);
+                frame.buffer_mut().set_stringn(area.x, y, number, gutter as usize, muted);
+            }
+            let mut paragraph = Paragraph::new(line);
+            if self.wrap {
+                paragraph = paragraph.wrap(Wrap { trim: false });
+            }
+            frame.render_widget(paragraph, Rect::new(area.x + gutter, y, text_width, rows));
+            y += rows;
+        }
+
+        if max_offset > 0 {
+            let mut state = ScrollbarState::new(max_offset + 1)
+                .position(offset)
+                .viewport_content_length(area.height as usize);
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .thumb_style(muted);
+            frame.render_stateful_widget(scrollbar, area, &mut state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::buffer_lines;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn keys_scroll_by_line_and_page_within_the_content() {
+        let content: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+        let content: Arc<str> = content.into();
+        let scroll = ScrollState::new();
+        let mut terminal = Terminal::new(TestBackend::new(12, 3)).unwrap();
+        let mut draw = |wrap: bool| {
+            let mut viewer = text_viewer(Arc::clone(&content)).line_numbers().state(&scroll);
+            if wrap {
+                viewer = viewer.wrap();
+            }
+            terminal.draw(|frame| viewer.render(frame, frame.area())).unwrap();
+            buffer_lines(terminal.backend().buffer())
+        };
+        let press = |code| scroll.handle_key(&KeyEvent::new(code, KeyModifiers::NONE));
+
+        assert_eq!(draw(false), [" 1 line 1  █", " 2 line 2  ║", " 3 line 3  ║"]);
+        assert!(press(KeyCode::PageDown) && press(KeyCode::Down));
+        assert_eq!(draw(false)[0], " 5 line 5  ║");
+        assert!(press(KeyCode::End));
+        assert_eq!(draw(false), ["18 line 18 ║", "19 line 19 ║", "20 line 20 █"]);
+        assert!(scroll.is_at_bottom());
+        assert!(!press(KeyCode::Char('x')));
+
+        // Wrapped, the last line fills two rows of the narrow viewport
+        let content: Arc<str> = "short\na long last line".into();
+        scroll.scroll_to_bottom();
+        let mut viewer = text_viewer(content).wrap().state(&scroll);
+        let mut terminal = Terminal::new(TestBackend::new(12, 2)).unwrap();
+        terminal.draw(|frame| viewer.render(frame, frame.area())).unwrap();
+        assert_eq!(scroll.offset(), 1);
+        assert_eq!(buffer_lines(terminal.backend().buffer()), ["a long last║", "line       █"]);
+    }
+}
@@ -14,6 +14,19 @@ pub enum Error {
 
     #[snafu(display("Task execution error"))]
     TaskError,
+
+    /// A task was aborted before it produced its result, see `TaskHandleResult`.
+    #[snafu(display("Task was cancelled"))]
+    Cancelled,
+
+    /// An entity was updated inside its own `read` or `update` closure (or read
+    /// inside its own `update`), which would deadlock.
+    #[snafu(display("Entity {id} is already locked by this thread; nested access would deadlock"))]
+    Reentrant { id: crate::state::EntityId },
+
+    /// A key description such as "ctrl+s" could not be parsed, see `keymap::parse_key`.
+    #[snafu(display("Invalid key description: {key:?}"))]
+    InvalidKey { key: String },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
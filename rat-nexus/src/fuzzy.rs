@@ -0,0 +1,94 @@
+//! Fuzzy matching for filtering lists by what the user typed.
+//!
+//! A query matches when its characters appear in the candidate in order, not
+//! necessarily next to each other ("gtm" matches "Go to Monitor"). Matches are
+//! scored so the likeliest candidates can be listed first, and the matched
+//! positions are returned so they can be highlighted:
+//!
+//! ```ignore
+//! let mut matches: Vec<_> = names
+//!     .iter()
+//!     .filter_map(|name| fuzzy_match(&query, name).map(|(score, indices)| (score, name, indices)))
+//!     .collect();
+//! matches.sort_by_key(|(score, ..)| std::cmp::Reverse(*score));
+//! let items = matches.iter().map(|(_, name, indices)| highlight_matches(name, indices, Style::default(), bold));
+//! ```
+
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+
+/// Match `query` against `candidate`, ignoring case and whitespace in the query.
+///
+/// Returns the score (higher is better) and the char indices of the matched
+/// characters in `candidate`, or `None` if the query doesn't match. Consecutive
+/// characters and characters at the start of a word score higher, and matches
+/// near the start are preferred. An empty query matches everything with score 0.
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<(i32, Vec<usize>)> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut indices = Vec::new();
+    let mut position = 0;
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let offset = candidate[position..]
+            .iter()
+            .position(|c| c.to_lowercase().eq(wanted.to_lowercase()))?;
+        let index = position + offset;
+        score += 1;
+        if indices.last().is_some_and(|&previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= offset.min(5) as i32;
+        indices.push(index);
+        position = index + 1;
+    }
+    Some((score, indices))
+}
+
+/// A line showing `candidate` in `style`, with the chars at `indices` (as
+/// returned by `fuzzy_match`) in `matched` instead.
+pub fn highlight_matches(candidate: &str, indices: &[usize], style: Style, matched: Style) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in candidate.chars().enumerate() {
+        let is_match = indices.contains(&i);
+        if is_match != run_matched && !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut run), if run_matched { matched } else { style }));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_matched { matched } else { style }));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Modifier;
+
+    #[test]
+    fn word_starts_and_runs_rank_first_and_positions_are_reported() {
+        let names = ["Toggle theme", "Go to Menu", "Go to Monitor", "Logs"];
+        let mut ranked: Vec<_> = names
+            .iter()
+            .filter_map(|name| fuzzy_match("to", name).map(|(score, _)| (score, *name)))
+            .collect();
+        ranked.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        assert_eq!(ranked.iter().map(|&(_, name)| name).collect::<Vec<_>>(), ["Toggle theme", "Go to Menu", "Go to Monitor"]);
+
+        assert_eq!(fuzzy_match("GTM", "Go to Monitor").unwrap().1, [0, 3, 6]);
+        assert_eq!(fuzzy_match("", "anything"), Some((0, Vec::new())));
+        assert!(fuzzy_match("xyz", "Go to Monitor").is_none());
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let line = highlight_matches("Go to", &[0, 3], Style::default(), bold);
+        let spans: Vec<_> = line.spans.iter().map(|span| (span.content.as_ref(), span.style == bold)).collect();
+        assert_eq!(spans, [("G", true), ("o ", false), ("t", true), ("o", false)]);
+    }
+}
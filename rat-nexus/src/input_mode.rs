@@ -0,0 +1,163 @@
+//! Normal and insert input modes.
+//!
+//! Components with a text input that is entered with a key ("press `/` to
+//! search") keep a `ModalInput` and let it route events: keys switch modes,
+//! and everything else comes back tagged with the mode it belongs to, so
+//! `handle_event` splits into one handler per mode.
+//!
+//! ```ignore
+//! fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+//!     match self.input.route(event) {
+//!         ModeEvent::Normal(event) => self.handle_normal(event, cx),
+//!         ModeEvent::Insert(event) => self.handle_insert(event, cx),
+//!         ModeEvent::Switched(_) => None,
+//!     }
+//! }
+//!
+//! fn captures_key(&self, key: &KeyEvent) -> bool {
+//!     self.input.captures_key(key)
+//! }
+//! ```
+
+use crate::component::traits::Event;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Whether keys are commands or text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InputMode {
+    /// Keys are commands.
+    #[default]
+    Normal,
+    /// Keys are typed into an input.
+    Insert,
+}
+
+/// An event routed by `ModalInput::route`.
+#[derive(Debug, Clone)]
+pub enum ModeEvent {
+    /// An event to handle as a command.
+    Normal(Event),
+    /// An event to handle as input: typed keys and pastes.
+    Insert(Event),
+    /// The event switched to this mode and needs no further handling.
+    Switched(InputMode),
+}
+
+/// The input mode of a component and the keys that switch it.
+///
+/// `/` enters insert mode and Esc or Enter leave it, unless configured otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModalInput {
+    mode: InputMode,
+    enter_keys: Vec<KeyCode>,
+    leave_keys: Vec<KeyCode>,
+}
+
+impl Default for ModalInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModalInput {
+    pub fn new() -> Self {
+        Self {
+            mode: InputMode::Normal,
+            enter_keys: vec![KeyCode::Char('/')],
+            leave_keys: vec![KeyCode::Esc, KeyCode::Enter],
+        }
+    }
+
+    /// Keys that enter insert mode from normal mode.
+    pub fn enter_on(mut self, keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        self.enter_keys = keys.into_iter().collect();
+        self
+    }
+
+    /// Keys that go back to normal mode.
+    pub fn leave_on(mut self, keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        self.leave_keys = keys.into_iter().collect();
+        self
+    }
+
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: InputMode) {
+        self.mode = mode;
+    }
+
+    pub fn is_insert(&self) -> bool {
+        self.mode == InputMode::Insert
+    }
+
+    /// Switch modes on the enter and leave keys and tag other events with
+    /// the current mode. Pastes in normal mode are passed on as `Normal`.
+    pub fn route(&mut self, event: Event) -> ModeEvent {
+        if let Event::Key(key) = &event {
+            let keys = match self.mode {
+                InputMode::Normal => &self.enter_keys,
+                InputMode::Insert => &self.leave_keys,
+            };
+            if key.modifiers.difference(KeyModifiers::SHIFT).is_empty() && keys.contains(&key.code) {
+                self.mode = match self.mode {
+                    InputMode::Normal => InputMode::Insert,
+                    InputMode::Insert => InputMode::Normal,
+                };
+                return ModeEvent::Switched(self.mode);
+            }
+        }
+        match self.mode {
+            InputMode::Normal => ModeEvent::Normal(event),
+            InputMode::Insert => ModeEvent::Insert(event),
+        }
+    }
+
+    /// Whether to keep `key` from global bindings: in insert mode, printable
+    /// characters, editing keys and the leave keys are text. Keys with Ctrl or
+    /// Alt still reach global bindings.
+    pub fn captures_key(&self, key: &KeyEvent) -> bool {
+        if self.mode != InputMode::Insert {
+            return false;
+        }
+        if !key.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+            return false;
+        }
+        matches!(
+            key.code,
+            KeyCode::Char(_)
+                | KeyCode::Backspace
+                | KeyCode::Delete
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Home
+                | KeyCode::End
+        ) || self.leave_keys.contains(&key.code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn keys_switch_modes_and_printable_keys_are_captured_while_inserting() {
+        let mut input = ModalInput::new();
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(matches!(input.route(key(KeyCode::Char('q'))), ModeEvent::Normal(_)));
+        assert!(!input.captures_key(&q));
+
+        assert!(matches!(input.route(key(KeyCode::Char('/'))), ModeEvent::Switched(InputMode::Insert)));
+        assert!(matches!(input.route(key(KeyCode::Char('/'))), ModeEvent::Insert(_)));
+        assert!(input.captures_key(&q));
+        assert!(!input.captures_key(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+
+        assert!(matches!(input.route(key(KeyCode::Enter)), ModeEvent::Switched(InputMode::Normal)));
+        assert!(!input.is_insert());
+    }
+}
@@ -0,0 +1,476 @@
+//! Key binding descriptions for components.
+//!
+//! Components describe the keys they respond to with `KeyBindings`. The
+//! framework uses these descriptions to build the help overlay and footer hints,
+//! so the list of shortcuts has a single source of truth.
+//!
+//! Apps that let users remap keys keep a `KeyConfig` of named actions instead
+//! of matching fixed keys, and merge the user's overrides over their defaults:
+//!
+//! ```ignore
+//! let defaults = KeyConfig::new()
+//!     .bind("save", KeyChord::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+//!     .bind("quit", KeyChord::from(KeyCode::Char('q')));
+//! // With the `config` feature; the file holds e.g. {"save": "ctrl+w"}
+//! let keys = defaults.merge(KeyConfig::load("keys.json").unwrap_or_default());
+//!
+//! // In `handle_event`
+//! match keys.action_for(&key) {
+//!     Some("save") => self.save(),
+//!     Some("quit") => return Some(Action::Quit),
+//!     _ => {}
+//! }
+//! ```
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single key binding with a human readable description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    /// `None` for an entry that only describes other input, see `KeyBindings::describe`.
+    pub key: Option<KeyCode>,
+    pub modifiers: KeyModifiers,
+    /// Display label override (e.g. "↑/↓" for a binding covering several keys).
+    pub label: Option<String>,
+    pub description: String,
+}
+
+impl KeyBinding {
+    /// Create a binding for a key without modifiers.
+    pub fn new(key: KeyCode, description: impl Into<String>) -> Self {
+        Self {
+            key: Some(key),
+            modifiers: KeyModifiers::NONE,
+            label: None,
+            description: description.into(),
+        }
+    }
+
+    /// Get the label shown for this binding, e.g. "SPACE" or "Ctrl+S".
+    pub fn label(&self) -> String {
+        match (&self.label, self.key) {
+            (Some(label), _) => label.clone(),
+            (None, Some(key)) => key_label(key, self.modifiers),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Whether `event` is this key with exactly these modifiers. Never true for
+    /// a description-only entry.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        self.key.is_some_and(|key| event.code == key && event.modifiers == self.modifiers)
+    }
+}
+
+/// An ordered list of key bindings registered by a component.
+///
+/// # Example
+/// ```ignore
+/// fn key_bindings(&self) -> KeyBindings {
+///     KeyBindings::new()
+///         .bind(KeyCode::Char(' '), "Start/Stop")
+///         .bind_as("↑/↓", KeyCode::Up, "Navigate")
+///         .bind(KeyCode::Char('q'), "Quit")
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: Vec<KeyBinding>,
+}
+
+impl KeyBindings {
+    /// Create an empty set of bindings.
+    pub fn new() -> Self {
+        Self { bindings: Vec::new() }
+    }
+
+    /// Register a key without modifiers.
+    pub fn bind(mut self, key: KeyCode, description: impl Into<String>) -> Self {
+        self.bindings.push(KeyBinding::new(key, description));
+        self
+    }
+
+    /// Register a key with modifiers (e.g. `KeyModifiers::CONTROL`).
+    pub fn bind_with(mut self, key: KeyCode, modifiers: KeyModifiers, description: impl Into<String>) -> Self {
+        self.bindings.push(KeyBinding {
+            modifiers,
+            ..KeyBinding::new(key, description)
+        });
+        self
+    }
+
+    /// Register a key with a custom display label.
+    /// Useful when one entry documents several keys, like "↑/↓" or "Arrow Keys".
+    pub fn bind_as(mut self, label: impl Into<String>, key: KeyCode, description: impl Into<String>) -> Self {
+        self.bindings.push(KeyBinding {
+            label: Some(label.into()),
+            ..KeyBinding::new(key, description)
+        });
+        self
+    }
+
+    /// Describe input that isn't a key, e.g. `describe("Mouse", "Scroll to adjust")`.
+    /// The entry is listed in the help overlay and hints but matches no key.
+    pub fn describe(mut self, label: impl Into<String>, description: impl Into<String>) -> Self {
+        self.bindings.push(KeyBinding {
+            key: None,
+            modifiers: KeyModifiers::NONE,
+            label: Some(label.into()),
+            description: description.into(),
+        });
+        self
+    }
+
+    /// Append all bindings from another set.
+    pub fn extend(mut self, other: KeyBindings) -> Self {
+        self.bindings.extend(other.bindings);
+        self
+    }
+
+    /// Iterate over the registered bindings in order.
+    pub fn iter(&self) -> impl Iterator<Item = &KeyBinding> {
+        self.bindings.iter()
+    }
+
+    /// Get the number of bindings.
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Check if no bindings are registered.
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Format the bindings as a one-line hint, e.g. "SPACE Start/Stop │ Q Quit".
+    pub fn hint_line(&self) -> String {
+        self.bindings
+            .iter()
+            .map(|b| format!("{} {}", b.label(), b.description))
+            .collect::<Vec<_>>()
+            .join(" │ ")
+    }
+}
+
+/// Get the display label for a key, e.g. "SPACE", "↑", "Ctrl+S".
+pub fn key_label(key: KeyCode, modifiers: KeyModifiers) -> String {
+    let mut label = String::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("Ctrl+");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("Alt+");
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        label.push_str("Shift+");
+    }
+
+    let name = match key {
+        KeyCode::Char(' ') => "SPACE".to_string(),
+        KeyCode::Char(c) => c.to_uppercase().to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    };
+    label.push_str(&name);
+    label
+}
+
+/// A key together with its modifiers, written in config files as e.g.
+/// "ctrl+s", "shift+tab", "alt+enter", "f5" or "space".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(key: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Whether `event` is this key with exactly these modifiers.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        event.code == self.key && event.modifiers == self.modifiers
+    }
+
+    /// The label shown in hints, e.g. "Ctrl+S".
+    pub fn label(&self) -> String {
+        key_label(self.key, self.modifiers)
+    }
+}
+
+impl From<KeyCode> for KeyChord {
+    fn from(key: KeyCode) -> Self {
+        Self::new(key, KeyModifiers::NONE)
+    }
+}
+
+/// Parse a key description such as "ctrl+s" into a key code and modifiers.
+///
+/// Modifiers (`ctrl`, `alt`, `shift`, `super`) come first, joined with `+`;
+/// names are case-insensitive. A letter is taken as written on its own, and
+/// otherwise follows `shift` the way terminals report it: "ctrl+S" is Ctrl+s
+/// and "shift+a" is Shift+A.
+pub fn parse_key(description: &str) -> crate::Result<(KeyCode, KeyModifiers)> {
+    let invalid = || crate::Error::InvalidKey { key: description.to_string() };
+    let trimmed = description.trim();
+    // The plus key itself, alone or after modifiers
+    let (modifiers, key) = match trimmed.strip_suffix("++") {
+        Some(modifiers) => (modifiers, "+"),
+        None if trimmed == "+" => ("", "+"),
+        None => trimmed.rsplit_once('+').unwrap_or(("", trimmed)),
+    };
+
+    let mut mods = KeyModifiers::NONE;
+    for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+        mods |= match modifier.trim().to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "option" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            "super" | "cmd" => KeyModifiers::SUPER,
+            _ => return Err(invalid()),
+        };
+    }
+
+    let name = key.trim().to_lowercase();
+    let code = match name.as_str() {
+        "space" => KeyCode::Char(' '),
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "ins" | "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pgup" | "pageup" => KeyCode::PageUp,
+        "pgdn" | "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => {
+            let mut chars = key.trim().chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if mods.contains(KeyModifiers::SHIFT) => KeyCode::Char(c.to_ascii_uppercase()),
+                (Some(c), None) if !mods.is_empty() => KeyCode::Char(c.to_ascii_lowercase()),
+                (Some(c), None) => KeyCode::Char(c),
+                _ => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            }
+        }
+    };
+    Ok((code, mods))
+}
+
+impl FromStr for KeyChord {
+    type Err = crate::Error;
+
+    fn from_str(description: &str) -> crate::Result<Self> {
+        let (key, modifiers) = parse_key(description)?;
+        Ok(Self::new(key, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    /// The form `parse_key` reads, e.g. "ctrl+s".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+            (KeyModifiers::SUPER, "super+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.key {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::SHIFT) => write!(f, "{}", c.to_ascii_lowercase()),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Keys assigned to named actions, e.g. an app's defaults with a user's
+/// remappings merged over them. See the module documentation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyConfig {
+    keys: BTreeMap<String, KeyChord>,
+}
+
+impl KeyConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assign `key` to `action`, replacing its previous key.
+    pub fn bind(mut self, action: impl Into<String>, key: impl Into<KeyChord>) -> Self {
+        self.keys.insert(action.into(), key.into());
+        self
+    }
+
+    /// The key assigned to `action`.
+    pub fn get(&self, action: &str) -> Option<KeyChord> {
+        self.keys.get(action).copied()
+    }
+
+    /// The action `event` is assigned to, if any.
+    pub fn action_for(&self, event: &KeyEvent) -> Option<&str> {
+        self.keys
+            .iter()
+            .find(|(_, key)| key.matches(event))
+            .map(|(action, _)| action.as_str())
+    }
+
+    /// These bindings with the ones in `overrides` taking precedence, e.g.
+    /// `defaults.merge(user_config)`. Actions only in `overrides` are added.
+    pub fn merge(mut self, overrides: KeyConfig) -> Self {
+        self.keys.extend(overrides.keys);
+        self
+    }
+
+    /// Actions and their keys, sorted by action name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, KeyChord)> {
+        self.keys.iter().map(|(action, key)| (action.as_str(), *key))
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Serialized as a map from action name to key description, so it can be
+/// read from any serde format: `{"save": "ctrl+s", "quit": "q"}`.
+#[cfg(feature = "config")]
+impl serde::Serialize for KeyConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.keys.iter().map(|(action, key)| (action, key.to_string())))
+    }
+}
+
+#[cfg(feature = "config")]
+impl<'de> serde::Deserialize<'de> for KeyConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let described = BTreeMap::<String, String>::deserialize(deserializer)?;
+        let keys = described
+            .into_iter()
+            .map(|(action, key)| Ok((action, key.parse().map_err(serde::de::Error::custom)?)))
+            .collect::<Result<_, D::Error>>()?;
+        Ok(Self { keys })
+    }
+}
+
+#[cfg(feature = "config")]
+impl KeyConfig {
+    /// Read bindings from a JSON file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> crate::Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(|source| crate::Error::IoError { source })?;
+        serde_json::from_str(&json).map_err(|e| crate::Error::IoError { source: e.into() })
+    }
+
+    /// Write the bindings to a JSON file, e.g. to give users a template to edit.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> crate::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| crate::Error::IoError { source: e.into() })?;
+        std::fs::write(path, json).map_err(|source| crate::Error::IoError { source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_descriptions_parse_and_print_back() {
+        let ctrl_s = KeyChord::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!("ctrl+s".parse::<KeyChord>().unwrap(), ctrl_s);
+        assert_eq!("Control + S".parse::<KeyChord>().unwrap(), ctrl_s);
+        assert_eq!(parse_key("shift+a").unwrap(), (KeyCode::Char('A'), KeyModifiers::SHIFT));
+        assert_eq!(parse_key("alt+shift+Tab").unwrap(), (KeyCode::Tab, KeyModifiers::ALT | KeyModifiers::SHIFT));
+        assert_eq!(parse_key("ctrl++").unwrap(), (KeyCode::Char('+'), KeyModifiers::CONTROL));
+        assert_eq!(parse_key("F5").unwrap(), (KeyCode::F(5), KeyModifiers::NONE));
+        assert_eq!(parse_key("space").unwrap().0, KeyCode::Char(' '));
+        for invalid in ["", "hyper+x", "f99", "ctrl+nope"] {
+            assert!(matches!(parse_key(invalid), Err(crate::Error::InvalidKey { .. })), "{invalid}");
+        }
+        for description in ["ctrl+s", "shift+a", "alt+enter", "f12", "space", "ctrl++", "pagedown"] {
+            assert_eq!(description.parse::<KeyChord>().unwrap().to_string(), description);
+        }
+    }
+
+    #[test]
+    fn described_input_is_listed_but_matches_no_key() {
+        let bindings = KeyBindings::new()
+            .bind_with(KeyCode::Char('s'), KeyModifiers::CONTROL, "Save")
+            .describe("Mouse", "Scroll to adjust");
+        assert_eq!(bindings.hint_line(), "Ctrl+S Save │ Mouse Scroll to adjust");
+        let matching = |event: KeyEvent| bindings.iter().filter(|b| b.matches(&event)).count();
+        assert_eq!(matching(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)), 1);
+        // Ctrl+Space as some terminals report it
+        assert_eq!(matching(KeyEvent::new(KeyCode::Null, KeyModifiers::NONE)), 0);
+    }
+
+    #[test]
+    fn overrides_replace_defaults_per_action() {
+        let defaults = KeyConfig::new()
+            .bind("save", KeyChord::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .bind("quit", KeyCode::Char('q'));
+        let keys = defaults.merge(KeyConfig::new().bind("save", KeyCode::F(2)));
+        assert_eq!(keys.get("save"), Some(KeyChord::from(KeyCode::F(2))));
+        assert_eq!(keys.action_for(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)), Some("quit"));
+        assert_eq!(keys.action_for(&KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)), None);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn configs_round_trip_through_serde() {
+        let keys: KeyConfig = serde_json::from_str(r#"{"save": "ctrl+s", "next": "shift+tab"}"#).unwrap();
+        assert_eq!(keys.get("next"), Some(KeyChord::new(KeyCode::Tab, KeyModifiers::SHIFT)));
+        let json = serde_json::to_string(&keys).unwrap();
+        assert_eq!(json, r#"{"next":"shift+tab","save":"ctrl+s"}"#);
+        assert!(serde_json::from_str::<KeyConfig>(r#"{"save": "ctrl+"}"#).is_err());
+    }
+}
@@ -4,29 +4,75 @@ pub mod state;
 pub mod router;
 pub mod task;
 pub mod error;
+pub mod clock;
+pub mod animation;
+pub mod theme;
+pub mod color;
+pub mod breakpoint;
+pub mod debug;
+pub mod overlay;
+pub mod palette;
+pub mod fuzzy;
+mod terminal;
+pub mod testing;
+pub mod keymap;
+pub mod input_mode;
+#[cfg(feature = "record")]
+pub mod replay;
 
 pub mod element;
 
 pub use error::{Error, Result};
 
 // Re-export common types for convenience
-pub use application::{Application, AppContext, Context, EventContext};
-pub use component::{Component, traits::{Event, Action, AnyComponent}};
-pub use state::{Entity, WeakEntity, EntityId};
-pub use router::{Route, Router};
-pub use task::{TaskHandle, TaskTracker};
+pub use application::{Application, AppContext, Context, EventContext, MessageSender};
+pub use component::{Component, ErrorBoundary, FrameComponent, Framed, TryComponent, traits::{Event, Action, AnyComponent, CustomAction, Message, TypedEntity, TypedRoute}};
+pub use state::{Entity, EntityList, WeakEntity, EntityId, Lazy, RingBuffer, TypeMap};
+pub use router::{AppRoute, BuildWith, Route, Router};
+pub use task::{BlockingTask, Debounce, IntervalHandle, TaskHandle, TaskHandleResult, TaskTracker};
+pub use keymap::{KeyBinding, KeyBindings, KeyChord, KeyConfig};
+pub use input_mode::{InputMode, ModalInput, ModeEvent};
+pub use clock::{Clock, RealClock, TestClock};
+pub use animation::{tween, Animation, Easing, FixedStep};
+pub use theme::Theme;
+pub use color::{AccessibilityMode, ColorDepth};
+pub use breakpoint::{Breakpoint, Breakpoints};
+pub use debug::{DebugStats, RenderStats};
+pub use overlay::OverlayId;
+pub use palette::CommandPalette;
+pub use fuzzy::{fuzzy_match, highlight_matches};
+pub use terminal::{request_cursor_shape, CursorShape};
 pub use element::{Element, IntoElement, div, text, Div, Text};
 
 // Re-export paste for macro usage
 pub use paste;
+// Re-export crossterm so macro-generated code and apps agree on event types
+pub use crossterm;
 
 pub mod prelude {
-    pub use crate::application::{Application, AppContext, Context, EventContext};
-    pub use crate::component::{Component, traits::{Event, Action, AnyComponent}};
-    pub use crate::state::{Entity, WeakEntity, EntityId};
-    pub use crate::router::{Route, Router};
-    pub use crate::element::{Element, IntoElement, div, text, Div, Text, widget, WidgetElement, canvas, Canvas};
-    pub use crate::task::{TaskHandle, TaskTracker};
+    pub use crate::application::{Application, AppContext, Context, EventContext, MessageSender};
+    pub use crate::component::{Component, ErrorBoundary, FrameComponent, Framed, TryComponent, traits::{Event, Action, AnyComponent, CustomAction, Message, TypedEntity, TypedRoute}};
+    pub use crate::state::{Entity, EntityList, WeakEntity, EntityId, RingBuffer};
+    pub use crate::router::{AppRoute, BuildWith, Route, Router};
+    pub use crate::element::{Element, IntoElement, div, text, Div, Text, widget, WidgetElement, canvas, canvas_measured, Canvas, CanvasWorld, centered, centered_popup, centered_rect, Centered, stack, Stack, grid, cell, Grid, GridCell, empty, Empty, breadcrumb, Breadcrumb, help_overlay, HelpOverlay, list, ListView, ListSelection, table, TableView, TableSelection, SortDirection, Form, FormField, FormEvent, FieldError, TextField, sanitize_paste, input, Input, InputView, progress, line_gauge, Progress, sparkline, bar_chart, line_chart, SparklineView, BarChartView, LineChartView, Series, text_viewer, TextViewer, scroll, Scroll, ScrollState, ElementKey, ScrollDirection, RenderCache, CachedView};
+    #[cfg(feature = "markdown")]
+    pub use crate::element::{markdown, Markdown};
+    #[cfg(feature = "code")]
+    pub use crate::element::{code, Code};
+    #[cfg(feature = "image")]
+    pub use crate::element::{image, Image, ImageProtocol};
+    pub use crate::keymap::{KeyBinding, KeyBindings, KeyChord, KeyConfig};
+    pub use crate::input_mode::{InputMode, ModalInput, ModeEvent};
+    pub use crate::terminal::{request_cursor_shape, CursorShape};
+    pub use crate::task::{BlockingTask, Debounce, IntervalHandle, TaskHandle, TaskHandleResult, TaskTracker};
+    pub use crate::clock::{Clock, RealClock, TestClock};
+    pub use crate::animation::{tween, Animation, Easing, FixedStep};
+    pub use crate::theme::Theme;
+    pub use crate::color::{AccessibilityMode, ColorDepth};
+    pub use crate::breakpoint::{Breakpoint, Breakpoints};
+    pub use crate::overlay::OverlayId;
+    pub use crate::palette::CommandPalette;
+    pub use crate::fuzzy::{fuzzy_match, highlight_matches};
     // Re-export commonly used ratatui types for convenience
     pub use ratatui::prelude::{Constraint, Direction, Rect, Frame, Color, Modifier, Style};
 }
@@ -0,0 +1,102 @@
+//! Components layered above the root: dialogs, pickers and toasts.
+//!
+//! Overlays are pushed with `AppContext::push_overlay` and drawn over the root
+//! in the order they were pushed, each into the whole screen (an overlay draws
+//! its own box, typically clearing the cells below it first). Key, mouse and
+//! paste events go to the topmost modal overlay instead of the root; passive
+//! overlays (`push_passive_overlay`) are only drawn, which suits toasts.
+//!
+//! ```ignore
+//! // In a page: open a confirmation dialog
+//! cx.push_overlay(ConfirmDialog::new("Discard changes?"));
+//!
+//! // In the dialog: close itself when answered
+//! fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+//!     if let Event::Key(key) = event {
+//!         if key.code == KeyCode::Esc {
+//!             cx.dismiss_overlay();
+//!         }
+//!     }
+//!     None
+//! }
+//! ```
+
+use crate::component::traits::AnyComponent;
+use crate::state::Entity;
+
+/// Identifies an overlay pushed with `AppContext::push_overlay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OverlayId(u64);
+
+struct OverlayEntry {
+    id: OverlayId,
+    component: Entity<dyn AnyComponent>,
+    /// Whether the overlay receives input.
+    modal: bool,
+}
+
+/// The overlays of an application, bottom to top.
+#[derive(Default)]
+pub(crate) struct OverlayStack {
+    entries: Vec<OverlayEntry>,
+    /// Overlays removed since the last event or frame, awaiting `on_exit`.
+    closed: Vec<Entity<dyn AnyComponent>>,
+    /// The overlay whose event handler is running, for `dismiss_overlay`.
+    handling: Option<OverlayId>,
+    next_id: u64,
+}
+
+impl OverlayStack {
+    pub(crate) fn push(&mut self, component: Entity<dyn AnyComponent>, modal: bool) -> OverlayId {
+        self.next_id += 1;
+        let id = OverlayId(self.next_id);
+        self.entries.push(OverlayEntry { id, component, modal });
+        id
+    }
+
+    /// Remove an overlay, returning whether it was open.
+    pub(crate) fn close(&mut self, id: OverlayId) -> bool {
+        match self.entries.iter().position(|entry| entry.id == id) {
+            Some(index) => {
+                let entry = self.entries.remove(index);
+                self.closed.push(entry.component);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn top(&self) -> Option<OverlayId> {
+        self.entries.last().map(|entry| entry.id)
+    }
+
+    /// The overlay that receives input, if any.
+    pub(crate) fn top_modal(&self) -> Option<(OverlayId, Entity<dyn AnyComponent>)> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.modal)
+            .map(|entry| (entry.id, Entity::clone(&entry.component)))
+    }
+
+    /// All open overlays, bottom to top.
+    pub(crate) fn components(&self) -> Vec<Entity<dyn AnyComponent>> {
+        self.entries.iter().map(|entry| Entity::clone(&entry.component)).collect()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn take_closed(&mut self) -> Vec<Entity<dyn AnyComponent>> {
+        std::mem::take(&mut self.closed)
+    }
+
+    pub(crate) fn handling(&self) -> Option<OverlayId> {
+        self.handling
+    }
+
+    pub(crate) fn set_handling(&mut self, id: Option<OverlayId>) {
+        self.handling = id;
+    }
+}
@@ -0,0 +1,266 @@
+//! A fuzzy-searchable list of commands, opened as an overlay.
+//!
+//! Commands are registered once with a name and what they do. The palette
+//! opens over the current page, filters the commands as the user types and
+//! runs the selected one on Enter:
+//!
+//! ```ignore
+//! let palette = CommandPalette::new()
+//!     .action("Go to Monitor", Action::Navigate("monitor".to_string()))
+//!     .command("Toggle theme", |cx| {
+//!         cx.next_theme();
+//!         None
+//!     });
+//! cx.enable_command_palette(palette, KeyCode::Char('p'), KeyModifiers::CONTROL);
+//! ```
+//!
+//! An action returned by a command is handled by the root like one returned by
+//! the page, so navigation and custom actions work as usual.
+
+use crate::application::{AppContext, Context, EventContext};
+use crate::component::traits::{Action, Component, Event};
+use crate::element::{centered, div, list, sanitize_paste, text, IntoElement, ListSelection};
+use crate::fuzzy::{fuzzy_match, highlight_matches};
+use crate::overlay::OverlayId;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use std::sync::Arc;
+
+/// What a command does when run: side effects on the application, and
+/// optionally an action for the root.
+type CommandFn = Arc<dyn Fn(&AppContext) -> Option<Action> + Send + Sync>;
+
+#[derive(Clone)]
+struct Command {
+    name: String,
+    run: CommandFn,
+}
+
+/// Most commands shown at once; the list scrolls for more.
+const MAX_VISIBLE: usize = 10;
+
+/// A command palette component. See the module documentation.
+///
+/// Clones share the registered commands, so opening a palette is cheap.
+#[derive(Clone)]
+pub struct CommandPalette {
+    commands: Arc<Vec<Command>>,
+    title: String,
+    query: String,
+    /// Indices into `commands` matching the query, best match first, with the
+    /// positions of the matched characters in the name.
+    matches: Vec<(usize, Vec<usize>)>,
+    /// Index into `matches`.
+    selected: usize,
+    selection: ListSelection,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self {
+            commands: Arc::new(Vec::new()),
+            title: " Commands ".to_string(),
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            selection: ListSelection::new(),
+        }
+    }
+
+    /// Register a command. Its closure runs after the palette has closed; the
+    /// action it returns, if any, goes to the root.
+    pub fn command<F>(mut self, name: impl Into<String>, run: F) -> Self
+    where
+        F: Fn(&AppContext) -> Option<Action> + Send + Sync + 'static,
+    {
+        Arc::make_mut(&mut self.commands).push(Command { name: name.into(), run: Arc::new(run) });
+        self.filter();
+        self
+    }
+
+    /// Register a command that returns `action`, e.g. a navigation.
+    pub fn action(self, name: impl Into<String>, action: Action) -> Self {
+        self.command(name, move |_| Some(action.clone()))
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Open the palette as a modal overlay, with an empty query.
+    pub fn open(&self, cx: &AppContext) -> OverlayId {
+        let mut palette = self.clone();
+        palette.set_query(String::new());
+        palette.selection = ListSelection::new();
+        cx.push_overlay(palette)
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Replace the query and filter the commands again.
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.filter();
+    }
+
+    /// Names of the commands matching the query, best match first.
+    pub fn matches(&self) -> Vec<&str> {
+        self.matches.iter().map(|(i, _)| self.commands[*i].name.as_str()).collect()
+    }
+
+    /// Name of the command Enter would run.
+    pub fn selected(&self) -> Option<&str> {
+        self.matches.get(self.selected).map(|(i, _)| self.commands[*i].name.as_str())
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i32, usize, Vec<usize>)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, command)| fuzzy_match(&self.query, &command.name).map(|(score, indices)| (score, i, indices)))
+            .collect();
+        // Stable, so equally good matches keep their registration order
+        scored.sort_by_key(|(score, ..)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(_, i, indices)| (i, indices)).collect();
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if self.matches.is_empty() {
+            return;
+        }
+        self.selected = if down {
+            (self.selected + 1) % self.matches.len()
+        } else {
+            (self.selected + self.matches.len() - 1) % self.matches.len()
+        };
+    }
+}
+
+impl Component for CommandPalette {
+    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+        let theme = crate::theme::current();
+        let matched = Style::default().fg(theme.primary).add_modifier(Modifier::BOLD);
+        let names: Vec<Line<'static>> = self
+            .matches
+            .iter()
+            .map(|(i, indices)| highlight_matches(&self.commands[*i].name, indices, Style::default().fg(theme.text), matched))
+            .collect();
+        let rows = names.len().clamp(1, MAX_VISIBLE) as u16;
+        self.selection.select((!names.is_empty()).then_some(self.selected));
+
+        let results = if names.is_empty() {
+            div().child(text("No matching commands").fg(theme.muted))
+        } else {
+            div().child(list(names).state(&self.selection).highlight_symbol("› "))
+        };
+        let body = div()
+            .border_all()
+            .title(self.title.clone())
+            .child(text(format!("> {}▏", self.query)).fg(theme.text).h(1))
+            .child(results);
+        // Borders, the query line and the results
+        centered(body, 60, rows + 3).clear()
+    }
+
+    fn handle_event(&mut self, event: Event, cx: &mut EventContext<Self>) -> Option<Action> {
+        match event {
+            Event::Key(key) => {
+                let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                match key.code {
+                    KeyCode::Esc => {
+                        cx.dismiss_overlay();
+                    }
+                    KeyCode::Enter => {
+                        cx.dismiss_overlay();
+                        let command = self.matches.get(self.selected).map(|(i, _)| &self.commands[*i])?;
+                        return (command.run)(cx);
+                    }
+                    KeyCode::Up => self.move_selection(false),
+                    KeyCode::Down | KeyCode::Tab => self.move_selection(true),
+                    KeyCode::Char('p') if ctrl => self.move_selection(false),
+                    KeyCode::Char('n') if ctrl => self.move_selection(true),
+                    KeyCode::Backspace => {
+                        let mut query = std::mem::take(&mut self.query);
+                        query.pop();
+                        self.set_query(query);
+                    }
+                    KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                        let query = format!("{}{c}", self.query);
+                        self.set_query(query);
+                    }
+                    _ => {}
+                }
+                None
+            }
+            Event::Paste(pasted) => {
+                let query = format!("{}{}", self.query, sanitize_paste(&pasted, true));
+                self.set_query(query);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn captures_key(&self, _key: &KeyEvent) -> bool {
+        // Every key is either typed into the query or handled by the palette
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use crate::state::Entity;
+
+    #[test]
+    fn typing_filters_by_fuzzy_match_and_enter_runs_the_selection() {
+        let ran = Entity::new(Vec::new());
+        let log = Entity::clone(&ran);
+        let mut palette = CommandPalette::new()
+            .action("Go to Monitor", Action::Navigate("monitor".to_string()))
+            .action("Go to Menu", Action::Navigate("menu".to_string()))
+            .command("Toggle theme", move |_| {
+                let _ = log.update(|ran| ran.push("theme"));
+                None
+            });
+        assert_eq!(palette.matches().len(), 3);
+
+        palette.set_query("gtm");
+        assert_eq!(palette.matches(), ["Go to Monitor", "Go to Menu", "Toggle theme"]);
+        palette.set_query("mon");
+        assert_eq!(palette.matches(), ["Go to Monitor"]);
+        palette.set_query("xyz");
+        assert!(palette.selected().is_none());
+
+        let (app, _rx) = AppContext::create(Arc::new(TestClock::new()));
+        let entity = Entity::new(CommandPalette::new());
+        let mut cx = Context::new(app, entity.downgrade());
+        let key = |code| Event::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        palette.set_query("");
+        for c in "th".chars() {
+            assert_eq!(palette.handle_event(key(KeyCode::Char(c)), &mut cx), None);
+        }
+        assert_eq!(palette.selected(), Some("Toggle theme"));
+        assert_eq!(palette.handle_event(key(KeyCode::Enter), &mut cx), None);
+        assert_eq!(ran.read(|ran| ran.clone()).unwrap(), ["theme"]);
+
+        palette.set_query("go");
+        palette.handle_event(key(KeyCode::Up), &mut cx);
+        assert_eq!(palette.selected(), Some("Go to Menu"));
+        let action = palette.handle_event(key(KeyCode::Enter), &mut cx);
+        assert_eq!(action, Some(Action::Navigate("menu".to_string())));
+    }
+}
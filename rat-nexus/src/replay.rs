@@ -0,0 +1,142 @@
+//! Event recording and replay.
+//!
+//! `Application::record_events` writes every input event to a JSON-lines file
+//! together with the frame it arrived on and its time since startup.
+//! `Application::replay` feeds such a file back into a component headlessly,
+//! which turns a manual reproduction into a regression test.
+//!
+//! ```ignore
+//! // Record while reproducing a bug by hand
+//! Application::new().record_events("session.jsonl").run(|cx| { ... })?;
+//!
+//! // Later, in a test
+//! let frames = Application::replay("session.jsonl", MyPage::default(), 80, 24)?;
+//! assert!(frames.last().unwrap().content().iter().any(|c| c.symbol() == "!"));
+//! ```
+
+use crate::component::traits::Event;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// A single recorded input event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Number of frames rendered before the event arrived.
+    pub frame: u64,
+    /// Milliseconds since the recording started.
+    pub elapsed_ms: u64,
+    pub event: Event,
+}
+
+/// Appends events to a JSON-lines recording file.
+pub struct EventRecorder {
+    writer: BufWriter<File>,
+    start: Instant,
+}
+
+impl EventRecorder {
+    /// Create (or truncate) the recording file. `start` is the instant elapsed times are measured from.
+    pub fn create(path: impl AsRef<Path>, start: Instant) -> crate::Result<Self> {
+        let file = File::create(path).map_err(|source| crate::Error::IoError { source })?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            start,
+        })
+    }
+
+    /// Write one event. Each line is flushed so a crash doesn't lose the events leading up to it.
+    pub fn record(&mut self, frame: u64, now: Instant, event: &Event) -> crate::Result<()> {
+        let recorded = RecordedEvent {
+            frame,
+            elapsed_ms: now.saturating_duration_since(self.start).as_millis() as u64,
+            event: event.clone(),
+        };
+        serde_json::to_writer(&mut self.writer, &recorded)
+            .map_err(|e| crate::Error::IoError { source: e.into() })?;
+        self.writer.write_all(b"\n")
+            .and_then(|_| self.writer.flush())
+            .map_err(|source| crate::Error::IoError { source })
+    }
+}
+
+/// Read a recording back. Blank lines are ignored.
+pub fn load_events(path: impl AsRef<Path>) -> crate::Result<Vec<RecordedEvent>> {
+    let file = File::open(path).map_err(|source| crate::Error::IoError { source })?;
+    let mut events = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|source| crate::Error::IoError { source })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str(&line)
+            .map_err(|e| crate::Error::IoError { source: e.into() })?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::Application;
+    use crate::component::traits::{Action, Component};
+    use crate::application::{Context, EventContext};
+    use crate::element::{text, IntoElement};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct Counter {
+        count: u32,
+    }
+
+    impl Component for Counter {
+        fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+            text(format!("count={}", self.count))
+        }
+
+        fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+            match event {
+                Event::Key(key) if key.code == KeyCode::Char('q') => Some(Action::Quit),
+                Event::Key(_) => {
+                    self.count += 1;
+                    None
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn recorded_session_replays_into_buffers() {
+        let path = std::env::temp_dir().join(format!("rat-nexus-replay-{}.jsonl", std::process::id()));
+        let start = Instant::now();
+        let mut recorder = EventRecorder::create(&path, start).unwrap();
+        recorder.record(1, start, &key('a')).unwrap();
+        recorder.record(2, start + Duration::from_millis(40), &key('b')).unwrap();
+        recorder.record(3, start + Duration::from_millis(80), &key('q')).unwrap();
+        recorder.record(4, start + Duration::from_millis(90), &key('c')).unwrap();
+        drop(recorder);
+
+        let loaded = load_events(&path).unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert_eq!(loaded[1].elapsed_ms, 40);
+
+        let frames = Application::replay(&path, Counter::default(), 20, 1).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        // Replay stops at the quit key, so the trailing event is never delivered
+        assert_eq!(frames.len(), 3);
+        let first: String = frames[0].content().iter().map(|c| c.symbol()).collect();
+        assert!(first.starts_with("count=1"));
+        let last: String = frames[2].content().iter().map(|c| c.symbol()).collect();
+        assert!(last.starts_with("count=2"));
+    }
+}
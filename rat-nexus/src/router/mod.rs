@@ -4,4 +4,4 @@
 
 pub mod traits;
 
-pub use traits::{Route, Router};
+pub use traits::{AppRoute, BuildWith, Route, Router};
@@ -22,36 +22,116 @@ pub type Route = String;
 /// ```
 #[derive(Debug, Clone)]
 pub struct Router<R: Clone + PartialEq> {
-    current: R,
-    history: Vec<R>,
+    /// The history followed by the current route; never empty.
+    stack: Vec<R>,
+    /// Routes left with `go_back`, the most recent last, see `forward`.
+    forward_stack: Vec<R>,
+    /// Oldest entries are dropped beyond this many, see `with_max_history`.
+    max_history: Option<usize>,
+    /// See `collapse_duplicates`.
+    collapse_duplicates: bool,
 }
 
 impl<R: Clone + PartialEq> Router<R> {
     /// Create a new router with the initial route.
     pub fn new(initial: R) -> Self {
         Self {
-            current: initial,
-            history: Vec::new(),
+            stack: vec![initial],
+            forward_stack: Vec::new(),
+            max_history: None,
+            collapse_duplicates: false,
         }
     }
 
+    /// Keep at most `max` routes to go back to, dropping the oldest first.
+    pub fn with_max_history(mut self, max: usize) -> Self {
+        self.max_history = Some(max);
+        self.trim_history();
+        self
+    }
+
+    /// Like `with_max_history`, for a router that is already in use, e.g. the
+    /// one of a long-running app; `None` lifts the limit. A shorter limit drops
+    /// the oldest routes right away.
+    pub fn set_history_limit(&mut self, max: Option<usize>) {
+        self.max_history = max;
+        self.trim_history();
+    }
+
+    /// Navigating to a route that is already in the history goes back to it
+    /// instead of pushing, so A → B → A leaves just A and going back from a
+    /// page never returns to it twice.
+    pub fn collapse_duplicates(mut self) -> Self {
+        self.collapse_duplicates = true;
+        self
+    }
+
     /// Get the current route.
     pub fn current(&self) -> &R {
-        &self.current
+        self.stack.last().expect("router stack is never empty")
     }
 
-    /// Navigate to a new route. The current route is pushed to history.
+    /// Navigate to a new route. The current route is pushed to history, and
+    /// the routes `forward` would return to are forgotten.
     pub fn navigate(&mut self, route: R) {
-        if self.current != route {
-            self.history.push(self.current.clone());
-            self.current = route;
+        if *self.current() == route {
+            return;
+        }
+        self.forward_stack.clear();
+        if self.collapse_duplicates {
+            if let Some(index) = self.stack.iter().position(|r| *r == route) {
+                self.stack.truncate(index + 1);
+                return;
+            }
         }
+        self.stack.push(route);
+        self.trim_history();
+    }
+
+    /// Replace the current route without pushing it to history.
+    pub fn replace(&mut self, route: R) {
+        if *self.current() == route {
+            return;
+        }
+        if self.collapse_duplicates {
+            if let Some(index) = self.stack.iter().position(|r| *r == route) {
+                self.stack.truncate(index + 1);
+                return;
+            }
+        }
+        *self.stack.last_mut().expect("router stack is never empty") = route;
+    }
+
+    /// Make `route` the current route with no history to go back to.
+    pub fn reset(&mut self, route: R) {
+        self.stack.clear();
+        self.forward_stack.clear();
+        self.stack.push(route);
+    }
+
+    fn trim_history(&mut self) {
+        if let Some(max) = self.max_history {
+            let excess = self.history_len().saturating_sub(max);
+            self.stack.drain(..excess);
+        }
+    }
+
+    /// The routes `go_back` returns to, oldest first.
+    pub fn history(&self) -> &[R] {
+        &self.stack[..self.stack.len() - 1]
+    }
+
+    /// The history followed by the current route, e.g. to show
+    /// "Menu › Settings › Network" with `element::breadcrumb`.
+    pub fn breadcrumb(&self) -> &[R] {
+        &self.stack
     }
 
     /// Go back to the previous route. Returns true if successful.
     pub fn go_back(&mut self) -> bool {
-        if let Some(prev) = self.history.pop() {
-            self.current = prev;
+        if self.can_go_back() {
+            let left = self.stack.pop().expect("checked by can_go_back");
+            self.forward_stack.push(left);
             true
         } else {
             false
@@ -60,17 +140,36 @@ impl<R: Clone + PartialEq> Router<R> {
 
     /// Check if there's history to go back to.
     pub fn can_go_back(&self) -> bool {
-        !self.history.is_empty()
+        self.stack.len() > 1
+    }
+
+    /// Return to the route last left with `go_back`, as long as nothing was
+    /// navigated to since. Returns true if successful.
+    pub fn forward(&mut self) -> bool {
+        match self.forward_stack.pop() {
+            Some(route) => {
+                self.stack.push(route);
+                self.trim_history();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if there's a route to go forward to.
+    pub fn can_go_forward(&self) -> bool {
+        !self.forward_stack.is_empty()
     }
 
     /// Get the history length.
     pub fn history_len(&self) -> usize {
-        self.history.len()
+        self.stack.len() - 1
     }
 
-    /// Clear the navigation history.
+    /// Clear the navigation history, both back and forward.
     pub fn clear_history(&mut self) {
-        self.history.clear();
+        self.stack.drain(..self.stack.len() - 1);
+        self.forward_stack.clear();
     }
 }
 
@@ -119,6 +218,46 @@ macro_rules! define_routes {
     };
 }
 
+/// Construction of a `define_app!` page from the dependencies shared by all pages.
+///
+/// Pages implementing `Default` get this for free and ignore the dependencies;
+/// implement it for pages that need shared state handed to them up front.
+///
+/// # Example
+/// ```ignore
+/// #[derive(Clone)]
+/// pub struct Deps {
+///     pub counter: Entity<u32>,
+/// }
+///
+/// impl BuildWith<Deps> for CounterPage {
+///     fn build_with(deps: &Deps) -> Self {
+///         Self { counter: Entity::clone(&deps.counter) }
+///     }
+/// }
+/// ```
+pub trait BuildWith<D> {
+    fn build_with(deps: &D) -> Self;
+}
+
+impl<D, T: Default> BuildWith<D> for T {
+    fn build_with(_deps: &D) -> Self {
+        T::default()
+    }
+}
+
+/// The `RootRoute` enum generated by `define_app!`, the only type of route
+/// `Action::navigate_to` accepts, so a route that doesn't exist is a compile error.
+///
+/// Sealed: only `define_app!` implements it.
+pub trait AppRoute: std::any::Any + Send + Sync + Copy + PartialEq + std::fmt::Debug + sealed::Sealed {}
+
+#[doc(hidden)]
+pub mod sealed {
+    /// Implemented by `define_app!` only; not part of the public API.
+    pub trait Sealed {}
+}
+
 /// Define an application with automatic routing and component dispatch.
 ///
 /// This macro generates a Root component that automatically handles:
@@ -126,10 +265,19 @@ macro_rules! define_routes {
 /// - Root struct with router and all page fields
 /// - Root::new(cx) with automatic page construction via Default trait
 /// - Complete Component implementation with routing and lifecycle dispatch
-/// - Navigation action handling
+/// - Navigation action handling, including `Action::Batch` (performed in order)
+/// - `Action::Custom` is returned unchanged for `AppContext::on_action` handlers
+/// - A help overlay listing the current page's `key_bindings()` and the described
+///   global bindings (see `AppContext::global_key_bindings`), toggled with `?`
+///
+/// Each page type can back only one route: the framework tracks page
+/// activity, messages and drawn areas per component type, so listing a type
+/// twice is a compile error.
 ///
 /// All components are created with Default::default() and can be customized
-/// in their on_mount() lifecycle method.
+/// in their on_mount() lifecycle method. To hand pages shared dependencies
+/// instead, declare the dependency type once with `deps=...`: `Root::new`
+/// then takes the dependencies and builds each page with `BuildWith::build_with`.
 ///
 /// Minimal syntax - just list the routes and page types!
 ///
@@ -153,6 +301,20 @@ macro_rules! define_routes {
 /// // In main.rs:
 /// // let root = Root::new();
 /// ```
+///
+/// With shared dependencies:
+/// ```ignore
+/// define_app! {
+///     #[Root(default=Counter, deps=Deps)]
+///     pub struct Root {
+///         Counter => counter: CounterPage,
+///         Stats => stats: StatsPage,
+///         About => about: AboutPage,
+///     }
+/// }
+///
+/// let root = Root::new(Deps { counter: cx.new_entity(0) });
+/// ```
 #[macro_export]
 macro_rules! define_app {
     // Syntax 1: Simple - just routes, first route is default
@@ -161,7 +323,7 @@ macro_rules! define_app {
             $route:ident => $field:ident : $page:ty
         ),* $(,)?
     ) => {
-        define_app!(@impl (Menu) $($route => $field : $page),*);
+        define_app!(@impl (Menu) () $($route => $field : $page),*);
     };
 
     // Syntax 2: Full - with #[Root(default=...)] attribute
@@ -173,11 +335,43 @@ macro_rules! define_app {
             ),* $(,)?
         }
     ) => {
-        define_app!(@impl ($default_route) $($route => $field : $page),*);
+        define_app!(@impl ($default_route) () $($route => $field : $page),*);
+    };
+
+    // Syntax 3: Full with shared dependencies handed to every page
+    (
+        #[Root(default=$default_route:ident, deps=$deps:ty)]
+        pub struct Root {
+            $(
+                $route:ident => $field:ident : $page:ty
+            ),* $(,)?
+        }
+    ) => {
+        define_app!(@impl ($default_route) ($deps) $($route => $field : $page),*);
     };
 
-    // Internal: actual implementation - takes default route and routes
-    (@impl ($default_route:ident) $($route:ident => $field:ident : $page:ty),*) => {
+    // Internal: the dependency type, `()` when none was declared
+    (@deps_ty ()) => { () };
+    (@deps_ty ($deps:ty)) => { $deps };
+
+    // Internal: the public constructor, taking the dependencies if there are any
+    (@new ()) => {
+        /// Create a new Root instance.
+        /// All pages are constructed using Default::default().
+        /// Customize components in their on_mount() lifecycle method.
+        pub fn new() -> Self {
+            Self::build(())
+        }
+    };
+    (@new ($deps:ty)) => {
+        /// Create a new Root instance, building every page from the shared dependencies.
+        pub fn new(deps: $deps) -> Self {
+            Self::build(deps)
+        }
+    };
+
+    // Internal: actual implementation - takes default route, dependencies and routes
+    (@impl ($default_route:ident) ($($deps:ty)?) $($route:ident => $field:ident : $page:ty),*) => {
         $crate::paste::paste! {
             use $crate::Component;
             // Generate RootRoute enum
@@ -192,6 +386,9 @@ macro_rules! define_app {
                 }
             }
 
+            impl $crate::router::traits::sealed::Sealed for RootRoute {}
+            impl $crate::AppRoute for RootRoute {}
+
             impl std::fmt::Display for RootRoute {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     match self {
@@ -223,20 +420,42 @@ macro_rules! define_app {
             // Generate Root struct
             pub struct Root {
                 router: $crate::Router<RootRoute>,
+                help: $crate::element::HelpState,
+                deps: define_app!(@deps_ty ($($deps)?)),
+                /// Whether the pages have been mounted; `on_mount` runs them only once.
+                mounted: bool,
+                /// Whether the current page has been entered and not exited since.
+                entered: bool,
                 $($field: $page),*
             }
 
+            // Page activity, messages and areas are tracked per page type, so two
+            // routes sharing a type would share them: reject that with conflicting impls.
+            const _: () = {
+                #[allow(dead_code)]
+                trait OneRoutePerPageType {}
+                $(impl OneRoutePerPageType for $page {})*
+            };
+
             impl Root {
-                /// Create a new Root instance.
-                /// All pages are constructed using Default::default().
-                /// Customize components in their on_mount() lifecycle method.
-                pub fn new() -> Self {
+                define_app!(@new ($($deps)?));
+
+                fn build(deps: define_app!(@deps_ty ($($deps)?))) -> Self {
                     Self {
                         router: $crate::Router::new(RootRoute::default()),
-                        $($field: <$page>::default()),*
+                        help: $crate::element::HelpState::new(),
+                        mounted: false,
+                        entered: false,
+                        $($field: <$page as $crate::router::BuildWith<_>>::build_with(&deps),)*
+                        deps,
                     }
                 }
 
+                /// The dependencies shared by all pages.
+                pub fn deps(&self) -> &define_app!(@deps_ty ($($deps)?)) {
+                    &self.deps
+                }
+
                 /// Get the current route
                 pub fn current_route(&self) -> &RootRoute {
                     self.router.current()
@@ -252,83 +471,222 @@ macro_rules! define_app {
                     self.router.go_back()
                 }
 
-                /// Helper: Call on_enter for the given route
+                /// The routes visited on the way to the current one, ending with it.
+                pub fn breadcrumb(&self) -> &[RootRoute] {
+                    self.router.breadcrumb()
+                }
+
+                /// Helper: Exit the current page and enter `route`, unless it is already shown
+                fn go_to(&mut self, route: RootRoute, cx: &mut $crate::EventContext<Self>) {
+                    let current = *self.router.current();
+                    if route != current {
+                        self.call_on_exit(current, cx);
+                        self.router.navigate(route);
+                        self.call_on_enter(route, cx);
+                    }
+                }
+
+                /// Helper: Call on_enter for the given route and resume its page tasks
                 fn call_on_enter(&mut self, route: RootRoute, cx: &mut $crate::Context<Self>) {
                     match route {
-                        $(RootRoute::$route => self.$field.on_enter(&mut cx.cast())),*
+                        $(RootRoute::$route => {
+                            cx.set_page_active::<$page>(true);
+                            self.$field.on_enter(&mut cx.cast());
+                        }),*
                     }
                 }
 
-                /// Helper: Call on_exit for the given route
+                /// Helper: Call on_exit for the given route and pause its page tasks
                 fn call_on_exit(&mut self, route: RootRoute, cx: &mut $crate::Context<Self>) {
                     match route {
-                        $(RootRoute::$route => self.$field.on_exit(&mut cx.cast())),*
+                        $(RootRoute::$route => {
+                            self.$field.on_exit(&mut cx.cast());
+                            cx.set_page_active::<$page>(false);
+                        }),*
                     }
                 }
             }
 
             impl $crate::Component for Root {
                 fn on_mount(&mut self, cx: &mut $crate::Context<Self>) {
+                    if std::mem::replace(&mut self.mounted, true) {
+                        return;
+                    }
+                    // Every page starts hidden; on_enter activates the current one
+                    $(cx.set_page_active::<$page>(false);)*
                     $(self.$field.on_mount(&mut cx.cast());)*
                 }
 
                 fn on_enter(&mut self, cx: &mut $crate::Context<Self>) {
-                    self.call_on_enter(*self.router.current(), cx);
+                    if !std::mem::replace(&mut self.entered, true) {
+                        self.call_on_enter(*self.router.current(), cx);
+                    }
                 }
 
                 fn on_exit(&mut self, cx: &mut $crate::Context<Self>) {
-                    self.call_on_exit(*self.router.current(), cx);
+                    if std::mem::replace(&mut self.entered, false) {
+                        self.call_on_exit(*self.router.current(), cx);
+                    }
                 }
 
                 fn on_shutdown(&mut self, cx: &mut $crate::Context<Self>) {
                     $(self.$field.on_shutdown(&mut cx.cast());)*
                 }
 
+                fn on_before_quit(&mut self, cx: &mut $crate::Context<Self>) -> bool {
+                    // The page on screen is the one that can ask for confirmation
+                    match self.router.current() {
+                        $(RootRoute::$route => self.$field.on_before_quit(&mut cx.cast())),*
+                    }
+                }
+
+                fn on_resize(&mut self, width: u16, height: u16, cx: &mut $crate::Context<Self>) {
+                    // Every page, so hidden pages are up to date when navigated to
+                    $(self.$field.on_resize(width, height, &mut cx.cast());)*
+                }
+
+                fn on_tick(&mut self, cx: &mut $crate::Context<Self>) {
+                    // Only the current page, like its page tasks
+                    match self.router.current() {
+                        $(RootRoute::$route => self.$field.on_tick(&mut cx.cast())),*
+                    }
+                }
+
                 fn render(&mut self, cx: &mut $crate::Context<Self>) -> impl $crate::IntoElement + 'static {
+                    // Every page, so tasks of hidden pages don't pile up messages
+                    $(cx.cast::<$page>().flush_messages(&mut self.$field);)*
+                    let page = match self.router.current() {
+                        $(RootRoute::$route => {
+                            cx.cast::<$page>().flush_observers(&mut self.$field);
+                            let element = Box::new($crate::IntoElement::into_element(self.$field.render(&mut cx.cast())));
+                            cx.cast::<$page>().track_area(element)
+                        }),*
+                    };
+                    if self.help.is_open() {
+                        let bindings = self.key_bindings().extend(cx.global_key_bindings());
+                        Box::new(self.help.overlay(bindings).over(page)) as Box<dyn $crate::Element>
+                    } else {
+                        page
+                    }
+                }
+
+                fn key_bindings(&self) -> $crate::KeyBindings {
+                    let bindings = match self.router.current() {
+                        $(RootRoute::$route => self.$field.key_bindings()),*
+                    };
+                    bindings.bind($crate::element::HelpState::TOGGLE_KEY, "Help")
+                }
+
+                fn captures_key(&self, key: &$crate::crossterm::event::KeyEvent) -> bool {
+                    // An open help overlay owns the keyboard
+                    if self.help.is_open() {
+                        return true;
+                    }
                     match self.router.current() {
-                        $(RootRoute::$route => Box::new(self.$field.render(&mut cx.cast()).into_element()) as Box<dyn $crate::Element>),*
+                        $(RootRoute::$route => self.$field.captures_key(key)),*
                     }
                 }
 
                 fn handle_event(&mut self, event: $crate::Event, cx: &mut $crate::EventContext<Self>) -> Option<$crate::Action> {
-                    let current = *self.router.current();
-                    let action = match current {
+                    // The help overlay is handled here so every page gets it for free
+                    if let $crate::Event::Key(key) = &event {
+                        // A page capturing the key (e.g. while typing) keeps it, even if it is `?`
+                        let captured = !self.help.is_open() && self.captures_key(key);
+                        let bindings = self.key_bindings().extend(cx.global_key_bindings());
+                        if !captured && self.help.handle_key(key, &bindings) {
+                            return None;
+                        }
+                    }
+
+                    let action = match self.router.current() {
                         $(RootRoute::$route => self.$field.handle_event(event, &mut cx.cast())),*
                     };
 
+                    match action {
+                        Some(action) => self.handle_action(action, cx),
+                        None => None,
+                    }
+                }
+
+                fn handle_action(&mut self, action: $crate::Action, cx: &mut $crate::EventContext<Self>) -> Option<$crate::Action> {
+                    let current = *self.router.current();
+
                     // Handle navigation actions with type-safe routing
-                    if let Some(action) = action {
-                        match &action {
-                            $crate::Action::Navigate(route_str) => {
-                                // Type-safe route parsing with clear error messages
-                                match route_str.parse::<RootRoute>() {
-                                    Ok(target_route) => {
-                                        // Exit current, enter new
+                    match &action {
+                        $crate::Action::Navigate(route_str) => {
+                            // Type-safe route parsing with clear error messages
+                            match route_str.parse::<RootRoute>() {
+                                Ok(target_route) => self.go_to(target_route, cx),
+                                Err(e) => {
+                                    eprintln!("Navigation error: {}", e);
+                                }
+                            }
+                            None
+                        }
+                        $crate::Action::NavigateTo(route) => {
+                            // Only another app's routes can fail here; printing would
+                            // corrupt the terminal, so they are dropped
+                            match route.downcast_ref::<RootRoute>() {
+                                Some(&target_route) => self.go_to(target_route, cx),
+                                None => debug_assert!(false, "{route:?} is not a RootRoute of this app"),
+                            }
+                            None
+                        }
+                        $crate::Action::Replace(route_str) | $crate::Action::Reset(route_str) => {
+                            // Like `Navigate`, the page left is exited and the new one entered;
+                            // only the history differs. Resetting to the current page only
+                            // clears the history, so it is neither exited nor entered.
+                            match route_str.parse::<RootRoute>() {
+                                Ok(target_route) => {
+                                    let changed = target_route != current;
+                                    if changed {
                                         self.call_on_exit(current, cx);
-                                        self.router.navigate(target_route);
-                                        self.call_on_enter(target_route, cx);
                                     }
-                                    Err(e) => {
-                                        eprintln!("Navigation error: {}", e);
+                                    if matches!(action, $crate::Action::Reset(_)) {
+                                        self.router.reset(target_route);
+                                    } else {
+                                        self.router.replace(target_route);
+                                    }
+                                    if changed {
+                                        self.call_on_enter(target_route, cx);
                                     }
                                 }
-                                None
+                                // Dropped in release builds; printing would corrupt the terminal
+                                Err(e) => debug_assert!(false, "{e}"),
                             }
-                            $crate::Action::Back => {
-                                // Exit current
+                            None
+                        }
+                        $crate::Action::Back => {
+                            // Without history the current page stays, so it isn't exited
+                            if self.router.can_go_back() {
                                 self.call_on_exit(current, cx);
-
-                                if self.router.go_back() {
-                                    // Enter previous
-                                    self.call_on_enter(*self.router.current(), cx);
+                                self.router.go_back();
+                                self.call_on_enter(*self.router.current(), cx);
+                            }
+                            None
+                        }
+                        $crate::Action::Quit => Some($crate::Action::Quit),
+                        $crate::Action::Noop => None,
+                        // Not ours to interpret: hand it to the application unchanged
+                        $crate::Action::Custom(_) => Some(action),
+                        $crate::Action::Batch(_) => {
+                            // Flattened actions are never batches, so this recurses only one level.
+                            // Actions we don't consume (custom ones, a final quit) are passed up.
+                            let mut unhandled = Vec::new();
+                            for action in action.flatten() {
+                                if let Some(action) = self.handle_action(action, cx) {
+                                    let quit = action.is_quit();
+                                    unhandled.push(action);
+                                    if quit {
+                                        break;
+                                    }
                                 }
-                                None
                             }
-                            $crate::Action::Quit => Some($crate::Action::Quit),
-                            $crate::Action::Noop => None,
+                            match unhandled.len() {
+                                0 => None,
+                                _ => Some($crate::Action::batch(unhandled)),
+                            }
                         }
-                    } else {
-                        None
                     }
                 }
             }
@@ -372,6 +730,286 @@ mod tests {
         assert_eq!(router.current(), &TestRoute::Home);
     }
 
+    mod shared_deps {
+        use crate::application::Context;
+        use crate::element::{text, IntoElement};
+        use crate::router::BuildWith;
+        use crate::state::Entity;
+
+        pub struct Deps {
+            pub clicks: Entity<u32>,
+        }
+
+        pub struct CounterPage {
+            pub clicks: Entity<u32>,
+        }
+
+        impl BuildWith<Deps> for CounterPage {
+            fn build_with(deps: &Deps) -> Self {
+                Self { clicks: Entity::clone(&deps.clicks) }
+            }
+        }
+
+        impl crate::Component for CounterPage {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+                text("counter")
+            }
+        }
+
+        pub struct StatsPage {
+            pub clicks: Entity<u32>,
+        }
+
+        impl BuildWith<Deps> for StatsPage {
+            fn build_with(deps: &Deps) -> Self {
+                Self { clicks: Entity::clone(&deps.clicks) }
+            }
+        }
+
+        impl crate::Component for StatsPage {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+                text("stats")
+            }
+        }
+
+        #[derive(Default)]
+        pub struct AboutPage;
+
+        impl crate::Component for AboutPage {
+            fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+                text("about")
+            }
+        }
+
+        define_app! {
+            #[Root(default=Counter, deps=Deps)]
+            pub struct Root {
+                Counter => counter: CounterPage,
+                Stats => stats: StatsPage,
+                About => about: AboutPage,
+            }
+        }
+
+        #[test]
+        fn pages_are_built_from_shared_dependencies() {
+            let mut root = Root::new(Deps { clicks: Entity::new(0) });
+            let _ = root.counter.clicks.update(|n| *n += 1);
+            assert_eq!(root.stats.clicks.read(|n| *n).unwrap(), 1);
+            assert_eq!(root.deps().clicks.entity_id(), root.stats.clicks.entity_id());
+            assert_eq!(*root.current_route(), RootRoute::Counter);
+            root.navigate(RootRoute::About);
+            assert_eq!(root.breadcrumb(), [RootRoute::Counter, RootRoute::About]);
+            assert!(root.go_back());
+        }
+    }
+
+    mod lifecycle {
+        use crate::application::{AppContext, Context};
+        use crate::clock::TestClock;
+        use crate::component::traits::Action;
+        use crate::element::{text, IntoElement};
+        use crate::router::BuildWith;
+        use crate::state::Entity;
+        use std::sync::Arc;
+
+        pub struct Log(Entity<Vec<String>>);
+
+        impl Log {
+            fn push(&self, page: &str, hook: &str) {
+                let _ = self.0.update(|log| log.push(format!("{page}.{hook}")));
+            }
+        }
+
+        macro_rules! logged_page {
+            ($name:ident) => {
+                pub struct $name(Log);
+
+                impl BuildWith<Log> for $name {
+                    fn build_with(log: &Log) -> Self {
+                        Self(Log(Entity::clone(&log.0)))
+                    }
+                }
+
+                impl crate::Component for $name {
+                    fn on_mount(&mut self, _cx: &mut Context<Self>) {
+                        self.0.push(stringify!($name), "mount");
+                    }
+
+                    fn on_enter(&mut self, _cx: &mut Context<Self>) {
+                        self.0.push(stringify!($name), "enter");
+                    }
+
+                    fn on_exit(&mut self, _cx: &mut Context<Self>) {
+                        self.0.push(stringify!($name), "exit");
+                    }
+
+                    fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+                        text(stringify!($name))
+                    }
+                }
+            };
+        }
+
+        logged_page!(Home);
+        logged_page!(Settings);
+
+        define_app! {
+            #[Root(default=Home, deps=Log)]
+            pub struct Root {
+                Home => home: Home,
+                Settings => settings: Settings,
+            }
+        }
+
+        #[test]
+        fn pages_mount_once_and_enters_pair_with_exits() {
+            let log = Entity::new(Vec::new());
+            let root = Entity::new(Root::new(Log(Entity::clone(&log))));
+            let (app, _rx) = AppContext::create(Arc::new(TestClock::new()));
+            let mut cx = Context::new(app, root.downgrade());
+            let _ = root.update(|root| {
+                root.on_mount(&mut cx);
+                root.on_mount(&mut cx);
+                root.on_enter(&mut cx);
+                root.on_enter(&mut cx);
+                // Back without history keeps the page, so it is not exited
+                root.handle_action(Action::Back, &mut cx);
+                root.handle_action(Action::Navigate("Settings".to_string()), &mut cx);
+                root.handle_action(Action::Navigate("Settings".to_string()), &mut cx);
+                root.handle_action(Action::Back, &mut cx);
+                root.on_exit(&mut cx);
+                root.on_exit(&mut cx);
+                assert!(!root.go_back());
+                assert_eq!(*root.current_route(), RootRoute::Home);
+                root.navigate(RootRoute::Settings);
+                assert_eq!(root.breadcrumb().len(), 2);
+                assert_eq!(root.deps().0.entity_id(), log.entity_id());
+            });
+            assert_eq!(
+                log.read(|log| log.clone()).unwrap(),
+                [
+                    "Home.mount", "Settings.mount", "Home.enter",
+                    "Home.exit", "Settings.enter",
+                    "Settings.exit", "Home.enter",
+                    "Home.exit",
+                ]
+            );
+        }
+
+        #[test]
+        fn typed_routes_navigate_like_route_names() {
+            let log = Entity::new(Vec::new());
+            let root = Entity::new(Root::new(Log(Entity::clone(&log))));
+            let (app, _rx) = AppContext::create(Arc::new(TestClock::new()));
+            let mut cx = Context::new(app, root.downgrade());
+            let _ = root.update(|root| {
+                root.handle_action(Action::navigate_to(RootRoute::Settings), &mut cx);
+                assert_eq!(*root.current_route(), RootRoute::Settings);
+                root.handle_action(Action::from("home"), &mut cx);
+                assert_eq!(root.breadcrumb(), [RootRoute::Home, RootRoute::Settings, RootRoute::Home]);
+            });
+            assert_eq!(Action::navigate_to(RootRoute::Home), Action::<String>::navigate_to(RootRoute::Home));
+            assert_ne!(Action::navigate_to(RootRoute::Home), Action::<String>::navigate_to(RootRoute::Settings));
+        }
+    }
+
+    #[test]
+    fn history_is_capped_and_revisits_collapse() {
+        use TestRoute::*;
+        let mut router = Router::new(Home).with_max_history(2);
+        for route in [Settings, Profile, Home, Settings] {
+            router.navigate(route);
+        }
+        assert_eq!(router.history(), [Profile, Home]);
+
+        let mut router = Router::new(Home).collapse_duplicates();
+        for route in [Settings, Home, Settings, Profile, Settings] {
+            router.navigate(route);
+        }
+        assert_eq!((router.current(), router.history()), (&Settings, &[Home][..]));
+        assert!(router.go_back());
+        assert!(!router.go_back());
+
+        let mut router = Router::new(Home);
+        router.navigate(Settings);
+        router.navigate(Profile);
+        assert_eq!(router.breadcrumb(), [Home, Settings, Profile]);
+        router.clear_history();
+        assert_eq!(router.breadcrumb(), [Profile]);
+    }
+
+    #[test]
+    fn replace_swaps_the_current_route_and_reset_clears_history() {
+        use TestRoute::*;
+        let mut router = Router::new(Home);
+        router.navigate(Profile);
+        router.replace(Settings);
+        assert_eq!(router.breadcrumb(), [Home, Settings]);
+        assert!(router.go_back());
+        assert_eq!(router.current(), &Home);
+
+        router.navigate(Settings);
+        router.reset(Profile);
+        assert_eq!(router.breadcrumb(), [Profile]);
+        assert!(!router.can_go_back());
+    }
+
+    #[test]
+    fn history_stays_within_the_limit_over_many_navigations() {
+        let mut router = Router::new(0);
+        router.set_history_limit(Some(5));
+        for route in 1..=1000 {
+            router.navigate(route);
+            assert!(router.history_len() <= 5);
+        }
+        while router.go_back() {}
+        assert_eq!(router.current(), &995);
+
+        router.set_history_limit(None);
+        for route in 0..10 {
+            router.navigate(route);
+        }
+        assert_eq!(router.history_len(), 10);
+        router.set_history_limit(Some(2));
+        assert_eq!(router.history(), [7, 8]);
+    }
+
+    #[test]
+    fn forward_returns_to_routes_left_with_back() {
+        use TestRoute::*;
+        let mut router = Router::new(Home);
+        router.navigate(Settings);
+        router.navigate(Profile);
+        assert!(router.go_back() && router.go_back());
+        assert!(router.can_go_forward());
+        assert!(router.forward());
+        assert_eq!(router.breadcrumb(), [Home, Settings]);
+        assert!(router.forward());
+        assert_eq!(router.current(), &Profile);
+        assert!(!router.forward());
+
+        router.clear_history();
+        router.navigate(Home);
+        assert!(router.go_back());
+        router.clear_history();
+        assert!(!router.can_go_forward());
+    }
+
+    #[test]
+    fn navigating_after_back_drops_the_forward_routes() {
+        use TestRoute::*;
+        let mut router = Router::new(Home);
+        router.navigate(Settings);
+        assert!(router.go_back());
+        // Staying put keeps them; a new branch invalidates them
+        router.navigate(Home);
+        assert!(router.can_go_forward());
+        router.navigate(Profile);
+        assert!(!router.can_go_forward());
+        assert!(!router.forward());
+        assert_eq!(router.breadcrumb(), [Home, Profile]);
+    }
+
     #[test]
     fn test_router_no_duplicate_navigation() {
         let mut router = Router::new(TestRoute::Home);
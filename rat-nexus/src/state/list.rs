@@ -0,0 +1,202 @@
+use super::{Entity, EntityId};
+use tokio::sync::watch;
+
+/// An ordered collection of entities for dynamic child lists (messages, tabs, rows).
+///
+/// Every item is its own `Entity` with a stable `EntityId`, so items can be
+/// updated individually and looked up by id after other items were inserted
+/// or removed. Inserting, removing or reordering notifies the list's
+/// subscribers; updating an item notifies that item's subscribers.
+/// `Context::observe_list` subscribes to both.
+///
+/// Element trees are rebuilt every frame, so nothing needs to be reconciled
+/// when the list changes. Use the item ids as keys for any per-item view state
+/// you keep outside the list (scroll offsets, expanded flags) so it follows
+/// the item rather than its position.
+///
+/// # Example
+/// ```ignore
+/// fn on_mount(&mut self, cx: &mut Context<Self>) {
+///     self.tasks.track(cx.observe_list(&self.messages));
+/// }
+///
+/// fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+///     div().children(self.messages.map(|_, msg| text(msg.body.clone())))
+/// }
+///
+/// // Elsewhere
+/// let msg = self.messages.push(Message::new("hello"));
+/// self.messages.remove(msg.entity_id());
+/// ```
+pub struct EntityList<T: Send + Sync + 'static> {
+    items: Entity<Vec<Entity<T>>>,
+}
+
+impl<T: Send + Sync + 'static> EntityList<T> {
+    pub fn new() -> Self {
+        Self {
+            items: Entity::new(Vec::new()),
+        }
+    }
+
+    /// Append a value as a new entity and return its handle.
+    pub fn push(&self, value: T) -> Entity<T> {
+        let entity = Entity::new(value);
+        let _ = self.items.update(|items| items.push(Entity::clone(&entity)));
+        entity
+    }
+
+    /// Insert a value at `index` (clamped to the length) and return its handle.
+    pub fn insert(&self, index: usize, value: T) -> Entity<T> {
+        let entity = Entity::new(value);
+        let _ = self.items.update(|items| {
+            let index = index.min(items.len());
+            items.insert(index, Entity::clone(&entity));
+        });
+        entity
+    }
+
+    /// Remove the item with the given id, returning it if it was present.
+    pub fn remove(&self, id: EntityId) -> Option<Entity<T>> {
+        // Look the item up under the same lock, as clones may change the list in between
+        self.items
+            .update_with(|items| {
+                let removed = items.iter().position(|e| e.entity_id() == id).map(|index| items.remove(index));
+                let changed = removed.is_some();
+                (removed, changed)
+            })
+            .ok()
+            .flatten()
+    }
+
+    /// Move the item with the given id to `index` (clamped). Returns false if it isn't in the list.
+    pub fn move_to(&self, id: EntityId, index: usize) -> bool {
+        self.items
+            .update_with(|items| {
+                let Some(from) = items.iter().position(|e| e.entity_id() == id) else {
+                    return (false, false);
+                };
+                let item = items.remove(from);
+                let index = index.min(items.len());
+                items.insert(index, item);
+                (true, true)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Keep only the items for which `f` returns true.
+    pub fn retain<F>(&self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let _ = self.items.update(|items| {
+            items.retain(|entity| entity.read(&mut f).unwrap_or(false));
+        });
+    }
+
+    pub fn clear(&self) {
+        let _ = self.items.update(|items| items.clear());
+    }
+
+    /// Look up an item by id.
+    pub fn get(&self, id: EntityId) -> Option<Entity<T>> {
+        self.items
+            .read(|items| items.iter().find(|e| e.entity_id() == id).cloned())
+            .ok()
+            .flatten()
+    }
+
+    /// Current index of the item with the given id.
+    pub fn position(&self, id: EntityId) -> Option<usize> {
+        self.items
+            .read(|items| items.iter().position(|e| e.entity_id() == id))
+            .ok()
+            .flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.read(|items| items.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Snapshot of the item handles, in order.
+    pub fn entities(&self) -> Vec<Entity<T>> {
+        self.items.read(|items| items.clone()).unwrap_or_default()
+    }
+
+    /// Ids of the items, in order.
+    pub fn ids(&self) -> Vec<EntityId> {
+        self.items
+            .read(|items| items.iter().map(|e| e.entity_id()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Map every item to a value (typically an element), in order.
+    /// Items whose lock is poisoned are skipped.
+    pub fn map<R, F>(&self, mut f: F) -> Vec<R>
+    where
+        F: FnMut(EntityId, &T) -> R,
+    {
+        self.entities()
+            .iter()
+            .filter_map(|entity| entity.read(|value| f(entity.entity_id(), value)).ok())
+            .collect()
+    }
+
+    /// Subscribe to structural changes (insert, remove, move, clear).
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.items.subscribe()
+    }
+}
+
+impl<T: Send + Sync + 'static> Default for EntityList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Send + Sync + 'static> Clone for EntityList<T> {
+    /// Clones share the same underlying list.
+    fn clone(&self) -> Self {
+        Self {
+            items: Entity::clone(&self.items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_stay_stable_across_structural_changes() {
+        let list = EntityList::new();
+        let mut rx = list.subscribe();
+        let a = list.push("a");
+        let b = list.push("b");
+        let c = list.insert(0, "c");
+        assert!(rx.has_changed().unwrap());
+        rx.mark_unchanged();
+
+        assert_eq!(list.ids(), [c.entity_id(), a.entity_id(), b.entity_id()]);
+        assert!(list.remove(a.entity_id()).is_some());
+        assert!(list.remove(a.entity_id()).is_none());
+        assert!(list.move_to(c.entity_id(), 5));
+        assert!(rx.has_changed().unwrap());
+        rx.mark_unchanged();
+
+        // Ids that aren't in the list change nothing
+        assert!(!list.move_to(a.entity_id(), 0));
+        assert!(list.remove(a.entity_id()).is_none());
+        assert!(!rx.has_changed().unwrap());
+
+        assert_eq!(list.position(b.entity_id()), Some(0));
+        assert_eq!(list.map(|_, s| s.to_string()), ["b", "c"]);
+
+        list.retain(|s| *s != "b");
+        assert_eq!(list.ids(), [c.entity_id()]);
+    }
+}
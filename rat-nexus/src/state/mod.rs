@@ -1,11 +1,25 @@
+use std::cell::RefCell;
 use std::num::NonZeroU64;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, RwLock, Weak};
-use tokio::sync::watch;
+use std::sync::{Arc, LazyLock, RwLock, Weak};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+mod list;
+mod ring;
+mod type_map;
+pub use list::EntityList;
+pub use ring::RingBuffer;
+pub use type_map::TypeMap;
 
 /// Global counter for generating unique entity IDs.
 static NEXT_ENTITY_ID: AtomicU64 = AtomicU64::new(1);
 
+/// Number of entities created so far, for the debug overlay.
+pub(crate) fn entities_created() -> u64 {
+    NEXT_ENTITY_ID.load(Ordering::Relaxed) - 1
+}
+
 /// A unique identifier for an entity across the application.
 /// Guaranteed to be unique across the entire application lifetime.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -43,6 +57,99 @@ impl std::fmt::Display for EntityId {
     }
 }
 
+thread_local! {
+    /// Entities locked by this thread inside `read` or `update`, and whether for writing.
+    static HELD: RefCell<Vec<(EntityId, bool)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Entities whose notifications are held back by `batch`, with their senders.
+type Deferred = Vec<(EntityId, watch::Sender<u64>)>;
+
+thread_local! {
+    /// Entities changed inside `batch` on this thread, notified when it ends;
+    /// `None` outside a batch.
+    static DEFERRED: RefCell<Option<Deferred>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with change notifications on this thread held back until it
+/// returns, then notify each changed entity once. Nested batches flush with
+/// the outermost one. See `AppContext::batch`.
+pub(crate) fn batch<R>(f: impl FnOnce() -> R) -> R {
+    /// Flushes on drop, so a panic in `f` doesn't leave notifications held back.
+    struct Flush(bool);
+
+    impl Drop for Flush {
+        fn drop(&mut self) {
+            if !self.0 {
+                return; // Nested: the outermost batch flushes
+            }
+            let changed = DEFERRED.with(|deferred| deferred.borrow_mut().take()).unwrap_or_default();
+            for (_, tx) in changed {
+                tx.send_modify(|generation| *generation += 1);
+            }
+        }
+    }
+
+    let outermost = DEFERRED.with(|deferred| {
+        let mut deferred = deferred.borrow_mut();
+        let outermost = deferred.is_none();
+        if outermost {
+            *deferred = Some(Vec::new());
+        }
+        outermost
+    });
+    let _flush = Flush(outermost);
+    f()
+}
+
+/// Entities whose lock was found poisoned, for the debug overlay.
+static POISONED: LazyLock<std::sync::Mutex<std::collections::HashSet<EntityId>>> =
+    LazyLock::new(Default::default);
+
+/// Number of entities found poisoned so far, for the debug overlay.
+pub(crate) fn entities_poisoned() -> u64 {
+    POISONED.lock().map_or(0, |poisoned| poisoned.len() as u64)
+}
+
+/// The error for an entity whose lock is poisoned. The entity is also counted
+/// in the debug overlay, since callers often discard the error; printing it
+/// would corrupt the terminal.
+fn poisoned(id: EntityId) -> crate::Error {
+    if let Ok(mut poisoned) = POISONED.lock() {
+        poisoned.insert(id);
+    }
+    crate::Error::LockPoisoned
+}
+
+/// Records that this thread holds an entity's lock, until dropped.
+struct Held(EntityId);
+
+impl Held {
+    /// Fail with `Error::Reentrant` instead of deadlocking when this thread
+    /// already holds the lock and the two accesses can't overlap.
+    fn acquire(id: EntityId, write: bool) -> crate::Result<Self> {
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            if held.iter().any(|&(held_id, held_write)| held_id == id && (write || held_write)) {
+                return Err(crate::Error::Reentrant { id });
+            }
+            held.push((id, write));
+            Ok(Held(id))
+        })
+    }
+}
+
+impl Drop for Held {
+    fn drop(&mut self) {
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(index) = held.iter().rposition(|&(id, _)| id == self.0) {
+                held.remove(index);
+            }
+        });
+    }
+}
+
 /// Shared state wrapper with RwLock for efficient concurrent access.
 /// - Use read() for read-heavy workloads (no contention)
 /// - Use write() for mutations (exclusive access)
@@ -51,17 +158,28 @@ pub type SharedState<T> = Arc<RwLock<T>>;
 
 /// Entity handle, inspired by GPUI.
 /// Each entity has a unique ID and can be subscribed to for change notifications.
+///
+/// Notifications carry the entity's generation, which every update advances.
+/// They only hold the latest generation, so a burst of updates wakes a
+/// subscriber once, and comparing generations tells whether anything changed
+/// since the subscriber last looked.
+///
+/// # Panic safety
+/// If a closure passed to `update` panics, the value may be left half-updated
+/// and the lock is poisoned: from then on `read` and `update` fail with
+/// `Error::LockPoisoned`. Use `read_or_recover` to keep reading the value
+/// anyway, or `read_unwrap` where a poisoned state is a bug worth crashing on.
 pub struct Entity<T: ?Sized + Send + Sync> {
     id: EntityId,
     pub(crate) inner: SharedState<T>,
-    tx: watch::Sender<()>,
+    tx: watch::Sender<u64>,
 }
 
 /// A weak handle to an entity.
 pub struct WeakEntity<T: ?Sized + Send + Sync> {
     id: EntityId,
     pub(crate) inner: Weak<RwLock<T>>,
-    tx: watch::Sender<()>,
+    tx: watch::Sender<u64>,
 }
 
 impl<T: ?Sized + Send + Sync> Entity<T> {
@@ -71,14 +189,52 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
     }
 
     /// Update the inner value using a closure and notify subscribers.
+    ///
+    /// Subscribers are notified even if `f` leaves the value as it was; use
+    /// `update_if_changed` or `update_with` to skip the redraws for no-op updates.
+    ///
+    /// Fails with `Error::Reentrant` when called inside a `read` or `update`
+    /// closure of the same entity, which would otherwise deadlock.
     pub fn update<F, R>(&self, f: F) -> crate::Result<R>
     where
         F: FnOnce(&mut T) -> R,
     {
-        let mut guard = self.inner.write().map_err(|_| crate::Error::LockPoisoned)?;
-        let res = f(&mut *guard);
+        self.update_with(|value| (f(value), true))
+    }
+
+    /// Like `update`, but only notifies subscribers if the value differs
+    /// afterwards. Clones the value to compare, so prefer `update_with` for
+    /// large states.
+    pub fn update_if_changed<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        T: Clone + PartialEq,
+        F: FnOnce(&mut T) -> R,
+    {
+        self.update_with(|value| {
+            let before = value.clone();
+            let res = f(value);
+            let changed = *value != before;
+            (res, changed)
+        })
+    }
+
+    /// Like `update`, with `f` telling whether it changed anything: subscribers
+    /// are only notified if it returns true alongside its result.
+    ///
+    /// ```ignore
+    /// state.update_with(|s| (s.running, std::mem::replace(&mut s.running, false)))?;
+    /// ```
+    pub fn update_with<F, R>(&self, f: F) -> crate::Result<R>
+    where
+        F: FnOnce(&mut T) -> (R, bool),
+    {
+        let _held = Held::acquire(self.id, true)?;
+        let mut guard = self.inner.write().map_err(|_| poisoned(self.id))?;
+        let (res, changed) = f(&mut *guard);
         drop(guard);
-        let _ = self.tx.send(());
+        if changed {
+            self.notify();
+        }
         Ok(res)
     }
 
@@ -101,22 +257,57 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
     {
         let weak = self.downgrade();
         let mut cx = crate::Context::new(app.clone(), weak);
-        let mut guard = self.inner.write().map_err(|_| crate::Error::LockPoisoned)?;
+        let _held = Held::acquire(self.id, true)?;
+        let mut guard = self.inner.write().map_err(|_| poisoned(self.id))?;
         let res = f(&mut *guard, &mut cx);
         drop(guard);
-        let _ = self.tx.send(());
+        self.notify();
         Ok(res)
     }
 
     /// Read the inner value using a closure (non-blocking for concurrent readers).
+    ///
+    /// Reads may nest, but a read inside an `update` closure of the same entity
+    /// fails with `Error::Reentrant`.
     pub fn read<F, R>(&self, f: F) -> crate::Result<R>
     where
         F: FnOnce(&T) -> R,
     {
-        let guard = self.inner.read().map_err(|_| crate::Error::LockPoisoned)?;
+        let _held = Held::acquire(self.id, false)?;
+        let guard = self.inner.read().map_err(|_| poisoned(self.id))?;
         Ok(f(&*guard))
     }
 
+    /// Like `read`, but a poisoned lock is cleared and the value read as the
+    /// panicking update left it, instead of failing.
+    ///
+    /// # Panics
+    /// If called inside an `update` closure of the same entity.
+    pub fn read_or_recover<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let _held = Held::acquire(self.id, false).unwrap_or_else(|e| panic!("{e}"));
+        let guard = self.inner.read().unwrap_or_else(|e| {
+            poisoned(self.id);
+            self.inner.clear_poison();
+            e.into_inner()
+        });
+        f(&*guard)
+    }
+
+    /// Like `read`, for state that can't realistically be poisoned.
+    ///
+    /// # Panics
+    /// If the lock is poisoned or this is called inside an `update` closure of
+    /// the same entity.
+    pub fn read_unwrap<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.read(f).unwrap_or_else(|e| panic!("{e}"))
+    }
+
     /// Downgrade this entity to a weak handle.
     pub fn downgrade(&self) -> WeakEntity<T> {
         WeakEntity {
@@ -126,10 +317,163 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
         }
     }
 
-    /// Subscribe to changes of this entity.
-    pub fn subscribe(&self) -> watch::Receiver<()> {
+    /// Subscribe to changes of this entity. The received value is the
+    /// generation of the latest change, see `generation`.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
         self.tx.subscribe()
     }
+
+    /// Number of updates so far. Unchanged generation means unchanged value,
+    /// so a subscriber can remember the generation it last rendered and skip
+    /// the work when it hasn't advanced.
+    pub fn generation(&self) -> u64 {
+        *self.tx.borrow()
+    }
+
+    fn notify(&self) {
+        let deferred = DEFERRED.with(|deferred| match deferred.borrow_mut().as_mut() {
+            Some(changed) => {
+                if !changed.iter().any(|(id, _)| *id == self.id) {
+                    changed.push((self.id, watch::Sender::clone(&self.tx)));
+                }
+                true
+            }
+            None => false,
+        });
+        if !deferred {
+            // Unlike `send`, this also advances the generation when nobody subscribed
+            self.tx.send_modify(|generation| *generation += 1);
+        }
+    }
+
+    /// Number of strong handles (`Entity`) to the value, including this one.
+    /// The value is dropped when this reaches zero.
+    pub fn ref_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    /// Number of `WeakEntity` handles, e.g. held by contexts and spawned tasks.
+    pub fn weak_count(&self) -> usize {
+        Arc::weak_count(&self.inner)
+    }
+
+    /// Number of live change subscriptions (`subscribe`, `observe`, `throttle`, `subscribe_diff`).
+    ///
+    /// A count that keeps growing while navigating between pages usually means
+    /// observer tasks aren't being aborted on exit.
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+
+    /// Subscribe to changes, notified at most once per `interval`.
+    ///
+    /// The value itself is always current; only the notification cadence is limited.
+    /// The first change is reported immediately, and changes within the interval after
+    /// it are collapsed into a single notification at the end of the interval.
+    /// Use it for entities updated far more often than the screen needs to redraw.
+    /// Must be called from within a tokio runtime.
+    pub fn throttle(&self, interval: Duration) -> watch::Receiver<u64> {
+        let mut source = self.subscribe();
+        let (tx, rx) = watch::channel(self.generation());
+        tokio::spawn(async move {
+            let mut last_sent: Option<tokio::time::Instant> = None;
+            while source.changed().await.is_ok() {
+                if let Some(last) = last_sent {
+                    tokio::time::sleep_until(last + interval).await;
+                }
+                // Everything that changed while waiting is covered by this one notification
+                let generation = *source.borrow_and_update();
+                if tx.send(generation).is_err() {
+                    break; // All subscribers are gone
+                }
+                last_sent = Some(tokio::time::Instant::now());
+            }
+        });
+        rx
+    }
+
+    /// Subscribe to changes with the value from before and after each one, e.g.
+    /// to animate a counter from its old value to the new one or to log
+    /// "theme changed from Cyan to Green".
+    ///
+    /// Each change clones the value, so use `subscribe` when knowing that
+    /// something changed is enough. Changes made before the subscriber task
+    /// wakes up arrive as a single pair spanning all of them.
+    /// Must be called from within a tokio runtime.
+    pub fn subscribe_diff(&self) -> mpsc::UnboundedReceiver<(T, T)>
+    where
+        T: Clone + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let Ok(mut old) = self.read(T::clone) else {
+            return rx;
+        };
+        let mut source = self.subscribe();
+        // Not a `WeakEntity`: its sender would keep `source` open after the entity is gone
+        let weak = Arc::downgrade(&self.inner);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = source.changed() => if changed.is_err() { break },
+                    _ = tx.closed() => break, // All subscribers are gone
+                }
+                let Some(inner) = weak.upgrade() else {
+                    break; // The entity was dropped
+                };
+                let Ok(new) = inner.read().map(|value| value.clone()) else {
+                    break;
+                };
+                if tx.send((std::mem::replace(&mut old, new.clone()), new)).is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+
+    /// Create an entity holding a value derived from this one, e.g. the indices
+    /// of the log lines matching a filter, kept up to date as this one changes.
+    ///
+    /// `f` reruns after every change, but the derived entity is only updated,
+    /// and its subscribers only notified, when the result differs from the
+    /// value it holds. Components observing it thus skip the redraws for
+    /// changes that don't affect it. Updates made to the derived entity
+    /// directly last until the next change here.
+    /// Fails like `read` if this entity can't be read to compute the first value.
+    /// Must be called from within a tokio runtime.
+    ///
+    /// ```ignore
+    /// let errors = logs.map(|logs| logs.iter().filter(|l| l.level == Level::Error).count())?;
+    /// cx.observe(&errors);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> crate::Result<Entity<U>>
+    where
+        U: PartialEq + Send + Sync + 'static,
+        F: Fn(&T) -> U + Send + 'static,
+        T: 'static,
+    {
+        let derived = Entity::new(self.read(&f)?);
+        let target = derived.downgrade();
+        let mut source = self.subscribe();
+        // Not a `WeakEntity`: its sender would keep `source` open after the entity is gone
+        let weak = Arc::downgrade(&self.inner);
+        tokio::spawn(async move {
+            while source.changed().await.is_ok() {
+                let (Some(inner), true) = (weak.upgrade(), target.is_alive()) else {
+                    break; // The source or the derived entity was dropped
+                };
+                let Ok(value) = inner.read().map(|value| f(&value)) else {
+                    break;
+                };
+                drop(inner);
+                let Some(current) = target.upgrade() else { break };
+                if current.read(|current| *current != value).unwrap_or(false) {
+                    let _ = current.update(|current| *current = value);
+                }
+            }
+        });
+        Ok(derived)
+    }
 }
 
 impl<T: ?Sized + Send + Sync> WeakEntity<T> {
@@ -154,6 +498,46 @@ impl<T: ?Sized + Send + Sync> WeakEntity<T> {
     {
         self.upgrade().map(|entity| entity.update(f))
     }
+
+    /// Read the entity if it is still alive. A background loop can stop once
+    /// the entity is gone:
+    ///
+    /// ```ignore
+    /// let Some(paused) = weak.read(|s| s.paused) else { break };
+    /// ```
+    pub fn read<F, R>(&self, f: F) -> Option<crate::Result<R>>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.upgrade().map(|entity| entity.read(f))
+    }
+
+    /// Whether the entity is still alive, i.e. `upgrade` would succeed.
+    pub fn is_alive(&self) -> bool {
+        self.inner.strong_count() > 0
+    }
+
+    /// Run `f` with the upgraded entity, or return `None` if it was dropped.
+    ///
+    /// ```ignore
+    /// let len = weak.with(|logs| logs.read(|logs| logs.len())).unwrap_or(Ok(0))?;
+    /// ```
+    pub fn with<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&Entity<T>) -> R,
+    {
+        self.upgrade().map(|entity| f(&entity))
+    }
+
+    /// Update the entity if it is still alive and its lock isn't poisoned,
+    /// the usual case for background tasks that should stop quietly once the
+    /// page is gone. Use `update` or `upgrade` to tell the two apart.
+    pub fn update_if_alive<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.upgrade()?.update(f).ok()
+    }
 }
 
 impl<T: ?Sized + Send + Sync> Clone for Entity<T> {
@@ -179,7 +563,7 @@ impl<T: ?Sized + Send + Sync> Clone for WeakEntity<T> {
 impl<T: Send + Sync> Entity<T> {
     /// Create a new entity with the given initial value.
     pub fn new(value: T) -> Self {
-        let (tx, _) = watch::channel(());
+        let (tx, _) = watch::channel(0);
         Self {
             id: EntityId::next(),
             inner: Arc::new(RwLock::new(value)),
@@ -188,6 +572,33 @@ impl<T: Send + Sync> Entity<T> {
     }
 }
 
+/// A value built on first access, see `Entity::lazy`.
+///
+/// Derefs to the value, so methods can be called on it directly; assigning a
+/// whole new value takes an extra deref (`**data = ...`).
+pub type Lazy<T> = LazyLock<T, Box<dyn FnOnce() -> T + Send>>;
+
+impl<T: Send + Sync + 'static> Entity<Lazy<T>> {
+    /// Create an entity whose value is built by `init` on first `read` or `update`,
+    /// e.g. a data set only needed by a rarely visited page.
+    ///
+    /// `init` runs at most once, even if several threads access the entity at
+    /// the same time; the others wait for it. Subscribing doesn't build the value.
+    ///
+    /// ```ignore
+    /// let index = Entity::lazy(|| SearchIndex::load("data/"));
+    /// index.read(|index| index.query("rust"))?;
+    /// ```
+    pub fn lazy(init: impl FnOnce() -> T + Send + 'static) -> Self {
+        Entity::new(LazyLock::new(Box::new(init) as Box<dyn FnOnce() -> T + Send>))
+    }
+
+    /// Whether the value has been built yet.
+    pub fn is_initialized(&self) -> bool {
+        self.read(|lazy| LazyLock::get(lazy).is_some()).unwrap_or(false)
+    }
+}
+
 impl<T: Send + Sync + Default> Default for Entity<T> {
     /// Create a default entity with the default value of T.
     /// Useful for component initialization - replace with real entity in on_mount().
@@ -200,7 +611,7 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
     /// Create an entity from an existing Arc<RwLock<T>>.
     /// This is useful for creating Entity<dyn Trait> from coerced Arc types.
     pub fn from_arc(inner: Arc<RwLock<T>>) -> Self {
-        let (tx, _) = watch::channel(());
+        let (tx, _) = watch::channel(0);
         Self {
             id: EntityId::next(),
             inner,
@@ -208,3 +619,209 @@ impl<T: ?Sized + Send + Sync> Entity<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::timeout;
+
+    #[test]
+    fn counts_track_handles_and_subscriptions() {
+        let entity = Entity::new(0);
+        assert_eq!((entity.ref_count(), entity.weak_count(), entity.subscriber_count()), (1, 0, 0));
+
+        let clone = Entity::clone(&entity);
+        let weak = entity.downgrade();
+        let rx = entity.subscribe();
+        assert_eq!((entity.ref_count(), entity.weak_count(), entity.subscriber_count()), (2, 1, 1));
+
+        drop((clone, weak, rx));
+        assert_eq!((entity.ref_count(), entity.weak_count(), entity.subscriber_count()), (1, 0, 0));
+    }
+
+    #[test]
+    fn weak_handles_run_closures_only_while_the_entity_is_alive() {
+        let entity = Entity::new(1);
+        let weak = entity.downgrade();
+        assert!(weak.is_alive());
+        assert_eq!(weak.update_if_alive(|v| { *v += 1; *v }), Some(2));
+        assert_eq!(weak.with(|e| e.entity_id()), Some(entity.entity_id()));
+        assert_eq!(weak.read(|v| *v).unwrap().unwrap(), 2);
+
+        drop(entity);
+        assert!(!weak.is_alive());
+        assert_eq!(weak.update_if_alive(|v| *v), None);
+        assert_eq!(weak.with(|e| e.entity_id()), None);
+        assert!(weak.read(|v| *v).is_none());
+    }
+
+    #[tokio::test]
+    async fn throttle_collapses_notifications_within_interval() {
+        let entity = Entity::new(0);
+        let mut rx = entity.throttle(Duration::from_millis(60));
+
+        let _ = entity.update(|v| *v = 1);
+        timeout(Duration::from_millis(30), rx.changed())
+            .await
+            .expect("first change is reported immediately")
+            .unwrap();
+
+        for i in 2..6 {
+            let _ = entity.update(|v| *v = i);
+        }
+        assert!(timeout(Duration::from_millis(20), rx.changed()).await.is_err());
+        timeout(Duration::from_millis(200), rx.changed())
+            .await
+            .expect("burst is reported once the interval has passed")
+            .unwrap();
+        assert_eq!(entity.read(|v| *v).unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn bursts_of_updates_wake_subscribers_once_with_the_latest_generation() {
+        let counter = Entity::new(0);
+        assert_eq!(counter.generation(), 0);
+        counter.update(|v| *v += 1).unwrap();
+        let mut rx = counter.subscribe();
+        assert_eq!(*rx.borrow_and_update(), 1);
+
+        for _ in 0..100 {
+            counter.update(|v| *v += 1).unwrap();
+        }
+        rx.changed().await.unwrap();
+        let seen = *rx.borrow_and_update();
+        assert_eq!((seen, counter.generation()), (101, 101));
+        assert_eq!(counter.read(|v| *v).unwrap(), 101);
+        assert!(timeout(Duration::from_millis(20), rx.changed()).await.is_err());
+
+        // Reading doesn't advance the generation, so there is nothing to redo
+        counter.read(|_| ()).unwrap();
+        assert_eq!(counter.generation(), seen);
+    }
+
+    #[test]
+    fn nested_access_to_the_same_entity_fails_instead_of_deadlocking() {
+        let total = Entity::new(0);
+        let other = Entity::new(5);
+        let id = total.entity_id();
+
+        let nested = total.update(|_| total.update(|v| *v += 1)).unwrap();
+        assert!(matches!(nested, Err(crate::Error::Reentrant { id: nested_id }) if nested_id == id));
+        assert!(matches!(total.read(|_| total.update(|_| ())).unwrap(), Err(crate::Error::Reentrant { .. })));
+        assert!(matches!(total.update(|_| total.read(|_| ())).unwrap(), Err(crate::Error::Reentrant { .. })));
+
+        // Nested reads and other entities are fine, and the entity is usable afterwards
+        assert_eq!(total.read(|a| total.read(|b| a + b)).unwrap().unwrap(), 0);
+        total.update(|v| *v = other.read(|o| *o).unwrap()).unwrap();
+        assert_eq!(total.read(|v| *v).unwrap(), 5);
+    }
+
+    #[test]
+    fn lazy_entities_are_built_once_on_first_access() {
+        let builds = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counter = Arc::clone(&builds);
+        let data = Entity::lazy(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+            vec![1, 2, 3]
+        });
+        let _rx = data.subscribe();
+        assert!(!data.is_initialized());
+        assert_eq!(builds.load(Ordering::Relaxed), 0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| assert_eq!(data.read(|data| data.len()).unwrap(), 3));
+            }
+        });
+        data.update(|data| data.push(4)).unwrap();
+        assert_eq!(data.read(|data| data.iter().sum::<i32>()).unwrap(), 10);
+        assert!(data.is_initialized());
+        assert_eq!(builds.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn subscribe_diff_reports_old_and_new_values() {
+        let entity = Entity::new("Cyan".to_string());
+        let mut rx = entity.subscribe_diff();
+
+        let _ = entity.update(|v| *v = "Green".to_string());
+        let diff = timeout(Duration::from_millis(100), rx.recv()).await.unwrap();
+        assert_eq!(diff, Some(("Cyan".to_string(), "Green".to_string())));
+
+        let _ = entity.update(|v| *v = "Red".to_string());
+        let diff = timeout(Duration::from_millis(100), rx.recv()).await.unwrap();
+        assert_eq!(diff, Some(("Green".to_string(), "Red".to_string())));
+
+        drop(entity);
+        assert_eq!(timeout(Duration::from_millis(100), rx.recv()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn map_notifies_only_when_the_derived_value_changes() {
+        let logs = Entity::new(vec!["info: start".to_string()]);
+        let errors = logs.map(|logs| logs.iter().filter(|line| line.starts_with("error")).count()).unwrap();
+        let mut rx = errors.subscribe();
+
+        let _ = logs.update(|logs| logs.push("error: disk full".to_string()));
+        timeout(Duration::from_millis(100), rx.changed()).await.unwrap().unwrap();
+        assert_eq!(errors.read(|count| *count).unwrap(), 1);
+
+        // More lines, same count: the derived entity stays quiet
+        let _ = logs.update(|logs| logs.push("info: retry".to_string()));
+        assert!(timeout(Duration::from_millis(50), rx.changed()).await.is_err());
+        assert_eq!(errors.generation(), 1);
+    }
+
+    #[test]
+    fn no_op_updates_keep_the_generation() {
+        let entity = Entity::new(3);
+        entity.update_if_changed(|v| *v = 3).unwrap();
+        assert_eq!(entity.generation(), 0);
+        entity.update_if_changed(|v| *v = 4).unwrap();
+        assert_eq!(entity.generation(), 1);
+
+        assert!(!entity.update_with(|v| (*v > 10, false)).unwrap());
+        assert_eq!(entity.generation(), 1);
+        entity.update(|_| ()).unwrap();
+        assert_eq!(entity.generation(), 2);
+    }
+
+    #[test]
+    fn batch_notifies_each_changed_entity_once_at_the_end() {
+        let counter = Entity::new(0);
+        let log = Entity::new(Vec::new());
+        let rx = counter.subscribe();
+        batch(|| {
+            for i in 1..=3 {
+                counter.update(|c| *c = i).unwrap();
+                batch(|| log.update(|log| log.push(i)).unwrap());
+            }
+            assert!(!rx.has_changed().unwrap());
+            assert_eq!((counter.generation(), log.generation()), (0, 0));
+        });
+        assert!(rx.has_changed().unwrap());
+        assert_eq!((counter.generation(), log.generation()), (1, 1));
+    }
+
+    #[test]
+    fn poisoned_entities_can_be_recovered() {
+        let entity = Entity::new(vec![1]);
+        let writer = Entity::clone(&entity);
+        let panicked = std::thread::spawn(move || {
+            let _ = writer.update(|v| {
+                v.push(2);
+                panic!("update failed halfway");
+            });
+        })
+        .join();
+        assert!(panicked.is_err());
+        assert!(matches!(entity.read(|v| v.len()), Err(crate::Error::LockPoisoned)));
+        assert!(POISONED.lock().unwrap().contains(&entity.entity_id()));
+
+        // Recovering sees the half-done update and clears the poison
+        assert_eq!(entity.read_or_recover(|v| v.clone()), [1, 2]);
+        assert_eq!(entity.read_unwrap(|v| v.len()), 2);
+        entity.update(|v| v.push(3)).unwrap();
+        assert_eq!(entity.read(|v| v.len()).unwrap(), 3);
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+
+/// A fixed-capacity buffer that drops its oldest item when a new one is
+/// pushed while full, for logs, chat messages and metric histories.
+///
+/// Pushing never shifts the other items, unlike `Vec::remove(0)`, so keeping
+/// the last thousand lines of a stream costs the same per line as keeping the
+/// first. It is `Clone`, so it can be the state of an `Entity` or a field of it.
+///
+/// # Example
+/// ```ignore
+/// let logs = Entity::new(RingBuffer::new(1000));
+/// logs.update(|logs| logs.push(line))?;
+///
+/// // In render
+/// let recent = logs.read(|logs| logs.latest(20).cloned().collect::<Vec<_>>())?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RingBuffer<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    /// Create an empty buffer holding at most `capacity` items.
+    ///
+    /// # Panics
+    /// If `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "RingBuffer capacity must be at least 1");
+        Self {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Append an item, returning the oldest one if the buffer was full.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let evicted = if self.items.len() == self.capacity { self.items.pop_front() } else { None };
+        self.items.push_back(item);
+        evicted
+    }
+
+    /// The item at `index`, counting from the oldest.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
+    }
+
+    /// The most recently pushed item.
+    pub fn last(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    /// Iterate from the oldest item to the newest.
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Iterate over the newest `n` items (or all of them if there are fewer),
+    /// from the oldest of those to the newest.
+    pub fn latest(&self, n: usize) -> std::collections::vec_deque::Iter<'_, T> {
+        self.items.range(self.items.len().saturating_sub(n)..)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Whether the next push will evict the oldest item.
+    pub fn is_full(&self) -> bool {
+        self.items.len() == self.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pushing_past_capacity_evicts_the_oldest() {
+        let mut ring = RingBuffer::new(3);
+        assert_eq!(ring.push(1), None);
+        ring.extend([2, 3]);
+        assert!(ring.is_full());
+        assert_eq!(ring.push(4), Some(1));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(ring.latest(2).copied().collect::<Vec<_>>(), [3, 4]);
+        assert_eq!(ring.latest(10).count(), 3);
+        assert_eq!((ring.get(0), ring.last(), ring.len()), (Some(&2), Some(&4), 3));
+
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(ring.capacity(), 3);
+    }
+}
@@ -0,0 +1,109 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// A thread-safe map holding at most one value per type.
+///
+/// This backs `AppContext::set`/`get`: shared services such as a config, an
+/// auth token or an `Entity` of shared state are stored once and fetched by
+/// type from any page. Values are cloned out on `get`, so store cheap handles
+/// (`Entity<T>`, `Arc<T>`) rather than large values.
+#[derive(Default)]
+pub struct TypeMap {
+    values: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl TypeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `value`, replacing and returning the previous value of the same type.
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> Option<T> {
+        let mut values = self.values.write().unwrap_or_else(|e| e.into_inner());
+        values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast::<T>().ok())
+            .map(|old| *old)
+    }
+
+    /// A clone of the stored `T`.
+    pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        let values = self.values.read().unwrap_or_else(|e| e.into_inner());
+        values.get(&TypeId::of::<T>()).and_then(|value| value.downcast_ref::<T>()).cloned()
+    }
+
+    /// A clone of the stored `T`, inserting `f()` first if there is none.
+    ///
+    /// The check and the insert happen under one lock, so concurrent callers
+    /// all get the same value and `f` runs at most once.
+    pub fn get_or_insert_with<T, F>(&self, f: F) -> T
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        let mut values = self.values.write().unwrap_or_else(|e| e.into_inner());
+        values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()))
+            .downcast_ref::<T>()
+            .cloned()
+            .expect("values are stored under their own TypeId")
+    }
+
+    pub fn contains<T: 'static>(&self) -> bool {
+        let values = self.values.read().unwrap_or_else(|e| e.into_inner());
+        values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Take the stored `T` out of the map.
+    pub fn remove<T: Send + Sync + 'static>(&self) -> Option<T> {
+        let mut values = self.values.write().unwrap_or_else(|e| e.into_inner());
+        values.remove(&TypeId::of::<T>()).and_then(|value| value.downcast::<T>().ok()).map(|value| *value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.read().map(|values| values.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn one_value_per_type_and_initialization_runs_once() {
+        let map = TypeMap::new();
+        assert_eq!(map.insert(3_u32), None);
+        assert_eq!(map.insert(7_u32), Some(3));
+        assert_eq!(map.get::<u32>(), Some(7));
+        assert_eq!(map.get::<u64>(), None);
+
+        let map = Arc::new(map);
+        let inits = Arc::new(AtomicUsize::new(0));
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let (map, inits) = (Arc::clone(&map), Arc::clone(&inits));
+                std::thread::spawn(move || {
+                    map.get_or_insert_with(|| {
+                        inits.fetch_add(1, Ordering::Relaxed);
+                        format!("token-{i}")
+                    })
+                })
+            })
+            .collect();
+        let tokens: Vec<String> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+        assert_eq!(inits.load(Ordering::Relaxed), 1);
+        assert!(tokens.iter().all(|t| *t == tokens[0]));
+
+        assert_eq!(map.remove::<String>(), Some(tokens[0].clone()));
+        assert!(!map.contains::<String>());
+        assert_eq!(map.len(), 1);
+    }
+}
@@ -3,7 +3,14 @@
 //! Provides `TaskHandle` for cancellable async tasks and `TaskTracker` for
 //! managing multiple tasks that should be cancelled together (e.g., on component exit).
 
-use tokio::task::AbortHandle;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::task::{AbortHandle, JoinHandle};
 
 /// A handle to a spawned task that can be aborted.
 #[derive(Debug)]
@@ -28,6 +35,184 @@ impl TaskHandle {
     }
 }
 
+/// A repeating task from `AppContext::spawn_interval`, stopped when this handle
+/// is dropped or aborted.
+///
+/// Pass it to `TaskTracker::track` to hand the interval's lifetime over to the
+/// tracker instead.
+#[derive(Debug)]
+pub struct IntervalHandle {
+    /// `None` once handed over to a `TaskHandle`.
+    handle: Option<TaskHandle>,
+}
+
+impl IntervalHandle {
+    pub(crate) fn new(handle: TaskHandle) -> Self {
+        Self { handle: Some(handle) }
+    }
+
+    /// Stop the interval. A tick that is running finishes first.
+    pub fn abort(&self) {
+        if let Some(handle) = &self.handle {
+            handle.abort();
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().is_none_or(TaskHandle::is_finished)
+    }
+}
+
+impl Drop for IntervalHandle {
+    fn drop(&mut self) {
+        self.abort();
+    }
+}
+
+impl From<IntervalHandle> for TaskHandle {
+    /// A handle that no longer stops the interval when dropped, e.g. for `TaskTracker`.
+    fn from(mut interval: IntervalHandle) -> Self {
+        interval.handle.take().expect("only taken when converting")
+    }
+}
+
+/// CPU-bound work running on the blocking thread pool, see `AppContext::spawn_blocking`.
+///
+/// Await it for the closure's result; it fails with `Error::TaskError` if the
+/// closure panicked or the task was aborted before it started.
+#[derive(Debug)]
+pub struct BlockingTask<T> {
+    join_handle: JoinHandle<T>,
+}
+
+impl<T> BlockingTask<T> {
+    pub(crate) fn new(join_handle: JoinHandle<T>) -> Self {
+        Self { join_handle }
+    }
+
+    /// A handle for `TaskTracker`. Aborting only cancels work that has not
+    /// started yet; a running closure finishes and its result is dropped.
+    pub fn handle(&self) -> TaskHandle {
+        TaskHandle::new(self.join_handle.abort_handle())
+    }
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = crate::Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.join_handle)
+            .poll(cx)
+            .map(|result| result.map_err(|_| crate::Error::TaskError))
+    }
+}
+
+/// An async task whose output can be awaited, see `AppContext::spawn_result`.
+///
+/// Await it for the output; it fails with `Error::Cancelled` if the task was
+/// aborted first, e.g. by a `TaskTracker` when the page was left, and with
+/// `Error::TaskError` if it panicked.
+#[derive(Debug)]
+pub struct TaskHandleResult<T> {
+    join_handle: JoinHandle<T>,
+}
+
+impl<T> TaskHandleResult<T> {
+    pub(crate) fn new(join_handle: JoinHandle<T>) -> Self {
+        Self { join_handle }
+    }
+
+    /// A handle for `TaskTracker`, to cancel the task with the others.
+    pub fn handle(&self) -> TaskHandle {
+        TaskHandle::new(self.join_handle.abort_handle())
+    }
+
+    /// Abort the task. Awaiting it then fails with `Error::Cancelled`.
+    pub fn abort(&self) {
+        self.join_handle.abort();
+    }
+
+    /// Whether the output (or the error) is ready, so awaiting won't wait.
+    pub fn is_finished(&self) -> bool {
+        self.join_handle.is_finished()
+    }
+}
+
+impl<T> Future for TaskHandleResult<T> {
+    type Output = crate::Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.join_handle).poll(cx).map(|result| {
+            result.map_err(|e| if e.is_cancelled() { crate::Error::Cancelled } else { crate::Error::TaskError })
+        })
+    }
+}
+
+/// Delayed calls that are pushed back each time they are requested again,
+/// e.g. to filter a list once the user stops typing. `AppContext::debounce`
+/// uses the application's one.
+///
+/// Calls are grouped by key: requesting a call cancels the one still waiting
+/// under the same key, so only the last of a quick burst runs. Clones share
+/// the pending calls.
+///
+/// # Example
+/// ```ignore
+/// // In handle_event, on each keystroke
+/// cx.debounce("filter", Duration::from_millis(150), move || {
+///     let _ = state.update(|s| s.recalc_filter());
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Debounce {
+    pending: Arc<Mutex<HashMap<u64, AbortHandle>>>,
+}
+
+impl Debounce {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `f` after `delay`, unless another call under `key` is requested
+    /// first. Must be called from within a tokio runtime.
+    pub fn call<K, F>(&self, key: K, delay: Duration, f: F)
+    where
+        K: Hash,
+        F: FnOnce() + Send + 'static,
+    {
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            f();
+        });
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.retain(|_, task| !task.is_finished());
+            if let Some(previous) = pending.insert(Self::slot(key), task.abort_handle()) {
+                previous.abort();
+            }
+        }
+    }
+
+    /// Cancel the call waiting under `key`, if any.
+    pub fn cancel<K: Hash>(&self, key: K) {
+        if let Some(task) = self.pending.lock().ok().and_then(|mut pending| pending.remove(&Self::slot(key))) {
+            task.abort();
+        }
+    }
+
+    /// Whether a call under `key` is still waiting to run.
+    pub fn is_pending<K: Hash>(&self, key: K) -> bool {
+        self.pending
+            .lock()
+            .is_ok_and(|pending| pending.get(&Self::slot(key)).is_some_and(|task| !task.is_finished()))
+    }
+
+    fn slot<K: Hash>(key: K) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
 /// A collection of task handles that can be cancelled together.
 ///
 /// Useful for components that spawn multiple background tasks that should
@@ -69,10 +254,10 @@ impl TaskTracker {
     }
 
     /// Track a task handle. The task will be aborted when `abort_all` is called.
-    pub fn track(&mut self, handle: TaskHandle) {
+    pub fn track(&mut self, handle: impl Into<TaskHandle>) {
         // Clean up finished tasks while adding new ones
         self.handles.retain(|h| !h.is_finished());
-        self.handles.push(handle);
+        self.handles.push(handle.into());
     }
 
     /// Abort all tracked tasks.
@@ -119,6 +304,37 @@ mod tests {
         assert!(task_handle.is_finished());
     }
 
+    #[tokio::test]
+    async fn result_tasks_return_their_output_or_cancelled() {
+        let task = TaskHandleResult::new(tokio::spawn(async { 6 * 7 }));
+        assert_eq!(task.await.unwrap(), 42);
+
+        let mut tracker = TaskTracker::new();
+        let task = TaskHandleResult::new(tokio::spawn(std::future::pending::<u32>()));
+        tracker.track(task.handle());
+        tracker.abort_all();
+        assert!(matches!(task.await, Err(crate::Error::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn debounce_runs_only_the_last_call_of_a_burst() {
+        let debounce = Debounce::new();
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        for query in ["r", "ru", "rus", "rust"] {
+            let runs = Arc::clone(&runs);
+            debounce.call("filter", Duration::from_millis(20), move || runs.lock().unwrap().push(query));
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let other = Arc::clone(&runs);
+        debounce.call("other", Duration::from_millis(20), move || other.lock().unwrap().push("other"));
+        debounce.cancel("other");
+        assert!(debounce.is_pending("filter"));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!debounce.is_pending("filter"));
+        assert_eq!(*runs.lock().unwrap(), ["rust"]);
+    }
+
     #[tokio::test]
     async fn test_task_tracker() {
         let mut tracker = TaskTracker::new();
@@ -0,0 +1,256 @@
+//! Terminal modes that components can change while the application runs.
+//!
+//! `AppContext` setters only record what is wanted; the run loop writes the
+//! differences to the terminal before drawing the next frame, and the teardown
+//! path resets everything regardless of what was requested.
+
+use crossterm::cursor::SetCursorStyle;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::queue;
+use crossterm::terminal::SetTitle;
+use ratatui::backend::Backend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Position;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use std::cell::Cell;
+use std::io::{self, Write};
+
+/// Shape of the text cursor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CursorShape {
+    /// Whatever the user configured in their terminal.
+    #[default]
+    Default,
+    Block,
+    BlinkingBlock,
+    Underline,
+    BlinkingUnderline,
+    /// A thin vertical bar, as used by text inputs.
+    Bar,
+    BlinkingBar,
+}
+
+impl CursorShape {
+    fn command(self) -> SetCursorStyle {
+        match self {
+            CursorShape::Default => SetCursorStyle::DefaultUserShape,
+            CursorShape::Block => SetCursorStyle::SteadyBlock,
+            CursorShape::BlinkingBlock => SetCursorStyle::BlinkingBlock,
+            CursorShape::Underline => SetCursorStyle::SteadyUnderScore,
+            CursorShape::BlinkingUnderline => SetCursorStyle::BlinkingUnderScore,
+            CursorShape::Bar => SetCursorStyle::SteadyBar,
+            CursorShape::BlinkingBar => SetCursorStyle::BlinkingBar,
+        }
+    }
+}
+
+thread_local! {
+    /// Cursor shape requested by an element in the frame being drawn.
+    static REQUESTED_SHAPE: Cell<Option<CursorShape>> = const { Cell::new(None) };
+}
+
+/// Request a cursor shape for the frame being drawn, typically next to
+/// `Frame::set_cursor_position` in a text input. Overrides
+/// `AppContext::set_cursor_style` for this frame only.
+pub fn request_cursor_shape(shape: CursorShape) {
+    REQUESTED_SHAPE.with(|requested| requested.set(Some(shape)));
+}
+
+/// Take the shape requested during the last frame, leaving none for the next.
+pub(crate) fn take_requested_cursor_shape() -> Option<CursorShape> {
+    REQUESTED_SHAPE.with(|requested| requested.take())
+}
+
+/// Terminal modes requested through `AppContext`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TerminalModes {
+    /// Whether the application receives mouse events. While off, the terminal
+    /// handles the mouse itself, e.g. to select and copy text.
+    pub(crate) mouse_capture: bool,
+    /// Window title, if the application set one.
+    pub(crate) title: Option<String>,
+    /// Cursor shape used when no element requests one.
+    pub(crate) cursor_shape: CursorShape,
+    /// Whether the cursor may be shown where an element places it.
+    pub(crate) cursor_visible: bool,
+}
+
+impl Default for TerminalModes {
+    fn default() -> Self {
+        Self {
+            mouse_capture: true,
+            title: None,
+            cursor_shape: CursorShape::Default,
+            cursor_visible: true,
+        }
+    }
+}
+
+/// Save the window title on the terminal's title stack (xterm `XTWINOPS`).
+/// Terminals without a title stack ignore it.
+const PUSH_TITLE: &str = "\x1b[22;0t";
+/// Restore the title saved by `PUSH_TITLE`.
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// The modes last written to the terminal, so only changes are sent.
+pub(crate) struct TerminalSync {
+    applied: TerminalModes,
+    /// Whether the title from before the application was saved.
+    title_saved: bool,
+    /// The cursor shape last written.
+    cursor_shape: CursorShape,
+}
+
+impl TerminalSync {
+    /// Start from the modes set up when the terminal was initialized.
+    pub(crate) fn new(applied: TerminalModes) -> Self {
+        Self {
+            applied,
+            title_saved: false,
+            cursor_shape: CursorShape::Default,
+        }
+    }
+
+    /// Write the commands that turn the applied modes into `wanted`.
+    pub(crate) fn apply(&mut self, wanted: &TerminalModes, out: &mut impl Write) -> io::Result<()> {
+        if wanted.mouse_capture != self.applied.mouse_capture {
+            if wanted.mouse_capture {
+                queue!(out, EnableMouseCapture)?;
+            } else {
+                queue!(out, DisableMouseCapture)?;
+            }
+        }
+        if wanted.title != self.applied.title {
+            if let Some(title) = &wanted.title {
+                if !self.title_saved {
+                    out.write_all(PUSH_TITLE.as_bytes())?;
+                    self.title_saved = true;
+                }
+                queue!(out, SetTitle(title))?;
+            }
+        }
+        self.applied = wanted.clone();
+        out.flush()
+    }
+
+    /// Write the cursor shape for the frame just drawn, if it changed.
+    pub(crate) fn apply_cursor_shape(&mut self, shape: CursorShape, out: &mut impl Write) -> io::Result<()> {
+        if shape != self.cursor_shape {
+            queue!(out, shape.command())?;
+            self.cursor_shape = shape;
+            out.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Put back the title the terminal had before the application changed it.
+    pub(crate) fn restore_title(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if std::mem::take(&mut self.title_saved) {
+            out.write_all(POP_TITLE.as_bytes())?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// A terminal on the normal screen with a one-line viewport at the cursor,
+/// for `print_to_scrollback`.
+pub(crate) fn scrollback_terminal<B: Backend>(backend: B) -> io::Result<Terminal<B>> {
+    Terminal::with_options(backend, TerminalOptions { viewport: Viewport::Inline(1) })
+}
+
+/// Print a drawn frame above the viewport of a `scrollback_terminal`, without
+/// its blank bottom rows, and leave the cursor on the line below it so shell
+/// output doesn't overwrite it. See `Application::keep_output_on_exit`.
+pub(crate) fn print_to_scrollback<B: Backend>(terminal: &mut Terminal<B>, frame: &Buffer) -> io::Result<()> {
+    let area = frame.area;
+    let height = (0..area.height)
+        .rev()
+        .find(|&y| (0..area.width).any(|x| frame[(area.x + x, area.y + y)] != Default::default()))
+        .map_or(0, |y| y + 1);
+    if height == 0 {
+        return Ok(());
+    }
+
+    terminal.insert_before(height, |buf| {
+        let target = buf.area;
+        for y in 0..height.min(target.height) {
+            for x in 0..area.width.min(target.width) {
+                buf[(target.x + x, target.y + y)] = frame[(area.x + x, area.y + y)].clone();
+            }
+        }
+    })?;
+    let below = terminal.get_frame().area();
+    terminal.set_cursor_position(Position::new(0, below.y))?;
+    terminal.show_cursor()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_changed_modes_are_written() {
+        let mut sync = TerminalSync::new(TerminalModes::default());
+        let mut out = Vec::new();
+        sync.apply(&TerminalModes::default(), &mut out).unwrap();
+        assert!(out.is_empty());
+
+        sync.apply(&TerminalModes { mouse_capture: false, ..TerminalModes::default() }, &mut out).unwrap();
+        assert!(String::from_utf8_lossy(&out).contains("\x1b[?1000l"));
+        out.clear();
+        sync.apply(&TerminalModes { mouse_capture: false, ..TerminalModes::default() }, &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn the_previous_title_is_saved_once_and_restored() {
+        let mut sync = TerminalSync::new(TerminalModes::default());
+        let mut out = Vec::new();
+        for title in ["rat-demo — Menu", "rat-demo — Monitor"] {
+            let modes = TerminalModes { title: Some(title.to_string()), ..TerminalModes::default() };
+            sync.apply(&modes, &mut out).unwrap();
+        }
+        sync.restore_title(&mut out).unwrap();
+        sync.restore_title(&mut out).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        assert_eq!(written.matches(PUSH_TITLE).count(), 1);
+        assert_eq!(written.matches(POP_TITLE).count(), 1);
+        assert!(written.contains("\x1b]0;rat-demo — Monitor\x07"));
+    }
+
+    #[test]
+    fn the_last_frame_is_printed_above_the_cursor_without_blank_rows() {
+        use ratatui::backend::TestBackend;
+
+        let mut frame = Buffer::empty(ratatui::layout::Rect::new(0, 0, 8, 4));
+        frame.set_string(0, 0, "done", ratatui::style::Style::default());
+        frame.set_string(0, 1, "3 items", ratatui::style::Style::default());
+
+        let mut backend = TestBackend::new(8, 5);
+        backend.set_cursor_position(Position::new(0, 1)).unwrap();
+        let mut scrollback = scrollback_terminal(backend).unwrap();
+        print_to_scrollback(&mut scrollback, &frame).unwrap();
+        let lines = crate::testing::buffer_lines(scrollback.backend().buffer());
+        assert_eq!(lines, ["", "done", "3 items", "", ""]);
+        assert_eq!(scrollback.backend_mut().get_cursor_position().unwrap(), Position::new(0, 3));
+    }
+
+    #[test]
+    fn a_requested_cursor_shape_lasts_one_frame() {
+        let mut sync = TerminalSync::new(TerminalModes::default());
+        let mut out = Vec::new();
+        let modes = TerminalModes { cursor_shape: CursorShape::Block, ..TerminalModes::default() };
+
+        request_cursor_shape(CursorShape::Bar);
+        let shape = take_requested_cursor_shape().unwrap_or(modes.cursor_shape);
+        sync.apply_cursor_shape(shape, &mut out).unwrap();
+        assert_eq!(String::from_utf8_lossy(&out), "\x1b[6 q");
+
+        out.clear();
+        let shape = take_requested_cursor_shape().unwrap_or(modes.cursor_shape);
+        sync.apply_cursor_shape(shape, &mut out).unwrap();
+        sync.apply_cursor_shape(shape, &mut out).unwrap();
+        assert_eq!(String::from_utf8_lossy(&out), "\x1b[2 q");
+    }
+}
@@ -0,0 +1,268 @@
+//! Snapshot assertions for rendered output.
+//!
+//! Snapshots are plain multi-line strings of the glyphs on screen; styles are
+//! ignored. Trailing spaces on each line and trailing blank lines don't matter,
+//! and a leading newline is skipped so snapshots can start on their own line:
+//!
+//! ```ignore
+//! #[test]
+//! fn counter_renders_its_value() {
+//!     assert_buffer_matches(Counter::new(3), 12, 2, "
+//! Count: 3
+//! [+] [-]
+//! ");
+//! }
+//! ```
+//!
+//! When a snapshot needs updating, the failure message contains the actual
+//! output ready to paste; `print_buffer` prints it on demand.
+//!
+//! To test how a component reacts to input, drive it with a `TestHarness`.
+
+use crate::component::traits::{Action, AnyComponent, Event};
+use crate::application::{self, AppContext, Application};
+use crate::clock::TestClock;
+use crate::state::Entity;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// The glyphs of each row of `buffer`, with trailing spaces removed.
+pub fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut line = String::new();
+            for x in area.left()..area.right() {
+                line.push_str(buffer[(x, y)].symbol());
+            }
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+/// Print the glyphs of `buffer`, e.g. to copy them into a snapshot.
+pub fn print_buffer(buffer: &Buffer) {
+    println!("{}", buffer_lines(buffer).join("\n"));
+}
+
+/// Normalize a snapshot string the same way `buffer_lines` normalizes a buffer.
+fn snapshot_lines(snapshot: &str) -> Vec<String> {
+    let snapshot = snapshot.strip_prefix('\n').unwrap_or(snapshot);
+    let mut lines: Vec<String> = snapshot.lines().map(|l| l.trim_end().to_string()).collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Assert that the glyphs of `buffer` match `expected`, ignoring styles.
+#[track_caller]
+pub fn assert_buffer_glyphs(buffer: &Buffer, expected: &str) {
+    let mut actual = buffer_lines(buffer);
+    while actual.last().is_some_and(|l| l.is_empty()) {
+        actual.pop();
+    }
+    let expected = snapshot_lines(expected);
+    if actual != expected {
+        let first_diff = actual.iter().zip(&expected).position(|(a, e)| a != e)
+            .unwrap_or(actual.len().min(expected.len()));
+        panic!(
+            "rendered output differs from the snapshot (first difference on line {})\n\
+             --- expected ---\n{}\n--- actual ---\n{}\n",
+            first_diff + 1,
+            expected.join("\n"),
+            actual.join("\n"),
+        );
+    }
+}
+
+/// Render `component` into a `width` x `height` buffer and compare its glyphs with `expected`.
+#[track_caller]
+pub fn assert_buffer_matches<C>(component: C, width: u16, height: u16, expected: &str)
+where
+    C: AnyComponent + 'static,
+{
+    let buffer = Application::render_headless(component, width, height)
+        .unwrap_or_else(|e| panic!("failed to render component: {e}"));
+    assert_buffer_glyphs(&buffer, expected);
+}
+
+/// A mounted component fed synthetic events and drawn without a terminal.
+///
+/// Events go through the same path as in `Application::run`, including global
+/// key bindings, overlays and mouse routing to elements. Time only moves with
+/// `advance`, and the component is shut down when the harness is dropped.
+///
+/// ```ignore
+/// let mut harness = TestHarness::new(Counter::new(0), 12, 2)?;
+/// assert_eq!(harness.key(KeyCode::Char('+'))?, None);
+/// assert_buffer_glyphs(harness.render()?, "Count: 1");
+/// assert_eq!(harness.key(KeyCode::Char('q'))?, Some(Action::Quit));
+/// ```
+pub struct TestHarness {
+    // An `Option` so `drop` can shut it down without waiting for endless tasks
+    runtime: Option<Runtime>,
+    app: AppContext,
+    root: Entity<dyn AnyComponent>,
+    terminal: Terminal<TestBackend>,
+    clock: TestClock,
+}
+
+impl TestHarness {
+    /// Mount `component` on a `width` x `height` screen.
+    pub fn new<C>(component: C, width: u16, height: u16) -> anyhow::Result<Self>
+    where
+        C: AnyComponent + 'static,
+    {
+        let runtime = Runtime::new().map_err(|e| anyhow::anyhow!("Failed to start tokio: {}", e))?;
+        let clock = TestClock::new();
+        let _guard = runtime.enter();
+        let (app, _re_render_rx) = AppContext::create(Arc::new(clock.clone()));
+        let root = Entity::from_arc(Arc::new(RwLock::new(component)) as Arc<RwLock<dyn AnyComponent>>);
+        let terminal = Terminal::new(TestBackend::new(width, height))?;
+        application::mount_root(&app, &root)?;
+        application::resize_root(&app, &root, width, height)?;
+        drop(_guard);
+        Ok(Self { runtime: Some(runtime), app, root, terminal, clock })
+    }
+
+    /// Deliver `event` and return the action the component answered with.
+    /// Tasks woken by it get a chance to run before this returns.
+    pub fn send(&mut self, event: Event) -> anyhow::Result<Option<Action>> {
+        if let Event::Resize(width, height) = event {
+            self.terminal.backend_mut().resize(width, height);
+        }
+        let (app, root) = (&self.app, &self.root);
+        self.runtime().block_on(async {
+            let action = application::dispatch_event(app, root, event);
+            tokio::task::yield_now().await;
+            action
+        })
+    }
+
+    /// Press `code` without modifiers, see `send`.
+    pub fn key(&mut self, code: KeyCode) -> anyhow::Result<Option<Action>> {
+        self.send(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    /// Draw a frame and return it. Elements hit by mouse events sent
+    /// afterwards are those of this frame.
+    pub fn render(&mut self) -> anyhow::Result<&Buffer> {
+        let _guard = self.runtime.as_ref().map(Runtime::enter);
+        let (app, root) = (&self.app, &self.root);
+        self.terminal.draw(|frame| application::render_root(app, root, frame))?;
+        Ok(self.terminal.backend().buffer())
+    }
+
+    /// Run the component's `on_tick` once, as `Application::with_tick_rate` would.
+    pub fn tick(&mut self) -> anyhow::Result<()> {
+        let _guard = self.runtime().enter();
+        application::tick_root(&self.app, &self.root)
+    }
+
+    /// Ask the component whether it may quit, as `Application::run` does on
+    /// `Action::Quit`; see `Component::on_before_quit`.
+    pub fn quit(&mut self) -> anyhow::Result<bool> {
+        let _guard = self.runtime().enter();
+        application::quit_confirmed(&self.app, &self.root)
+    }
+
+    /// Move the clock forward by `duration` and let the tasks waiting on it run.
+    pub fn advance(&mut self, duration: Duration) {
+        self.clock.advance(duration);
+        self.runtime().block_on(tokio::task::yield_now());
+    }
+
+    /// The application context the component runs in, e.g. to set globals.
+    pub fn app(&self) -> &AppContext {
+        &self.app
+    }
+
+    fn runtime(&self) -> &Runtime {
+        self.runtime.as_ref().expect("runtime is only taken on drop")
+    }
+}
+
+impl Drop for TestHarness {
+    fn drop(&mut self) {
+        let Some(runtime) = self.runtime.take() else { return };
+        runtime.block_on(async {
+            let _ = application::shutdown_root(&self.app, &self.root);
+            self.app.finish_cleanup(application::DEFAULT_SHUTDOWN_TIMEOUT).await;
+        });
+        runtime.shutdown_timeout(Duration::from_millis(100));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::{Context, EventContext};
+    use crate::component::Component;
+    use crate::element::{div, text, IntoElement};
+
+    struct Greeting;
+
+    impl Component for Greeting {
+        fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+            div().border_all().title("Hi").child(text("hello"))
+        }
+    }
+
+    #[test]
+    fn component_output_matches_snapshot() {
+        assert_buffer_matches(Greeting, 9, 3, "
+┌Hi─────┐
+│hello  │
+└───────┘
+");
+    }
+
+    #[test]
+    #[should_panic(expected = "first difference on line 2")]
+    fn mismatches_report_the_first_differing_line() {
+        assert_buffer_matches(Greeting, 9, 3, "
+┌Hi─────┐
+│goodbye│
+└───────┘
+");
+    }
+
+    struct Counter(u32);
+
+    impl Component for Counter {
+        fn render(&mut self, _cx: &mut Context<Self>) -> impl IntoElement + 'static {
+            text(format!("Count: {}", self.0))
+        }
+
+        fn on_tick(&mut self, _cx: &mut Context<Self>) {
+            self.0 += 10;
+        }
+
+        fn handle_event(&mut self, event: Event, _cx: &mut EventContext<Self>) -> Option<Action> {
+            match event {
+                Event::Key(key) if key.code == KeyCode::Char('q') => Some(Action::Quit),
+                Event::Key(_) => {
+                    self.0 += 1;
+                    None
+                }
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn harness_feeds_events_and_reports_actions() {
+        let mut harness = TestHarness::new(Counter(0), 12, 1).unwrap();
+        assert_buffer_glyphs(harness.render().unwrap(), "Count: 0");
+        assert_eq!(harness.key(KeyCode::Char('+')).unwrap(), None);
+        assert_eq!(harness.key(KeyCode::Up).unwrap(), None);
+        assert_buffer_glyphs(harness.render().unwrap(), "Count: 2");
+        harness.tick().unwrap();
+        assert_buffer_glyphs(harness.render().unwrap(), "Count: 12");
+        assert_eq!(harness.key(KeyCode::Char('q')).unwrap(), Some(Action::Quit));
+    }
+}
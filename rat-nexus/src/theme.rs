@@ -0,0 +1,146 @@
+//! Application-wide color themes.
+//!
+//! The active theme lives in an `Entity<Theme>` on the `AppContext`, so
+//! components can `observe` it and re-render when it changes:
+//!
+//! ```ignore
+//! fn on_mount(&mut self, cx: &mut Context<Self>) {
+//!     self.tasks.track(cx.observe(&cx.theme()));
+//! }
+//!
+//! fn render(&mut self, cx: &mut Context<Self>) -> impl IntoElement + 'static {
+//!     let theme = cx.theme().read(|t| t.clone()).unwrap_or_default();
+//!     div().border_all().border_style(Style::default().fg(theme.primary))
+//! }
+//! ```
+//!
+//! Built-in elements pick up the active theme while rendering, e.g. `Div` uses
+//! the `border` role for borders that weren't styled explicitly.
+
+use ratatui::style::Color;
+use std::cell::RefCell;
+
+/// A named palette of color roles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub name: String,
+    /// Accent for titles, selections and highlighted values.
+    pub primary: Color,
+    /// Secondary accent, e.g. key labels in hints.
+    pub accent: Color,
+    pub border: Color,
+    pub text: Color,
+    /// De-emphasized text such as captions and hints.
+    pub muted: Color,
+    pub background: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+}
+
+impl Theme {
+    /// The terminal's own colors with a cyan accent. This is the default theme.
+    pub fn terminal() -> Self {
+        Self {
+            name: "terminal".to_string(),
+            primary: Color::Cyan,
+            accent: Color::Yellow,
+            border: Color::Reset,
+            text: Color::Reset,
+            muted: Color::DarkGray,
+            background: Color::Reset,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+        }
+    }
+
+    /// A dark palette that doesn't depend on the terminal's color scheme.
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            primary: Color::Rgb(97, 175, 239),
+            accent: Color::Rgb(229, 192, 123),
+            border: Color::Rgb(92, 99, 112),
+            text: Color::Rgb(220, 223, 228),
+            muted: Color::Rgb(127, 132, 142),
+            background: Color::Rgb(40, 44, 52),
+            success: Color::Rgb(152, 195, 121),
+            warning: Color::Rgb(229, 192, 123),
+            error: Color::Rgb(224, 108, 117),
+        }
+    }
+
+    /// A light palette for light terminal backgrounds.
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            primary: Color::Rgb(64, 120, 242),
+            accent: Color::Rgb(152, 104, 1),
+            border: Color::Rgb(160, 161, 167),
+            text: Color::Rgb(56, 58, 66),
+            muted: Color::Rgb(120, 122, 128),
+            background: Color::Rgb(250, 250, 250),
+            success: Color::Rgb(80, 161, 79),
+            warning: Color::Rgb(193, 132, 1),
+            error: Color::Rgb(228, 86, 73),
+        }
+    }
+
+    /// All built-in themes, in the order `AppContext::next_theme` cycles through them.
+    pub fn builtin() -> Vec<Theme> {
+        vec![Self::terminal(), Self::dark(), Self::light()]
+    }
+
+    /// Copy this theme under a new name with a different primary color.
+    /// Handy for registering accent variants of a built-in palette.
+    pub fn with_primary(mut self, name: impl Into<String>, primary: Color) -> Self {
+        self.name = name.into();
+        self.primary = primary;
+        self
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::terminal()
+    }
+}
+
+thread_local! {
+    /// Snapshot of the active theme for the frame being drawn.
+    static CURRENT: RefCell<Option<Theme>> = const { RefCell::new(None) };
+}
+
+/// The theme of the frame currently being rendered.
+/// Outside of rendering (or without an application) this is the default theme.
+pub fn current() -> Theme {
+    CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+}
+
+/// Install the theme elements see while rendering the next frame.
+pub(crate) fn set_current(theme: Theme) {
+    CURRENT.with(|current| *current.borrow_mut() = Some(theme));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::AppContext;
+    use crate::clock::RealClock;
+    use std::sync::Arc;
+
+    #[test]
+    fn registered_themes_can_be_selected_and_cycled() {
+        let (cx, _rx) = AppContext::create(Arc::new(RealClock));
+        cx.register_theme(Theme::terminal().with_primary("green", Color::Green));
+        assert_eq!(cx.theme_names(), ["terminal", "dark", "light", "green"]);
+
+        assert!(cx.set_theme("green"));
+        assert_eq!(cx.theme().read(|t| t.primary).unwrap(), Color::Green);
+        assert!(!cx.set_theme("missing"));
+
+        cx.next_theme();
+        assert_eq!(cx.theme().read(|t| t.name.clone()).unwrap(), "terminal");
+    }
+}